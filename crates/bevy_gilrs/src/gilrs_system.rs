@@ -8,11 +8,8 @@ pub fn gilrs_event_startup_system(world: &mut World) {
     let world = world.cell();
     let gilrs = world.get_non_send::<Gilrs>().unwrap();
     let mut event = world.get_resource_mut::<Events<GamepadEventRaw>>().unwrap();
-    for (id, _) in gilrs.gamepads() {
-        event.send(GamepadEventRaw(
-            convert_gamepad_id(id),
-            GamepadEventType::Connected,
-        ));
+    for gamepad_event in connected_gamepads(&gilrs) {
+        event.send(gamepad_event);
     }
 }
 
@@ -22,37 +19,33 @@ pub fn gilrs_event_system(world: &mut World) {
     let mut event = world.get_resource_mut::<Events<GamepadEventRaw>>().unwrap();
     event.update();
     while let Some(gilrs_event) = gilrs.next_event() {
-        match gilrs_event.event {
-            EventType::Connected => {
-                event.send(GamepadEventRaw(
-                    convert_gamepad_id(gilrs_event.id),
-                    GamepadEventType::Connected,
-                ));
-            }
-            EventType::Disconnected => {
-                event.send(GamepadEventRaw(
-                    convert_gamepad_id(gilrs_event.id),
-                    GamepadEventType::Disconnected,
-                ));
-            }
-            EventType::ButtonChanged(gilrs_button, value, _) => {
-                if let Some(button_type) = convert_button(gilrs_button) {
-                    event.send(GamepadEventRaw(
-                        convert_gamepad_id(gilrs_event.id),
-                        GamepadEventType::ButtonChanged(button_type, value),
-                    ));
-                }
-            }
-            EventType::AxisChanged(gilrs_axis, value, _) => {
-                if let Some(axis_type) = convert_axis(gilrs_axis) {
-                    event.send(GamepadEventRaw(
-                        convert_gamepad_id(gilrs_event.id),
-                        GamepadEventType::AxisChanged(axis_type, value),
-                    ));
-                }
-            }
-            _ => (),
-        };
+        if let Some(gamepad_event) = convert_event(&gilrs_event) {
+            event.send(gamepad_event);
+        }
     }
     gilrs.inc();
 }
+
+/// A `Connected` event for each gamepad that is connected when gilrs starts.
+pub fn connected_gamepads(gilrs: &Gilrs) -> impl Iterator<Item = GamepadEventRaw> + '_ {
+    gilrs
+        .gamepads()
+        .map(|(id, _)| GamepadEventRaw(convert_gamepad_id(id), GamepadEventType::Connected))
+}
+
+/// Converts a gilrs event, if bevy has an equivalent for it.
+pub fn convert_event(gilrs_event: &gilrs::Event) -> Option<GamepadEventRaw> {
+    let gamepad = convert_gamepad_id(gilrs_event.id);
+    let event_type = match gilrs_event.event {
+        EventType::Connected => GamepadEventType::Connected,
+        EventType::Disconnected => GamepadEventType::Disconnected,
+        EventType::ButtonChanged(gilrs_button, value, _) => {
+            GamepadEventType::ButtonChanged(convert_button(gilrs_button)?, value)
+        }
+        EventType::AxisChanged(gilrs_axis, value, _) => {
+            GamepadEventType::AxisChanged(convert_axis(gilrs_axis)?, value)
+        }
+        _ => return None,
+    };
+    Some(GamepadEventRaw(gamepad, event_type))
+}
@@ -7,16 +7,27 @@ use bevy_utils::tracing::error;
 use gilrs::GilrsBuilder;
 use gilrs_system::{gilrs_event_startup_system, gilrs_event_system};
 
+pub use gilrs::Gilrs;
+pub use gilrs_system::{connected_gamepads, convert_event};
+
+/// Polls gamepads on the app thread. When the winit runner polls them on its own thread (the
+/// `gilrs` feature of `bevy_winit` together with `WinitConfig::poll_gamepads`), leave this
+/// plugin out.
 #[derive(Default)]
 pub struct GilrsPlugin;
 
+/// Creates a [Gilrs] configured the way bevy expects: without filters and state tracking,
+/// which are done by `bevy_input` instead.
+pub fn build_gilrs() -> Result<Gilrs, gilrs::Error> {
+    GilrsBuilder::new()
+        .with_default_filters(false)
+        .set_update_state(false)
+        .build()
+}
+
 impl Plugin for GilrsPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        match GilrsBuilder::new()
-            .with_default_filters(false)
-            .set_update_state(false)
-            .build()
-        {
+        match build_gilrs() {
             Ok(gilrs) => {
                 app.insert_non_send_resource(gilrs)
                     .add_startup_system_to_stage(
@@ -0,0 +1,167 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{keyboard::KeyCode, mouse::MouseButton, Input};
+use bevy_app::{AppBuilder, Plugin};
+use bevy_utils::tracing::warn;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A physical input that can trigger an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+/// Maps named actions to the inputs that trigger them.
+///
+/// Bindings can be loaded from and saved to a RON file, so user rebinds persist between runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InputBindings {
+    actions: BTreeMap<String, Vec<Binding>>,
+}
+
+/// An error that occurs when loading or saving [InputBindings].
+#[derive(Error, Debug)]
+pub enum InputBindingsError {
+    #[error("failed to access bindings file")]
+    Io(#[from] io::Error),
+    #[error("invalid bindings file")]
+    Ron(#[from] ron::Error),
+}
+
+impl InputBindings {
+    pub fn insert(&mut self, action: impl Into<String>, bindings: Vec<Binding>) {
+        self.actions.insert(action.into(), bindings);
+    }
+
+    pub fn get(&self, action: &str) -> &[Binding] {
+        self.actions.get(action).map_or(&[], |bindings| bindings)
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.actions.keys().map(|action| action.as_str())
+    }
+
+    pub fn pressed(
+        &self,
+        action: &str,
+        keys: &Input<KeyCode>,
+        mouse_buttons: &Input<MouseButton>,
+    ) -> bool {
+        self.get(action).iter().any(|binding| match *binding {
+            Binding::Key(key) => keys.pressed(key),
+            Binding::Mouse(button) => mouse_buttons.pressed(button),
+        })
+    }
+
+    pub fn just_pressed(
+        &self,
+        action: &str,
+        keys: &Input<KeyCode>,
+        mouse_buttons: &Input<MouseButton>,
+    ) -> bool {
+        self.get(action).iter().any(|binding| match *binding {
+            Binding::Key(key) => keys.just_pressed(key),
+            Binding::Mouse(button) => mouse_buttons.just_pressed(button),
+        })
+    }
+
+    /// Parses bindings from RON and validates them against `defaults`: actions unknown to
+    /// `defaults` are dropped and actions missing from the input fall back to their defaults.
+    pub fn from_ron_str(input: &str, defaults: &InputBindings) -> Result<Self, ron::Error> {
+        let mut loaded: InputBindings = ron::from_str(input)?;
+        loaded.actions.retain(|action, _| {
+            let known = defaults.actions.contains_key(action);
+            if !known {
+                warn!("Ignoring bindings for unknown action {:?}", action);
+            }
+            known
+        });
+        for (action, bindings) in defaults.actions.iter() {
+            loaded
+                .actions
+                .entry(action.clone())
+                .or_insert_with(|| bindings.clone());
+        }
+        Ok(loaded)
+    }
+
+    pub fn to_ron_string(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, Default::default())
+    }
+
+    /// Loads bindings from the RON file at `path`. See [InputBindings::from_ron_str].
+    pub fn load(
+        path: impl AsRef<Path>,
+        defaults: &InputBindings,
+    ) -> Result<Self, InputBindingsError> {
+        let input = std::fs::read_to_string(path)?;
+        Ok(Self::from_ron_str(&input, defaults)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), InputBindingsError> {
+        std::fs::write(path, self.to_ron_string()?)?;
+        Ok(())
+    }
+}
+
+/// Loads [InputBindings] from a file at startup, using the [InputBindings] resource present at
+/// that point as defaults. Falls back to the defaults if the file is missing or invalid.
+pub struct InputBindingsPlugin {
+    pub path: PathBuf,
+}
+
+impl Plugin for InputBindingsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let defaults = app
+            .world()
+            .get_resource::<InputBindings>()
+            .cloned()
+            .unwrap_or_default();
+        let bindings = match InputBindings::load(&self.path, &defaults) {
+            Ok(bindings) => bindings,
+            Err(InputBindingsError::Io(err)) if err.kind() == io::ErrorKind::NotFound => defaults,
+            Err(err) => {
+                warn!(
+                    "Using default bindings, failed to load {}: {:?}",
+                    self.path.display(),
+                    err
+                );
+                defaults
+            }
+        };
+        app.insert_resource(bindings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loading_falls_back_to_defaults() {
+        let mut defaults = InputBindings::default();
+        defaults.insert("jump", vec![Binding::Key(KeyCode::Space)]);
+        defaults.insert("fire", vec![Binding::Mouse(MouseButton::Left)]);
+
+        let loaded =
+            InputBindings::from_ron_str(r#"{ "jump": [Key(W)], "dance": [Key(D)] }"#, &defaults)
+                .unwrap();
+
+        assert_eq!(loaded.get("jump"), &[Binding::Key(KeyCode::W)]);
+        assert_eq!(loaded.get("fire"), &[Binding::Mouse(MouseButton::Left)]);
+        assert!(loaded.get("dance").is_empty());
+
+        let saved = loaded.to_ron_string().unwrap();
+        assert_eq!(
+            InputBindings::from_ron_str(&saved, &defaults).unwrap(),
+            loaded
+        );
+    }
+}
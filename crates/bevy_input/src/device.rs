@@ -0,0 +1,112 @@
+use crate::{keyboard::KeyCode, mouse::MouseScrollUnit, ElementState};
+use bevy_ecs::{event::EventReader, system::ResMut};
+use bevy_utils::HashSet;
+
+/// Identifies a physical input device, e.g. one of several mice.
+///
+/// Ids are assigned by the windowing backend and are only meaningful while the device is
+/// connected, a device that is reconnected may get a different id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceId(pub u64);
+
+/// An input device was connected or disconnected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceConnectionEvent {
+    pub device: DeviceId,
+    pub connected: bool,
+}
+
+/// A button of a pointing device was pressed or released, regardless of which window has
+/// focus. Button numbers are platform-specific.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawMouseButtonInput {
+    pub device: DeviceId,
+    pub button: u32,
+    pub state: ElementState,
+}
+
+/// A key of a keyboard was pressed or released, regardless of which window has focus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawKeyboardInput {
+    pub device: DeviceId,
+    pub scan_code: u32,
+    pub key_code: Option<KeyCode>,
+    pub state: ElementState,
+}
+
+/// The scroll wheel of a device was moved, regardless of which window has focus.
+#[derive(Debug, Clone)]
+pub struct RawMouseWheel {
+    pub device: DeviceId,
+    pub unit: MouseScrollUnit,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// The currently connected input devices, as far as the backend reports them.
+#[derive(Default, Debug)]
+pub struct InputDevices {
+    devices: HashSet<DeviceId>,
+}
+
+impl InputDevices {
+    pub fn contains(&self, device: &DeviceId) -> bool {
+        self.devices.contains(device)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DeviceId> + '_ {
+        self.devices.iter()
+    }
+}
+
+/// Updates the [InputDevices] resource with the latest [DeviceConnectionEvent]s
+pub fn input_device_system(
+    mut input_devices: ResMut<InputDevices>,
+    mut device_connection_events: EventReader<DeviceConnectionEvent>,
+) {
+    for event in device_connection_events.iter() {
+        if event.connected {
+            input_devices.devices.insert(event.device);
+        } else {
+            input_devices.devices.remove(&event.device);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::Events;
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+
+    #[test]
+    fn tracks_connected_devices() {
+        let mut world = World::default();
+        world.insert_resource(InputDevices::default());
+        world.insert_resource(Events::<DeviceConnectionEvent>::default());
+        let mut stage = SystemStage::single(input_device_system.system());
+        let mut send = |world: &mut World, device, connected| {
+            world
+                .get_resource_mut::<Events<DeviceConnectionEvent>>()
+                .unwrap()
+                .send(DeviceConnectionEvent {
+                    device: DeviceId(device),
+                    connected,
+                });
+            stage.run(world);
+        };
+
+        send(&mut world, 0, true);
+        send(&mut world, 1, true);
+        send(&mut world, 0, false);
+        let devices = world.get_resource::<InputDevices>().unwrap();
+        assert!(!devices.contains(&DeviceId(0)));
+        assert!(devices.contains(&DeviceId(1)));
+        assert_eq!(devices.iter().count(), 1);
+    }
+}
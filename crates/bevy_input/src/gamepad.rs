@@ -1,7 +1,7 @@
-use crate::{Axis, Input};
+use crate::{Axis, ElementState, Input};
 use bevy_app::{EventReader, EventWriter};
 use bevy_ecs::system::{Res, ResMut};
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -69,6 +69,49 @@ pub enum GamepadAxisType {
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct GamepadAxis(pub Gamepad, pub GamepadAxisType);
 
+/// The gamepads that are currently connected.
+#[derive(Debug, Default)]
+pub struct Gamepads {
+    gamepads: HashSet<Gamepad>,
+}
+
+impl Gamepads {
+    pub fn contains(&self, gamepad: Gamepad) -> bool {
+        self.gamepads.contains(&gamepad)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Gamepad> + '_ {
+        self.gamepads.iter()
+    }
+}
+
+/// An event that is sent when a gamepad is connected or disconnected.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadConnectionEvent {
+    pub gamepad: Gamepad,
+    pub connected: bool,
+}
+
+/// An event that is sent when a gamepad button is pressed or released, according to its
+/// [ButtonSettings].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadButtonInput {
+    pub button: GamepadButton,
+    pub state: ElementState,
+}
+
+/// An event that is sent when the value of a gamepad axis changed, after the dead zone and
+/// threshold of its [AxisSettings] have been applied.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadAxisChanged {
+    pub axis: GamepadAxis,
+    pub value: f32,
+}
+
+/// Configures how raw gamepad values are turned into input, e.g. the dead zones of the sticks.
 #[derive(Default, Debug)]
 pub struct GamepadSettings {
     pub default_button_settings: ButtonSettings,
@@ -201,12 +244,17 @@ impl ButtonAxisSettings {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn gamepad_event_system(
+    mut gamepads: ResMut<Gamepads>,
     mut button_input: ResMut<Input<GamepadButton>>,
     mut axis: ResMut<Axis<GamepadAxis>>,
     mut button_axis: ResMut<Axis<GamepadButton>>,
     mut raw_events: EventReader<GamepadEventRaw>,
     mut events: EventWriter<GamepadEvent>,
+    mut connection_events: EventWriter<GamepadConnectionEvent>,
+    mut button_input_events: EventWriter<GamepadButtonInput>,
+    mut axis_changed_events: EventWriter<GamepadAxisChanged>,
     settings: Res<GamepadSettings>,
 ) {
     button_input.clear();
@@ -214,6 +262,11 @@ pub fn gamepad_event_system(
         let (gamepad, event) = (event.0, &event.1);
         match event {
             GamepadEventType::Connected => {
+                gamepads.gamepads.insert(gamepad);
+                connection_events.send(GamepadConnectionEvent {
+                    gamepad,
+                    connected: true,
+                });
                 events.send(GamepadEvent(gamepad, event.clone()));
                 for button_type in ALL_BUTTON_TYPES.iter() {
                     let gamepad_button = GamepadButton(gamepad, *button_type);
@@ -225,6 +278,11 @@ pub fn gamepad_event_system(
                 }
             }
             GamepadEventType::Disconnected => {
+                gamepads.gamepads.remove(&gamepad);
+                connection_events.send(GamepadConnectionEvent {
+                    gamepad,
+                    connected: false,
+                });
                 events.send(GamepadEvent(gamepad, event.clone()));
                 for button_type in ALL_BUTTON_TYPES.iter() {
                     let gamepad_button = GamepadButton(gamepad, *button_type);
@@ -242,6 +300,10 @@ pub fn gamepad_event_system(
                     .filter(*value, axis.get(gamepad_axis))
                 {
                     axis.set(gamepad_axis, filtered_value);
+                    axis_changed_events.send(GamepadAxisChanged {
+                        axis: gamepad_axis,
+                        value: filtered_value,
+                    });
                     events.send(GamepadEvent(
                         gamepad,
                         GamepadEventType::AxisChanged(*axis_type, filtered_value),
@@ -262,12 +324,26 @@ pub fn gamepad_event_system(
                 }
 
                 let button_property = settings.get_button_settings(gamepad_button);
-                if button_input.pressed(gamepad_button) {
+                let state = if button_input.pressed(gamepad_button) {
                     if button_property.is_released(*value) {
-                        button_input.release(gamepad_button);
+                        Some(ElementState::Released)
+                    } else {
+                        None
                     }
                 } else if button_property.is_pressed(*value) {
-                    button_input.press(gamepad_button);
+                    Some(ElementState::Pressed)
+                } else {
+                    None
+                };
+                if let Some(state) = state {
+                    match state {
+                        ElementState::Pressed => button_input.press(gamepad_button),
+                        ElementState::Released => button_input.release(gamepad_button),
+                    }
+                    button_input_events.send(GamepadButtonInput {
+                        button: gamepad_button,
+                        state,
+                    });
                 }
             }
         }
@@ -1,13 +1,23 @@
-use crate::{ElementState, Input};
+use crate::{ElementState, Input, ReceivedAt};
 use bevy_app::EventReader;
 use bevy_ecs::system::ResMut;
 
 /// A key input event from a keyboard device
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyboardInput {
+    /// The raw, layout independent code of the physical key. Use it for bindings that depend on
+    /// the position of a key, like WASD movement on any layout.
     pub scan_code: u32,
     pub key_code: Option<KeyCode>,
     pub state: ElementState,
+    /// Whether this press was generated by the key being held down.
+    pub repeat: bool,
+    /// The text the key press produced with the current layout and modifiers, if any.
+    pub text: Option<String>,
+    /// When the windowing backend received the input, see [ReceivedAt].
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub received_at: ReceivedAt,
 }
 
 /// Updates the Input<KeyCode> resource with the latest KeyboardInput events
@@ -31,6 +41,39 @@ pub fn keyboard_input_system(
     }
 }
 
+/// Which modifier keys are held down. Either key of a left/right pair counts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifierKeysState {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// The Windows key on PC keyboards, the Command key on Mac keyboards.
+    pub logo: bool,
+}
+
+impl ModifierKeysState {
+    pub fn any(&self) -> bool {
+        self.shift || self.ctrl || self.alt || self.logo
+    }
+}
+
+/// An event that is sent when the backend reports a change of the held modifier keys
+#[derive(Debug, Clone)]
+pub struct KeyboardModifiers {
+    pub state: ModifierKeysState,
+}
+
+/// Updates the ModifierKeysState resource with the latest KeyboardModifiers event
+pub fn modifier_keys_system(
+    mut modifier_keys: ResMut<ModifierKeysState>,
+    mut keyboard_modifiers_events: EventReader<KeyboardModifiers>,
+) {
+    if let Some(event) = keyboard_modifiers_events.iter().last() {
+        *modifier_keys = event.state;
+    }
+}
+
 /// The key code of a keyboard input.
 #[derive(Debug, Hash, Ord, PartialOrd, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -1,4 +1,7 @@
 mod axis;
+#[cfg(feature = "bindings")]
+pub mod bindings;
+pub mod device;
 pub mod gamepad;
 mod input;
 pub mod keyboard;
@@ -13,14 +16,19 @@ use bevy_ecs::{
 };
 pub use input::*;
 
+/// When the windowing backend received an input event, carried by the `received_at` field of the
+/// input events. It is `None` for input that the OS did not report, e.g. scripted input. Compare
+/// it with `Instant::now()` to measure or compensate for the delay until the input is processed.
+pub type ReceivedAt = Option<bevy_utils::Instant>;
+
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         gamepad::{
             Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEvent,
-            GamepadEventType,
+            GamepadEventType, Gamepads,
         },
-        keyboard::KeyCode,
+        keyboard::{KeyCode, ModifierKeysState},
         mouse::MouseButton,
         touch::{TouchInput, Touches},
         Axis, Input,
@@ -28,13 +36,21 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
-use keyboard::{keyboard_input_system, KeyCode, KeyboardInput};
+use keyboard::{
+    keyboard_input_system, modifier_keys_system, KeyCode, KeyboardInput, KeyboardModifiers,
+    ModifierKeysState,
+};
 use mouse::{mouse_button_input_system, MouseButton, MouseButtonInput, MouseMotion, MouseWheel};
-use touch::{touch_screen_input_system, TouchInput, Touches};
+use touch::{touch_screen_input_system, PenInput, TouchInput, Touches};
+
+use device::{
+    input_device_system, DeviceConnectionEvent, InputDevices, RawKeyboardInput,
+    RawMouseButtonInput, RawMouseWheel,
+};
 
 use gamepad::{
-    gamepad_event_system, GamepadAxis, GamepadButton, GamepadEvent, GamepadEventRaw,
-    GamepadSettings,
+    gamepad_event_system, GamepadAxis, GamepadAxisChanged, GamepadButton, GamepadButtonInput,
+    GamepadConnectionEvent, GamepadEvent, GamepadEventRaw, GamepadSettings, Gamepads,
 };
 
 /// Adds keyboard and mouse input to an App
@@ -54,6 +70,12 @@ impl Plugin for InputPlugin {
                 CoreStage::PreUpdate,
                 keyboard_input_system.system().label(InputSystem),
             )
+            .add_event::<KeyboardModifiers>()
+            .init_resource::<ModifierKeysState>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                modifier_keys_system.system().label(InputSystem),
+            )
             // mouse
             .add_event::<MouseButtonInput>()
             .add_event::<MouseMotion>()
@@ -66,6 +88,10 @@ impl Plugin for InputPlugin {
             // gamepad
             .add_event::<GamepadEvent>()
             .add_event::<GamepadEventRaw>()
+            .add_event::<GamepadConnectionEvent>()
+            .add_event::<GamepadButtonInput>()
+            .add_event::<GamepadAxisChanged>()
+            .init_resource::<Gamepads>()
             .init_resource::<GamepadSettings>()
             .init_resource::<Input<GamepadButton>>()
             .init_resource::<Axis<GamepadAxis>>()
@@ -74,8 +100,19 @@ impl Plugin for InputPlugin {
                 CoreStage::PreUpdate,
                 gamepad_event_system.system().label(InputSystem),
             )
+            // raw device input
+            .add_event::<DeviceConnectionEvent>()
+            .add_event::<RawMouseButtonInput>()
+            .add_event::<RawKeyboardInput>()
+            .add_event::<RawMouseWheel>()
+            .init_resource::<InputDevices>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                input_device_system.system().label(InputSystem),
+            )
             // touch
             .add_event::<TouchInput>()
+            .add_event::<PenInput>()
             .init_resource::<Touches>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
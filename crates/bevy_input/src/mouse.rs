@@ -1,12 +1,16 @@
-use crate::{ElementState, Input};
+use crate::{ElementState, Input, ReceivedAt};
 use bevy_ecs::{event::EventReader, system::ResMut};
 use bevy_math::Vec2;
 
 /// A mouse button input event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct MouseButtonInput {
     pub button: MouseButton,
     pub state: ElementState,
+    /// When the windowing backend received the input, see [ReceivedAt].
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub received_at: ReceivedAt,
 }
 
 /// A button on a mouse device
@@ -26,7 +30,8 @@ pub struct MouseMotion {
 }
 
 /// Unit of scroll
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseScrollUnit {
     Line,
     Pixel,
@@ -34,11 +39,15 @@ pub enum MouseScrollUnit {
 
 /// A mouse scroll wheel event, where x represents horizontal scroll and y represents vertical
 /// scroll.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct MouseWheel {
     pub unit: MouseScrollUnit,
     pub x: f32,
     pub y: f32,
+    /// When the windowing backend received the input, see [ReceivedAt].
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub received_at: ReceivedAt,
 }
 
 /// Updates the Input<MouseButton> resource with the latest MouseButtonInput events
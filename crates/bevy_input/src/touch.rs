@@ -1,7 +1,9 @@
+use crate::ReceivedAt;
 use bevy_app::EventReader;
 use bevy_ecs::system::ResMut;
 use bevy_math::Vec2;
 use bevy_utils::HashMap;
+use bevy_window::WindowId;
 
 /// Represents a touch event
 ///
@@ -18,11 +20,16 @@ use bevy_utils::HashMap;
 ///
 /// A `Cancelled` event is emitted when the system has canceled tracking this
 /// touch, such as when the window loses focus, or on iOS if the user moves the
-/// device against their face.
+/// device against their face. Unlike an `End`, it means the touch should not
+/// take effect. When all touches of a window are cancelled, a
+/// `TouchesCancelled` window event follows.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TouchInput {
     pub phase: TouchPhase,
+    /// The position in the window the touch is on.
     pub position: Vec2,
+    /// The window that reported the touch, for routing touches in apps with several windows.
+    pub window: WindowId,
     /// Describes how hard the screen was pressed. May be `None` if the platform
     /// does not support pressure sensitivity.
     ///
@@ -31,7 +38,12 @@ pub struct TouchInput {
     /// - Only available on **iOS** 9.0+ and **Windows** 8+.
     pub force: Option<ForceTouch>,
     /// Unique identifier of a finger.
+    ///
+    /// The winit backend hands out small ids that stay the same until the touch ends or is
+    /// cancelled, and reuses the lowest free id for the next touch.
     pub id: u64,
+    /// When the windowing backend received the input, see [ReceivedAt].
+    pub received_at: ReceivedAt,
 }
 
 /// Describes the force of a touch event
@@ -67,6 +79,75 @@ pub enum ForceTouch {
     Normalized(f64),
 }
 
+impl ForceTouch {
+    /// The force in the range `0.0..=1.0`, where `1.0` is the maximum force the device can
+    /// report.
+    pub fn normalized(&self) -> f64 {
+        match *self {
+            ForceTouch::Calibrated {
+                force,
+                max_possible_force,
+                ..
+            } => {
+                if max_possible_force > 0.0 {
+                    (force / max_possible_force).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                }
+            }
+            ForceTouch::Normalized(force) => force.clamp(0.0, 1.0),
+        }
+    }
+
+    /// The altitude (in radians) of the stylus, if the device reports it.
+    pub fn altitude_angle(&self) -> Option<f64> {
+        match *self {
+            ForceTouch::Calibrated { altitude_angle, .. } => altitude_angle,
+            ForceTouch::Normalized(_) => None,
+        }
+    }
+}
+
+/// A pressure-sensitive touch event, as sent by styluses and drawing tablets.
+///
+/// It is sent in addition to the [TouchInput] with the same `id` for every touch that reports
+/// its force, which on some devices includes fingers.
+///
+/// ## Platform-specific
+///
+/// - winit 0.25 reports the tilt only for the Apple Pencil on **iOS**, and does not report
+///   the azimuth or which end of the pen is used, so `eraser` is always `false`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PenInput {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: Vec2,
+    /// The window that reported the pen input, like [TouchInput::window].
+    pub window: WindowId,
+    /// The pressure in the range `0.0..=1.0`.
+    pub pressure: f32,
+    /// The altitude (in radians) of the pen: `0` when it is parallel to the surface and `Pi/2`
+    /// when it is perpendicular to it.
+    pub altitude_angle: Option<f32>,
+    pub eraser: bool,
+}
+
+impl PenInput {
+    /// The pen input for `touch`, if it reports its force.
+    pub fn from_touch(touch: &TouchInput) -> Option<PenInput> {
+        let force = touch.force?;
+        Some(PenInput {
+            id: touch.id,
+            phase: touch.phase,
+            position: touch.position,
+            window: touch.window,
+            pressure: force.normalized() as f32,
+            altitude_angle: force.altitude_angle().map(|angle| angle as f32),
+            eraser: false,
+        })
+    }
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -80,6 +161,7 @@ pub enum TouchPhase {
 #[derive(Debug, Clone, Copy)]
 pub struct Touch {
     id: u64,
+    window: WindowId,
     start_position: Vec2,
     start_force: Option<ForceTouch>,
     previous_position: Vec2,
@@ -102,6 +184,12 @@ impl Touch {
         self.id
     }
 
+    /// The window the touch is on.
+    #[inline]
+    pub fn window(&self) -> WindowId {
+        self.window
+    }
+
     #[inline]
     pub fn start_position(&self) -> Vec2 {
         self.start_position
@@ -117,6 +205,11 @@ impl Touch {
         self.previous_position
     }
 
+    #[inline]
+    pub fn previous_force(&self) -> Option<ForceTouch> {
+        self.previous_force
+    }
+
     #[inline]
     pub fn position(&self) -> Vec2 {
         self.position
@@ -132,6 +225,7 @@ impl From<&TouchInput> for Touch {
     fn from(input: &TouchInput) -> Touch {
         Touch {
             id: input.id,
+            window: input.window,
             start_position: input.position,
             start_force: input.force,
             previous_position: input.position,
@@ -179,6 +273,12 @@ impl Touches {
         self.just_released.values()
     }
 
+    /// The touch with `id` if it was cancelled since the last update. It still has the start
+    /// position of the touch, so interactions driven by it can be rolled back.
+    pub fn get_cancelled(&self, id: u64) -> Option<&Touch> {
+        self.just_cancelled.get(&id)
+    }
+
     pub fn just_cancelled(&self, id: u64) -> bool {
         self.just_cancelled.contains_key(&id)
     }
@@ -207,8 +307,17 @@ impl Touches {
                 self.pressed.remove_entry(&event.id);
             }
             TouchPhase::Cancelled => {
-                self.just_cancelled.insert(event.id, event.into());
-                self.pressed.remove_entry(&event.id);
+                let touch = match self.pressed.remove(&event.id) {
+                    Some(mut touch) => {
+                        touch.previous_position = touch.position;
+                        touch.previous_force = touch.force;
+                        touch.position = event.position;
+                        touch.force = event.force;
+                        touch
+                    }
+                    None => event.into(),
+                };
+                self.just_cancelled.insert(event.id, touch);
             }
         };
     }
@@ -234,6 +343,7 @@ pub fn touch_screen_input_system(
 
 #[cfg(test)]
 mod test {
+    use bevy_window::WindowId;
 
     #[test]
     fn touch_update() {
@@ -244,6 +354,7 @@ mod test {
 
         let touch_event = Touch {
             id: 4,
+            window: WindowId::primary(),
             start_position: Vec2::new(0.0, 0.0),
             start_force: None,
             previous_position: Vec2::new(0.0, 0.0),
@@ -278,8 +389,10 @@ mod test {
         let touch_event = TouchInput {
             phase: TouchPhase::Started,
             position: Vec2::new(4.0, 4.0),
+            window: WindowId::primary(),
             force: None,
             id: 4,
+            received_at: None,
         };
 
         touches.update();
@@ -293,8 +406,10 @@ mod test {
         let moved_touch_event = TouchInput {
             phase: TouchPhase::Moved,
             position: Vec2::new(5.0, 5.0),
+            window: WindowId::primary(),
             force: None,
             id: touch_event.id,
+            received_at: None,
         };
 
         touches.update();
@@ -314,8 +429,10 @@ mod test {
         let cancel_touch_event = TouchInput {
             phase: TouchPhase::Cancelled,
             position: Vec2::new(1.0, 1.0),
+            window: WindowId::primary(),
             force: None,
             id: touch_event.id,
+            received_at: None,
         };
 
         touches.update();
@@ -329,8 +446,10 @@ mod test {
         let end_touch_event = TouchInput {
             phase: TouchPhase::Ended,
             position: Vec2::new(4.0, 4.0),
+            window: WindowId::primary(),
             force: None,
             id: 4,
+            received_at: None,
         };
 
         touches.update();
@@ -351,8 +470,10 @@ mod test {
         let touch_event = TouchInput {
             phase: TouchPhase::Started,
             position: Vec2::new(4.0, 4.0),
+            window: WindowId::primary(),
             force: None,
             id: 4,
+            received_at: None,
         };
 
         // Register the touch and test that it was registered correctly
@@ -373,8 +494,10 @@ mod test {
         let touch_event = TouchInput {
             phase: TouchPhase::Ended,
             position: Vec2::new(4.0, 4.0),
+            window: WindowId::primary(),
             force: None,
             id: 4,
+            received_at: None,
         };
 
         // Register the touch and test that it was registered correctly
@@ -395,8 +518,10 @@ mod test {
         let touch_event = TouchInput {
             phase: TouchPhase::Cancelled,
             position: Vec2::new(4.0, 4.0),
+            window: WindowId::primary(),
             force: None,
             id: 4,
+            received_at: None,
         };
 
         // Register the touch and test that it was registered correctly
@@ -405,4 +530,103 @@ mod test {
         assert!(touches.just_cancelled(touch_event.id));
         assert_eq!(touches.iter_just_cancelled().count(), 1);
     }
+
+    #[test]
+    fn touch_cancelled_keeps_start() {
+        use crate::{touch::TouchPhase, TouchInput, Touches};
+        use bevy_math::Vec2;
+
+        let mut touches = Touches::default();
+
+        touches.process_touch_event(&TouchInput {
+            phase: TouchPhase::Started,
+            position: Vec2::new(1.0, 1.0),
+            window: WindowId::primary(),
+            force: None,
+            id: 4,
+            received_at: None,
+        });
+        touches.process_touch_event(&TouchInput {
+            phase: TouchPhase::Cancelled,
+            position: Vec2::new(5.0, 1.0),
+            window: WindowId::primary(),
+            force: None,
+            id: 4,
+            received_at: None,
+        });
+
+        let touch = touches.get_cancelled(4).unwrap();
+        assert_eq!(touch.start_position(), Vec2::new(1.0, 1.0));
+        assert_eq!(touch.distance(), Vec2::new(4.0, 0.0));
+        assert!(touches.get_pressed(4).is_none());
+    }
+
+    #[test]
+    fn pen_input_from_touch() {
+        use crate::touch::{ForceTouch, PenInput, TouchInput, TouchPhase};
+        use bevy_math::Vec2;
+
+        let mut touch = TouchInput {
+            phase: TouchPhase::Moved,
+            position: Vec2::new(3.0, 2.0),
+            window: WindowId::primary(),
+            force: None,
+            id: 1,
+            received_at: None,
+        };
+        assert!(PenInput::from_touch(&touch).is_none());
+
+        touch.force = Some(ForceTouch::Calibrated {
+            force: 1.5,
+            max_possible_force: 6.0,
+            altitude_angle: Some(0.5),
+        });
+        let pen = PenInput::from_touch(&touch).unwrap();
+        assert_eq!(pen.id, 1);
+        assert_eq!(pen.window, WindowId::primary());
+        assert_eq!(pen.pressure, 0.25);
+        assert_eq!(pen.altitude_angle, Some(0.5));
+
+        touch.force = Some(ForceTouch::Normalized(1.2));
+        let pen = PenInput::from_touch(&touch).unwrap();
+        assert_eq!(pen.pressure, 1.0);
+        assert_eq!(pen.altitude_angle, None);
+    }
+
+    #[test]
+    fn touches_of_two_windows_stay_apart() {
+        use crate::touch::{ForceTouch, PenInput, TouchInput, TouchPhase};
+        use crate::Touches;
+        use bevy_math::Vec2;
+
+        let (first, second) = (WindowId::primary(), WindowId::new());
+        let touch = |id, window, phase, position| TouchInput {
+            phase,
+            position,
+            window,
+            force: Some(ForceTouch::Normalized(0.5)),
+            id,
+            received_at: None,
+        };
+        let mut touches = Touches::default();
+        touches.process_touch_event(&touch(1, first, TouchPhase::Started, Vec2::new(1.0, 1.0)));
+        touches.process_touch_event(&touch(2, second, TouchPhase::Started, Vec2::new(2.0, 2.0)));
+        touches.process_touch_event(&touch(2, second, TouchPhase::Moved, Vec2::new(3.0, 2.0)));
+
+        let first_touch = touches.get_pressed(1).unwrap();
+        assert_eq!(first_touch.window(), first);
+        assert_eq!(first_touch.distance(), Vec2::ZERO);
+        let second_touch = touches.get_pressed(2).unwrap();
+        assert_eq!(second_touch.window(), second);
+        assert_eq!(second_touch.distance(), Vec2::new(1.0, 0.0));
+
+        let pens = [
+            touch(1, first, TouchPhase::Moved, Vec2::new(1.0, 1.0)),
+            touch(2, second, TouchPhase::Moved, Vec2::new(3.0, 2.0)),
+        ]
+        .iter()
+        .map(|touch| PenInput::from_touch(touch).unwrap().window)
+        .collect::<Vec<_>>();
+        assert_eq!(pens, [first, second]);
+    }
 }
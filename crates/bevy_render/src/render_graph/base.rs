@@ -154,6 +154,7 @@ pub(crate) fn add_base_graph(config: &BaseRenderGraphConfig, world: &mut World)
         });
 
         main_pass_node.use_default_clear_color(0);
+        main_pass_node.use_window_clear_alpha(WindowId::primary());
 
         if config.add_3d_camera {
             main_pass_node.add_camera(camera::CAMERA_3D);
@@ -15,6 +15,7 @@ use bevy_ecs::{
     world::{Mut, World},
 };
 use bevy_utils::{tracing::debug, HashMap};
+use bevy_window::{WindowId, Windows};
 use std::fmt;
 
 pub struct PassNode<Q: WorldQuery> {
@@ -25,6 +26,7 @@ pub struct PassNode<Q: WorldQuery> {
     color_resolve_target_indices: Vec<Option<usize>>,
     depth_stencil_attachment_input_index: Option<usize>,
     default_clear_color_inputs: Vec<usize>,
+    clear_alpha_window: Option<WindowId>,
     query_state: Option<QueryState<Q>>,
     commands: Vec<RenderCommand>,
 }
@@ -51,6 +53,7 @@ impl<Q: WorldQuery> fmt::Debug for PassNode<Q> {
                 "default_clear_color_inputs",
                 &self.default_clear_color_inputs,
             )
+            .field("clear_alpha_window", &self.clear_alpha_window)
             .finish()
     }
 }
@@ -101,6 +104,7 @@ impl<Q: WorldQuery> PassNode<Q> {
             color_resolve_target_indices,
             depth_stencil_attachment_input_index,
             default_clear_color_inputs: Vec::new(),
+            clear_alpha_window: None,
             query_state: None,
             commands: Vec::new(),
         }
@@ -113,6 +117,12 @@ impl<Q: WorldQuery> PassNode<Q> {
     pub fn use_default_clear_color(&mut self, color_attachment_index: usize) {
         self.default_clear_color_inputs.push(color_attachment_index);
     }
+
+    /// Clears the color attachments with the [clear_alpha](bevy_window::Window::clear_alpha)
+    /// of `window_id`, if it has one.
+    pub fn use_window_clear_alpha(&mut self, window_id: WindowId) {
+        self.clear_alpha_window = Some(window_id);
+    }
 }
 
 impl<Q: WorldQuery + Send + Sync + 'static> Node for PassNode<Q>
@@ -204,12 +214,23 @@ where
         input: &ResourceSlots,
         _output: &mut ResourceSlots,
     ) {
+        let clear_alpha = self.clear_alpha_window.and_then(|window_id| {
+            world
+                .get_resource::<Windows>()
+                .and_then(|windows| windows.get(window_id))
+                .and_then(|window| window.clear_alpha())
+        });
         for (i, color_attachment) in self.descriptor.color_attachments.iter_mut().enumerate() {
             if self.default_clear_color_inputs.contains(&i) {
                 if let Some(default_clear_color) = world.get_resource::<ClearColor>() {
                     color_attachment.ops.load = LoadOp::Clear(default_clear_color.0);
                 }
             }
+            if let (Some(alpha), LoadOp::Clear(color)) =
+                (clear_alpha, &mut color_attachment.ops.load)
+            {
+                color.set_a(alpha);
+            }
             if let Some(input_index) = self.color_attachment_input_indices[i] {
                 color_attachment.attachment =
                     TextureAttachment::Id(input.get(input_index).unwrap().get_texture().unwrap());
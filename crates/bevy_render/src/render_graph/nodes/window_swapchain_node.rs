@@ -4,13 +4,14 @@ use crate::{
 };
 use bevy_app::{Events, ManualEventReader};
 use bevy_ecs::world::World;
-use bevy_window::{WindowCreated, WindowId, WindowResized, Windows};
+use bevy_window::{WindowCreated, WindowId, WindowResized, WindowVsyncChanged, Windows};
 use std::borrow::Cow;
 
 pub struct WindowSwapChainNode {
     window_id: WindowId,
     window_created_event_reader: ManualEventReader<WindowCreated>,
     window_resized_event_reader: ManualEventReader<WindowResized>,
+    window_vsync_changed_event_reader: ManualEventReader<WindowVsyncChanged>,
 }
 
 impl WindowSwapChainNode {
@@ -21,6 +22,7 @@ impl WindowSwapChainNode {
             window_id,
             window_created_event_reader: Default::default(),
             window_resized_event_reader: Default::default(),
+            window_vsync_changed_event_reader: Default::default(),
         }
     }
 }
@@ -44,6 +46,8 @@ impl Node for WindowSwapChainNode {
         const WINDOW_TEXTURE: usize = 0;
         let window_created_events = world.get_resource::<Events<WindowCreated>>().unwrap();
         let window_resized_events = world.get_resource::<Events<WindowResized>>().unwrap();
+        let window_vsync_changed_events =
+            world.get_resource::<Events<WindowVsyncChanged>>().unwrap();
         let windows = world.get_resource::<Windows>().unwrap();
 
         let window = windows
@@ -52,7 +56,7 @@ impl Node for WindowSwapChainNode {
 
         let render_resource_context = render_context.resources_mut();
 
-        // create window swapchain when window is resized or created
+        // create window swapchain when window is resized, created or its vsync setting changes
         if self
             .window_created_event_reader
             .iter(&window_created_events)
@@ -61,6 +65,10 @@ impl Node for WindowSwapChainNode {
                 .window_resized_event_reader
                 .iter(&window_resized_events)
                 .any(|e| e.id == window.id())
+            || self
+                .window_vsync_changed_event_reader
+                .iter(&window_vsync_changed_events)
+                .any(|e| e.id == window.id())
         {
             render_resource_context.create_swap_chain(window);
         }
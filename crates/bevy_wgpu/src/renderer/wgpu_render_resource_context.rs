@@ -48,6 +48,11 @@ impl WgpuRenderResourceContext {
         window_surfaces.insert(window_id, surface);
     }
 
+    pub fn remove_window_surface(&self, window_id: WindowId) {
+        self.resources.window_swap_chains.write().remove(&window_id);
+        self.resources.window_surfaces.write().remove(&window_id);
+    }
+
     pub fn copy_buffer_to_buffer(
         &self,
         command_encoder: &mut wgpu::CommandEncoder,
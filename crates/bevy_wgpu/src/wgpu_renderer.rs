@@ -4,12 +4,14 @@ use crate::{
     WgpuBackend, WgpuOptions, WgpuPowerOptions,
 };
 use bevy_app::{Events, ManualEventReader};
-use bevy_ecs::world::{Mut, World};
+use bevy_ecs::world::{Mut, World, WorldCell};
 use bevy_render::{
     render_graph::{DependentNodeStager, RenderGraph, RenderGraphStager},
     renderer::RenderResourceContext,
 };
-use bevy_window::{WindowCreated, WindowResized, Windows};
+use bevy_window::{
+    ApplicationLifecycle, Window, WindowClosed, WindowCreated, WindowResized, Windows,
+};
 use std::{ops::Deref, sync::Arc};
 
 pub struct WgpuRenderer {
@@ -18,6 +20,10 @@ pub struct WgpuRenderer {
     pub queue: wgpu::Queue,
     pub window_resized_event_reader: ManualEventReader<WindowResized>,
     pub window_created_event_reader: ManualEventReader<WindowCreated>,
+    pub window_closed_event_reader: ManualEventReader<WindowClosed>,
+    pub lifecycle_event_reader: ManualEventReader<ApplicationLifecycle>,
+    /// Set while the application is suspended, when there are no window surfaces to render to.
+    pub suspended: bool,
     pub initialized: bool,
 }
 
@@ -74,6 +80,9 @@ impl WgpuRenderer {
             queue,
             window_resized_event_reader: Default::default(),
             window_created_event_reader: Default::default(),
+            window_closed_event_reader: Default::default(),
+            lifecycle_event_reader: Default::default(),
+            suspended: false,
             initialized: false,
         }
     }
@@ -95,17 +104,64 @@ impl WgpuRenderer {
             let window = windows
                 .get(window_created_event.id)
                 .expect("Received window created event for non-existent window.");
-            #[cfg(feature = "bevy_winit")]
-            {
-                let winit_windows = world.get_resource::<bevy_winit::WinitWindows>().unwrap();
-                let winit_window = winit_windows.get_window(window.id()).unwrap();
-                // SAFE: The raw window handle created from a `winit::Window` is always valid.
-                let surface = unsafe { self.instance.create_surface(winit_window.deref()) };
-                render_resource_context.set_window_surface(window.id(), surface);
+            if !self.suspended {
+                create_window_surface(&self.instance, &world, render_resource_context, window);
+            }
+        }
+    }
+
+    /// Drops all window surfaces when the application is suspended, and creates them again when
+    /// it is resumed, since the native windows they belong to may be destroyed in between.
+    pub fn handle_lifecycle_events(&mut self, world: &mut World) {
+        let world = world.cell();
+        let mut render_resource_context = world
+            .get_resource_mut::<Box<dyn RenderResourceContext>>()
+            .unwrap();
+        let render_resource_context = render_resource_context
+            .downcast_mut::<WgpuRenderResourceContext>()
+            .unwrap();
+        let windows = world.get_resource::<Windows>().unwrap();
+        let lifecycle_events = world
+            .get_resource::<Events<ApplicationLifecycle>>()
+            .unwrap();
+        for lifecycle in self.lifecycle_event_reader.iter(&lifecycle_events) {
+            match lifecycle {
+                ApplicationLifecycle::Suspended if !self.suspended => {
+                    self.suspended = true;
+                    for window in windows.iter() {
+                        render_resource_context.remove_window_surface(window.id());
+                    }
+                }
+                ApplicationLifecycle::Resumed if self.suspended => {
+                    self.suspended = false;
+                    for window in windows.iter() {
+                        create_window_surface(
+                            &self.instance,
+                            &world,
+                            render_resource_context,
+                            window,
+                        );
+                    }
+                }
+                _ => {}
             }
         }
     }
 
+    pub fn handle_window_closed_events(&mut self, world: &mut World) {
+        let world = world.cell();
+        let render_resource_context = world
+            .get_resource::<Box<dyn RenderResourceContext>>()
+            .unwrap();
+        let render_resource_context = render_resource_context
+            .downcast_ref::<WgpuRenderResourceContext>()
+            .unwrap();
+        let window_closed_events = world.get_resource::<Events<WindowClosed>>().unwrap();
+        for window_closed_event in self.window_closed_event_reader.iter(&window_closed_events) {
+            render_resource_context.remove_window_surface(window_closed_event.id);
+        }
+    }
+
     pub fn run_graph(&mut self, world: &mut World) {
         world.resource_scope(|world, mut render_graph: Mut<RenderGraph>| {
             render_graph.prepare(world);
@@ -123,7 +179,13 @@ impl WgpuRenderer {
     }
 
     pub fn update(&mut self, world: &mut World) {
+        // closed first, a window that is recreated gets both events in the same frame
+        self.handle_window_closed_events(world);
         self.handle_window_created_events(world);
+        self.handle_lifecycle_events(world);
+        if self.suspended {
+            return;
+        }
         self.run_graph(world);
 
         let render_resource_context = world
@@ -133,3 +195,20 @@ impl WgpuRenderer {
         render_resource_context.remove_stale_bind_groups();
     }
 }
+
+#[allow(unused_variables)]
+fn create_window_surface(
+    instance: &wgpu::Instance,
+    world: &WorldCell,
+    render_resource_context: &WgpuRenderResourceContext,
+    window: &Window,
+) {
+    #[cfg(feature = "bevy_winit")]
+    {
+        let winit_windows = world.get_resource::<bevy_winit::WinitWindows>().unwrap();
+        let winit_window = winit_windows.get_window(window.id()).unwrap();
+        // SAFE: The raw window handle created from a `winit::Window` is always valid.
+        let surface = unsafe { instance.create_surface(winit_window.deref()) };
+        render_resource_context.set_window_surface(window.id(), surface);
+    }
+}
@@ -16,7 +16,7 @@ use bevy_render::{
         TextureSampleType, TextureUsage, TextureViewDimension,
     },
 };
-use bevy_window::Window;
+use bevy_window::{PresentMode, Window};
 use wgpu::BufferBindingType;
 
 pub trait WgpuFrom<T> {
@@ -647,10 +647,12 @@ impl WgpuFrom<&Window> for wgpu::SwapChainDescriptor {
             format: TextureFormat::default().wgpu_into(),
             width: window.physical_width().max(1),
             height: window.physical_height().max(1),
-            present_mode: if window.vsync() {
-                wgpu::PresentMode::Fifo
-            } else {
-                wgpu::PresentMode::Immediate
+            present_mode: match window.surface_hints().present_mode {
+                Some(PresentMode::Immediate) => wgpu::PresentMode::Immediate,
+                Some(PresentMode::Mailbox) => wgpu::PresentMode::Mailbox,
+                Some(PresentMode::Fifo) => wgpu::PresentMode::Fifo,
+                None if window.vsync() => wgpu::PresentMode::Fifo,
+                None => wgpu::PresentMode::Immediate,
             },
         }
     }
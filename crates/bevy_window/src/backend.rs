@@ -0,0 +1,838 @@
+use crate::{
+    CreateWindow, FocusedWindow, MonitorSelection, Window, WindowCapabilities,
+    WindowCloseRequested, WindowClosed, WindowCommand, WindowCommandFailed,
+    WindowCommandUnsupported, WindowCreated, WindowCreationDenied, WindowCreationFailed,
+    WindowCreationLimits, WindowDescriptor, WindowFocused, WindowId, WindowMinimized, WindowMoved,
+    WindowResized, WindowRestored, WindowScaleFactorChanged, WindowStatistics, WindowStats,
+    WindowVsyncChanged, Windows,
+};
+use bevy_app::{Events, ManualEventReader};
+use bevy_ecs::world::{World, WorldCell};
+use bevy_math::IVec2;
+use bevy_utils::{
+    tracing::{error, warn},
+    Duration, Instant,
+};
+use std::fmt;
+
+/// The native side of [Windows]: creates the backend windows and applies [WindowCommand]s to
+/// them.
+///
+/// The backend is stored as a resource. The bookkeeping that does not depend on the windowing
+/// library, like updating [Windows] and sending events, is done by [create_windows] and
+/// [apply_window_commands], so alternative backends only have to implement this trait and a
+/// runner that dispatches their events, or that calls [poll_window_events] if they implement
+/// [WindowBackend::poll_events].
+pub trait WindowBackend: Send + Sync + 'static {
+    /// What the backend needs to create windows, such as its event loop.
+    type Target: ?Sized;
+
+    fn create_window(
+        &mut self,
+        target: &Self::Target,
+        id: WindowId,
+        descriptor: &WindowDescriptor,
+    ) -> Result<Window, WindowCommandError>;
+
+    /// Applies `command` to the backend window of `id`. [WindowCommand::Close] is never passed
+    /// here, see [WindowBackend::close_window].
+    fn apply_command(
+        &mut self,
+        id: WindowId,
+        command: &WindowCommand,
+    ) -> Result<(), WindowCommandError>;
+
+    fn close_window(&mut self, id: WindowId);
+
+    /// Whether the backend window of `id` exists. Commands queued on a [Window] whose backend
+    /// window does not exist yet stay queued, and are applied in order once it does.
+    fn has_window(&self, _id: WindowId) -> bool {
+        true
+    }
+
+    /// Moves the events that happened to the backend windows since the last call into
+    /// `events`, for backends that are polled instead of dispatching events from their runner.
+    /// Called by [poll_window_events]. Does nothing by default.
+    fn poll_events(&mut self, _events: &mut Vec<WindowBackendEvent>) {}
+}
+
+/// An event of a backend window, reported by [WindowBackend::poll_events].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowBackendEvent {
+    /// The window was resized, in physical pixels.
+    Resized {
+        id: WindowId,
+        width: u32,
+        height: u32,
+    },
+    /// The window was moved, in physical pixels.
+    Moved {
+        id: WindowId,
+        position: IVec2,
+    },
+    Focused {
+        id: WindowId,
+        focused: bool,
+    },
+    CloseRequested {
+        id: WindowId,
+    },
+}
+
+/// Why a [WindowBackend] could not apply a [WindowCommand].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowCommandError {
+    MonitorNotFound(MonitorSelection),
+    /// The monitor reports no video modes at all.
+    NoMatchingVideoMode,
+    /// The monitor has no video mode at the requested `video_mode_index`.
+    VideoModeNotFound {
+        index: usize,
+    },
+    /// The backend cannot apply this kind of command to an existing window. Handled according
+    /// to the [BackendErrorPolicy] and reported with a [WindowCommandUnsupported] event as well
+    /// by [apply_window_commands].
+    Unsupported {
+        command: &'static str,
+    },
+    /// The backend failed to create the window.
+    CreationFailed(String),
+    /// The window is known to the backend but not to [Windows], or the other way around.
+    UnknownWindow,
+    /// An error reported by the windowing library.
+    Platform(String),
+}
+
+impl fmt::Display for WindowCommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowCommandError::MonitorNotFound(selection) => {
+                write!(f, "no monitor found for {:?}", selection)
+            }
+            WindowCommandError::NoMatchingVideoMode => {
+                write!(f, "the monitor reports no video modes")
+            }
+            WindowCommandError::VideoModeNotFound { index } => {
+                write!(f, "the monitor has no video mode at index {}", index)
+            }
+            WindowCommandError::Unsupported { command } => {
+                write!(f, "{} is not supported by the backend", command)
+            }
+            WindowCommandError::CreationFailed(reason) => {
+                write!(f, "failed to create the window: {}", reason)
+            }
+            WindowCommandError::UnknownWindow => write!(f, "the window is unknown"),
+            WindowCommandError::Platform(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+/// How failed calls into the windowing backend are handled, when creating windows, applying
+/// [WindowCommand]s or dispatching backend events. Inserted as a resource by the
+/// [WindowPlugin](crate::WindowPlugin), change it to make an app strict during development or
+/// forgiving in deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackendErrorPolicy {
+    Panic,
+    LogAndContinue,
+    /// Logs the error and sends a [WindowCommandFailed] event for it.
+    #[default]
+    EmitEvent,
+}
+
+impl BackendErrorPolicy {
+    /// Handles `error` of the window `id` according to this policy.
+    pub fn handle(
+        &self,
+        id: WindowId,
+        error: WindowCommandError,
+        events: &mut Events<WindowCommandFailed>,
+    ) {
+        match self {
+            BackendErrorPolicy::Panic => panic!("Window backend error for {}: {}", id, error),
+            BackendErrorPolicy::LogAndContinue => {
+                error!("Window backend error for {}: {}", id, error)
+            }
+            BackendErrorPolicy::EmitEvent => {
+                error!("Window backend error for {}: {}", id, error);
+                events.send(WindowCommandFailed { id, error });
+            }
+        }
+    }
+}
+
+/// How much time [apply_window_commands] may spend per frame. Inserted as a resource by the
+/// [WindowPlugin](crate::WindowPlugin).
+///
+/// With a budget, the commands of windows with [user-visible](WindowCommand::is_user_visible)
+/// changes are applied before those of the other windows, each window's in the order they were
+/// queued. Commands that do not fit into the budget stay queued for the next frame, so
+/// bursts like restoring many windows at once are spread over several frames. At least one
+/// command is applied per frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WindowCommandBudget {
+    /// `None` applies all queued commands every frame.
+    pub per_frame: Option<Duration>,
+}
+
+/// Creates a window through `B` for every new [CreateWindow] event that is within the
+/// [WindowCreationLimits].
+pub fn create_windows<B: WindowBackend>(
+    world: &mut World,
+    target: &B::Target,
+    create_window_event_reader: &mut ManualEventReader<CreateWindow>,
+    frame_count: u64,
+) {
+    let world = world.cell();
+    let mut backend = world.get_resource_mut::<B>().unwrap();
+    let mut windows = world.get_resource_mut::<Windows>().unwrap();
+    let create_window_events = world.get_resource::<Events<CreateWindow>>().unwrap();
+    let mut window_created_events = world.get_resource_mut::<Events<WindowCreated>>().unwrap();
+    let mut window_stats = world.get_resource_mut::<WindowStats>().unwrap();
+    let policy = world.get_resource::<BackendErrorPolicy>().unwrap();
+    let mut limits = world.get_resource_mut::<WindowCreationLimits>();
+    for create_window_event in create_window_event_reader.iter(&create_window_events) {
+        if let Some(limits) = limits.as_mut() {
+            if let Err(reason) = limits.try_create(windows.iter().count(), Instant::now()) {
+                warn!("Not creating window {}: {}", create_window_event.id, reason);
+                world
+                    .get_resource_mut::<Events<WindowCreationDenied>>()
+                    .unwrap()
+                    .send(WindowCreationDenied {
+                        id: create_window_event.id,
+                        reason,
+                    });
+                continue;
+            }
+        }
+        let window = match backend.create_window(
+            target,
+            create_window_event.id,
+            &create_window_event.descriptor,
+        ) {
+            Ok(window) => window,
+            Err(error) => {
+                let mut window_command_failed_events = world
+                    .get_resource_mut::<Events<WindowCommandFailed>>()
+                    .unwrap();
+                policy.handle(
+                    create_window_event.id,
+                    error.clone(),
+                    &mut window_command_failed_events,
+                );
+                world
+                    .get_resource_mut::<Events<WindowCreationFailed>>()
+                    .unwrap()
+                    .send(WindowCreationFailed {
+                        id: create_window_event.id,
+                        error,
+                    });
+                continue;
+            }
+        };
+        if let Some(capabilities) = world.get_resource::<WindowCapabilities>() {
+            for command in capabilities.unsupported_settings(&create_window_event.descriptor) {
+                report_unsupported(&world, create_window_event.id, command);
+            }
+        }
+        let surface_hints = window.surface_hints().clone();
+        window_stats.insert(
+            window.id(),
+            WindowStatistics::new(frame_count, window.is_focused()),
+        );
+        windows.add(window);
+        window_created_events.send(WindowCreated {
+            id: create_window_event.id,
+            surface_hints,
+        });
+    }
+}
+
+/// Drains the commands queued on each [Window] and applies them through `B`, within the
+/// [WindowCommandBudget]. Windows whose backend window does not exist yet keep their commands
+/// until it does, unless they are closed.
+pub fn apply_window_commands<B: WindowBackend>(world: &mut World) {
+    let world = world.cell();
+    let mut backend = world.get_resource_mut::<B>().unwrap();
+    let mut windows = world.get_resource_mut::<Windows>().unwrap();
+    let budget = world
+        .get_resource::<WindowCommandBudget>()
+        .and_then(|budget| budget.per_frame);
+    let started_at = Instant::now();
+    let mut closed_windows = Vec::new();
+
+    let mut queues = Vec::new();
+    for window in windows.iter_mut() {
+        let id = window.id();
+        if !backend.has_window(id) {
+            if window.is_close_queued() {
+                window.drain_commands().for_each(drop);
+                closed_windows.push(id);
+            }
+            continue;
+        }
+        let commands = window.drain_commands().map(Some).collect::<Vec<_>>();
+        if !commands.is_empty() {
+            queues.push((id, commands));
+        }
+    }
+
+    // indices into `queues` in the order the commands are applied
+    let mut order = queues
+        .iter()
+        .enumerate()
+        .flat_map(|(queue, (_, commands))| (0..commands.len()).map(move |i| (queue, i)))
+        .collect::<Vec<_>>();
+    if budget.is_some() {
+        // later commands of a window may depend on its earlier ones, so only whole windows are
+        // moved ahead, and the sort keeps the order of their commands
+        let user_visible = queues
+            .iter()
+            .map(|(_, commands)| {
+                commands
+                    .iter()
+                    .flatten()
+                    .any(WindowCommand::is_user_visible)
+            })
+            .collect::<Vec<_>>();
+        order.sort_by_key(|&(queue, _)| !user_visible[queue]);
+    }
+    for (applied, (queue, i)) in order.into_iter().enumerate() {
+        if let Some(budget) = budget {
+            if applied > 0 && started_at.elapsed() >= budget {
+                break;
+            }
+        }
+        let (id, commands) = &mut queues[queue];
+        let id = *id;
+        if closed_windows.contains(&id) {
+            continue;
+        }
+        let command = commands[i].take().unwrap();
+        match command {
+            WindowCommand::SetScaleFactor { scale_factor } => {
+                world
+                    .get_resource_mut::<Events<WindowScaleFactorChanged>>()
+                    .unwrap()
+                    .send(WindowScaleFactorChanged { id, scale_factor });
+            }
+            WindowCommand::SetVsync { vsync } => {
+                world
+                    .get_resource_mut::<Events<WindowVsyncChanged>>()
+                    .unwrap()
+                    .send(WindowVsyncChanged { id, vsync });
+            }
+            WindowCommand::Close => {
+                closed_windows.push(id);
+                continue;
+            }
+            _ => {}
+        }
+        // the backend still applies what it can of commands that need a missing capability
+        let mut unsupported = world
+            .get_resource::<WindowCapabilities>()
+            .and_then(|capabilities| capabilities.unsupported_command(&command));
+        match backend.apply_command(id, &command) {
+            Ok(()) => {
+                if let Some(window) = windows.get_mut(id) {
+                    let was_minimized = window.is_minimized();
+                    window.update_from_applied_command(&command);
+                    // the backend cannot tell on every platform, so it may not report this
+                    if window.is_minimized() && !was_minimized {
+                        world
+                            .get_resource_mut::<Events<WindowMinimized>>()
+                            .unwrap()
+                            .send(WindowMinimized { id });
+                    } else if !window.is_minimized() && was_minimized {
+                        world
+                            .get_resource_mut::<Events<WindowRestored>>()
+                            .unwrap()
+                            .send(WindowRestored { id });
+                    }
+                }
+            }
+            Err(error) => {
+                if let WindowCommandError::Unsupported { command } = error {
+                    unsupported = Some(command);
+                }
+                world.get_resource::<BackendErrorPolicy>().unwrap().handle(
+                    id,
+                    error,
+                    &mut world
+                        .get_resource_mut::<Events<WindowCommandFailed>>()
+                        .unwrap(),
+                )
+            }
+        }
+        if let Some(command) = unsupported {
+            report_unsupported(&world, id, command);
+        }
+    }
+
+    // the commands that did not fit into the budget go before those queued in the meantime
+    for (id, commands) in queues {
+        if closed_windows.contains(&id) {
+            continue;
+        }
+        if let Some(window) = windows.get_mut(id) {
+            window.requeue_commands(commands.into_iter().flatten());
+        }
+    }
+
+    if !closed_windows.is_empty() {
+        let mut window_closed_events = world.get_resource_mut::<Events<WindowClosed>>().unwrap();
+        let mut window_stats = world.get_resource_mut::<WindowStats>().unwrap();
+        let mut focused_window = world.get_resource_mut::<FocusedWindow>();
+        for id in closed_windows {
+            if let Some(focused_window) = focused_window.as_mut() {
+                focused_window.update_from_backend(id, false);
+            }
+            backend.close_window(id);
+            windows.remove(id);
+            window_stats.remove(id);
+            window_closed_events.send(WindowClosed { id });
+        }
+    }
+}
+
+/// Polls the events of `B` and applies them to [Windows], sending the matching window events.
+/// Runners of polled backends call this before each update.
+pub fn poll_window_events<B: WindowBackend>(world: &mut World) {
+    let mut events = Vec::new();
+    world
+        .get_resource_mut::<B>()
+        .unwrap()
+        .poll_events(&mut events);
+    let world = world.cell();
+    let mut windows = world.get_resource_mut::<Windows>().unwrap();
+    for event in events {
+        match event {
+            WindowBackendEvent::Resized { id, width, height } => {
+                if let Some(window) = windows.get_mut(id) {
+                    window.update_actual_size_from_backend(width, height);
+                    world
+                        .get_resource_mut::<Events<WindowResized>>()
+                        .unwrap()
+                        .send(WindowResized {
+                            id,
+                            width: window.width(),
+                            height: window.height(),
+                        });
+                }
+            }
+            WindowBackendEvent::Moved { id, position } => {
+                if let Some(window) = windows.get_mut(id) {
+                    window.update_actual_position_from_backend(position);
+                    world
+                        .get_resource_mut::<Events<WindowMoved>>()
+                        .unwrap()
+                        .send(WindowMoved { id, position });
+                }
+            }
+            WindowBackendEvent::Focused { id, focused } => {
+                if let Some(window) = windows.get_mut(id) {
+                    window.update_focused_status_from_backend(focused);
+                    if let Some(mut focused_window) = world.get_resource_mut::<FocusedWindow>() {
+                        focused_window.update_from_backend(id, focused);
+                    }
+                    world
+                        .get_resource_mut::<Events<WindowFocused>>()
+                        .unwrap()
+                        .send(WindowFocused { id, focused });
+                }
+            }
+            WindowBackendEvent::CloseRequested { id } => {
+                world
+                    .get_resource_mut::<Events<WindowCloseRequested>>()
+                    .unwrap()
+                    .send(WindowCloseRequested { id });
+            }
+        }
+    }
+}
+
+fn report_unsupported(world: &WorldCell, id: WindowId, command: &'static str) {
+    warn!("{} is not supported by the backend for {}", command, id);
+    if let Some(mut events) = world.get_resource_mut::<Events<WindowCommandUnsupported>>() {
+        events.send(WindowCommandUnsupported { id, command });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WindowMode;
+
+    #[derive(Default)]
+    struct TestBackend {
+        created: Vec<WindowId>,
+        applied: Vec<String>,
+        unsupported: bool,
+        events: Vec<WindowBackendEvent>,
+    }
+
+    impl WindowBackend for TestBackend {
+        type Target = ();
+
+        fn create_window(
+            &mut self,
+            _target: &(),
+            _id: WindowId,
+            _descriptor: &WindowDescriptor,
+        ) -> Result<Window, WindowCommandError> {
+            Err(WindowCommandError::Unsupported {
+                command: "create_window",
+            })
+        }
+
+        fn apply_command(
+            &mut self,
+            _id: WindowId,
+            command: &WindowCommand,
+        ) -> Result<(), WindowCommandError> {
+            if self.unsupported {
+                return Err(WindowCommandError::Unsupported { command: "test" });
+            }
+            self.applied.push(format!("{:?}", command));
+            Ok(())
+        }
+
+        fn close_window(&mut self, _id: WindowId) {}
+
+        fn has_window(&self, id: WindowId) -> bool {
+            self.created.contains(&id)
+        }
+
+        fn poll_events(&mut self, events: &mut Vec<WindowBackendEvent>) {
+            events.append(&mut self.events);
+        }
+    }
+
+    #[test]
+    fn commands_wait_for_the_backend_window() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        let mut window = Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None);
+        window.set_title("first".to_string());
+        window.set_mode(WindowMode::BorderlessFullscreen);
+        windows.add(window);
+        world.insert_resource(windows);
+        world.insert_resource(TestBackend::default());
+        world.insert_resource(BackendErrorPolicy::Panic);
+
+        apply_window_commands::<TestBackend>(&mut world);
+        assert!(world
+            .get_resource::<TestBackend>()
+            .unwrap()
+            .applied
+            .is_empty());
+
+        world
+            .get_resource_mut::<TestBackend>()
+            .unwrap()
+            .created
+            .push(id);
+        apply_window_commands::<TestBackend>(&mut world);
+        let applied = &world.get_resource::<TestBackend>().unwrap().applied;
+        assert_eq!(applied.len(), 2);
+        assert!(applied[0].starts_with("SetTitle"));
+        assert!(applied[1].starts_with("SetWindowMode"));
+    }
+
+    #[test]
+    fn budget_applies_user_visible_commands_first() {
+        let (background, foreground) = (WindowId::new(), WindowId::new());
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        let mut window = Window::new(
+            background,
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            1.0,
+            None,
+        );
+        window.set_resizable(false);
+        windows.add(window);
+        let mut window = Window::new(
+            foreground,
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            1.0,
+            None,
+        );
+        window.set_title("first".to_string());
+        windows.add(window);
+        world.insert_resource(windows);
+        world.insert_resource(TestBackend {
+            created: vec![background, foreground],
+            ..Default::default()
+        });
+        world.insert_resource(BackendErrorPolicy::Panic);
+        world.insert_resource(WindowCommandBudget {
+            per_frame: Some(Duration::from_secs(0)),
+        });
+
+        apply_window_commands::<TestBackend>(&mut world);
+        let applied = &world.get_resource::<TestBackend>().unwrap().applied;
+        assert_eq!(applied.len(), 1);
+        assert!(applied[0].starts_with("SetTitle"));
+
+        world
+            .get_resource_mut::<Windows>()
+            .unwrap()
+            .get_mut(foreground)
+            .unwrap()
+            .set_title("second".to_string());
+        apply_window_commands::<TestBackend>(&mut world);
+        apply_window_commands::<TestBackend>(&mut world);
+        let applied = &world.get_resource::<TestBackend>().unwrap().applied;
+        assert_eq!(applied.len(), 3);
+        assert!(applied[1].contains("second"));
+        assert!(applied[2].starts_with("SetResizable"));
+    }
+
+    #[test]
+    fn budget_keeps_the_order_of_a_windows_commands() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        let mut window = Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None);
+        window.set_resizable(false);
+        window.set_resolution(800.0, 600.0);
+        windows.add(window);
+        world.insert_resource(windows);
+        world.insert_resource(TestBackend {
+            created: vec![id],
+            ..Default::default()
+        });
+        world.insert_resource(BackendErrorPolicy::Panic);
+        world.insert_resource(WindowCommandBudget {
+            per_frame: Some(Duration::from_secs(0)),
+        });
+
+        apply_window_commands::<TestBackend>(&mut world);
+        apply_window_commands::<TestBackend>(&mut world);
+        let applied = &world.get_resource::<TestBackend>().unwrap().applied;
+        assert_eq!(applied.len(), 2);
+        assert!(applied[0].starts_with("SetResizable"));
+        assert!(applied[1].starts_with("SetResolution"));
+    }
+
+    #[test]
+    fn unsupported_commands_are_reported() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        let mut window = Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None);
+        window.set_decorations(false);
+        window.set_title("title".to_string());
+        windows.add(window);
+        world.insert_resource(windows);
+        world.insert_resource(TestBackend {
+            created: vec![id],
+            ..Default::default()
+        });
+        world.insert_resource(BackendErrorPolicy::Panic);
+        world.insert_resource(WindowCapabilities {
+            decorations_toggle: false,
+            ..Default::default()
+        });
+        world.insert_resource(Events::<WindowCommandUnsupported>::default());
+
+        apply_window_commands::<TestBackend>(&mut world);
+        assert_eq!(
+            world.get_resource::<TestBackend>().unwrap().applied.len(),
+            2
+        );
+        let unsupported_events = world
+            .get_resource::<Events<WindowCommandUnsupported>>()
+            .unwrap();
+        let unsupported = unsupported_events
+            .get_reader()
+            .iter(&unsupported_events)
+            .map(|event| (event.id, event.command))
+            .collect::<Vec<_>>();
+        assert_eq!(unsupported, [(id, "SetDecorations")]);
+    }
+
+    #[test]
+    fn window_state_follows_the_applied_command() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        let mut window = Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None);
+        window.set_transparent(true);
+        windows.add(window);
+        world.insert_resource(windows);
+        world.insert_resource(TestBackend {
+            created: vec![id],
+            unsupported: true,
+            ..Default::default()
+        });
+        world.insert_resource(BackendErrorPolicy::EmitEvent);
+        world.insert_resource(Events::<WindowCommandFailed>::default());
+        world.insert_resource(Events::<WindowCommandUnsupported>::default());
+
+        apply_window_commands::<TestBackend>(&mut world);
+        let windows = world.get_resource::<Windows>().unwrap();
+        assert!(!windows.get(id).unwrap().transparent());
+        // the policy handles what the backend does not support, the event is sent as well
+        let failed_events = world.get_resource::<Events<WindowCommandFailed>>().unwrap();
+        assert_eq!(failed_events.get_reader().iter(&failed_events).count(), 1);
+        let unsupported_events = world
+            .get_resource::<Events<WindowCommandUnsupported>>()
+            .unwrap();
+        assert_eq!(
+            unsupported_events
+                .get_reader()
+                .iter(&unsupported_events)
+                .count(),
+            1
+        );
+
+        world.get_resource_mut::<TestBackend>().unwrap().unsupported = false;
+        world
+            .get_resource_mut::<Windows>()
+            .unwrap()
+            .get_mut(id)
+            .unwrap()
+            .set_transparent(true);
+        apply_window_commands::<TestBackend>(&mut world);
+        let windows = world.get_resource::<Windows>().unwrap();
+        assert!(windows.get(id).unwrap().transparent());
+    }
+
+    #[test]
+    fn minimizing_is_reported_once_applied() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        let mut window = Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None);
+        window.set_minimized(true);
+        windows.add(window);
+        world.insert_resource(windows);
+        world.insert_resource(TestBackend {
+            created: vec![id],
+            ..Default::default()
+        });
+        world.insert_resource(BackendErrorPolicy::Panic);
+        world.insert_resource(Events::<WindowMinimized>::default());
+        world.insert_resource(Events::<WindowRestored>::default());
+
+        apply_window_commands::<TestBackend>(&mut world);
+        let windows = world.get_resource::<Windows>().unwrap();
+        assert!(windows.get(id).unwrap().is_minimized());
+        let minimized_events = world.get_resource::<Events<WindowMinimized>>().unwrap();
+        assert_eq!(
+            minimized_events
+                .get_reader()
+                .iter(&minimized_events)
+                .count(),
+            1
+        );
+
+        world
+            .get_resource_mut::<Windows>()
+            .unwrap()
+            .get_mut(id)
+            .unwrap()
+            .set_minimized(false);
+        apply_window_commands::<TestBackend>(&mut world);
+        let windows = world.get_resource::<Windows>().unwrap();
+        assert!(!windows.get(id).unwrap().is_minimized());
+        let restored_events = world.get_resource::<Events<WindowRestored>>().unwrap();
+        assert_eq!(
+            restored_events.get_reader().iter(&restored_events).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn failed_creation_is_reported() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        world.insert_resource(Windows::default());
+        world.insert_resource(TestBackend::default());
+        world.insert_resource(BackendErrorPolicy::LogAndContinue);
+        world.insert_resource(WindowStats::default());
+        world.insert_resource(Events::<WindowCreated>::default());
+        world.insert_resource(Events::<WindowCommandFailed>::default());
+        world.insert_resource(Events::<WindowCreationFailed>::default());
+        let mut create_window_events = Events::<CreateWindow>::default();
+        create_window_events.send(CreateWindow {
+            id,
+            descriptor: WindowDescriptor::default(),
+        });
+        world.insert_resource(create_window_events);
+
+        create_windows::<TestBackend>(&mut world, &(), &mut Default::default(), 0);
+        assert!(world.get_resource::<Windows>().unwrap().get(id).is_none());
+        let failed_events = world
+            .get_resource::<Events<WindowCreationFailed>>()
+            .unwrap();
+        let failed = failed_events
+            .get_reader()
+            .iter(&failed_events)
+            .next()
+            .unwrap();
+        assert_eq!(failed.id, id);
+    }
+
+    #[test]
+    fn polled_events_update_the_window() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            id,
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            2.0,
+            None,
+        ));
+        world.insert_resource(windows);
+        world.insert_resource(TestBackend {
+            created: vec![id],
+            events: vec![
+                WindowBackendEvent::Resized {
+                    id,
+                    width: 800,
+                    height: 600,
+                },
+                WindowBackendEvent::Focused { id, focused: true },
+            ],
+            ..Default::default()
+        });
+        world.insert_resource(FocusedWindow::default());
+        world.insert_resource(Events::<WindowResized>::default());
+        world.insert_resource(Events::<WindowFocused>::default());
+
+        poll_window_events::<TestBackend>(&mut world);
+        let window = world.get_resource::<Windows>().unwrap().get(id).unwrap();
+        assert_eq!(
+            (window.physical_width(), window.physical_height()),
+            (800, 600)
+        );
+        assert!(window.is_focused());
+        assert_eq!(
+            world.get_resource::<FocusedWindow>().unwrap().get(),
+            Some(id)
+        );
+        let resized_events = world.get_resource::<Events<WindowResized>>().unwrap();
+        let resized = resized_events
+            .get_reader()
+            .iter(&resized_events)
+            .map(|event| (event.width, event.height))
+            .collect::<Vec<_>>();
+        assert_eq!(resized, [(400.0, 300.0)]);
+        assert!(world
+            .get_resource::<TestBackend>()
+            .unwrap()
+            .events
+            .is_empty());
+    }
+}
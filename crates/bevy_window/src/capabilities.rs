@@ -0,0 +1,78 @@
+use crate::{CursorGrabMode, WindowCommand, WindowDescriptor, WindowMode};
+
+/// What the windowing backend supports, so apps can hide settings that would not apply. Inserted
+/// as a resource by the [WindowPlugin](crate::WindowPlugin), and filled in by the backend once
+/// it has started; until then every capability is assumed.
+///
+/// Commands and [WindowDescriptor] settings that need a missing capability are reported with a
+/// [WindowCommandUnsupported](crate::WindowCommandUnsupported) event. The backend still applies
+/// what it can, e.g. a borderless window instead of exclusive fullscreen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowCapabilities {
+    /// [WindowMode::Fullscreen] and [WindowMode::SizedFullscreen] change the video mode of the
+    /// monitor.
+    pub exclusive_fullscreen: bool,
+    /// [CursorGrabMode::Locked] keeps the cursor in place, natively or emulated.
+    pub cursor_lock: bool,
+    /// Windows can be created with [WindowDescriptor::transparent].
+    pub transparency: bool,
+    /// Decorations can be turned off and on with
+    /// [Window::set_decorations](crate::Window::set_decorations).
+    pub decorations_toggle: bool,
+}
+
+impl Default for WindowCapabilities {
+    fn default() -> Self {
+        WindowCapabilities {
+            exclusive_fullscreen: true,
+            cursor_lock: true,
+            transparency: true,
+            decorations_toggle: true,
+        }
+    }
+}
+
+impl WindowCapabilities {
+    /// The name of the capability-dependent command the backend cannot fully apply, if `command`
+    /// needs a missing capability.
+    pub fn unsupported_command(&self, command: &WindowCommand) -> Option<&'static str> {
+        match command {
+            WindowCommand::SetWindowMode {
+                mode: WindowMode::Fullscreen { .. } | WindowMode::SizedFullscreen,
+                ..
+            } if !self.exclusive_fullscreen => Some("SetWindowMode"),
+            WindowCommand::SetCursorGrabMode {
+                mode: CursorGrabMode::Locked,
+            } if !self.cursor_lock => Some("SetCursorGrabMode"),
+            WindowCommand::SetTransparent { .. } if !self.transparency => Some("SetTransparent"),
+            WindowCommand::SetDecorations { .. } if !self.decorations_toggle => {
+                Some("SetDecorations")
+            }
+            _ => None,
+        }
+    }
+
+    /// The names of the commands that correspond to the settings of `descriptor` which need a
+    /// missing capability.
+    pub fn unsupported_settings(&self, descriptor: &WindowDescriptor) -> Vec<&'static str> {
+        let mut unsupported = Vec::new();
+        if !self.exclusive_fullscreen
+            && matches!(
+                descriptor.mode,
+                WindowMode::Fullscreen { .. } | WindowMode::SizedFullscreen
+            )
+        {
+            unsupported.push("SetWindowMode");
+        }
+        if !self.cursor_lock && descriptor.cursor_grab_mode == CursorGrabMode::Locked {
+            unsupported.push("SetCursorGrabMode");
+        }
+        if !self.transparency && descriptor.transparent {
+            unsupported.push("SetTransparent");
+        }
+        if !self.decorations_toggle && !descriptor.decorations {
+            unsupported.push("SetDecorations");
+        }
+        unsupported
+    }
+}
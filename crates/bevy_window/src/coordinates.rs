@@ -0,0 +1,43 @@
+/// Where the origin of window coordinates is, for cursor and touch positions and the positions
+/// passed to [Window::set_cursor_position](crate::Window::set_cursor_position) and
+/// [Window::set_ime_position](crate::Window::set_ime_position). Inserted as a resource by the
+/// [WindowPlugin](crate::WindowPlugin), backends convert the positions of the platform with it.
+///
+/// Coordinates are in logical pixels either way, and [DropZone](crate::DropZone)s are compared
+/// with cursor positions, so they use the same convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowCoordinateConvention {
+    /// `y` grows downwards, like on most platforms and in UI layouts.
+    TopLeft,
+    /// `y` grows upwards, like in world space.
+    #[default]
+    BottomLeft,
+}
+
+impl WindowCoordinateConvention {
+    /// Converts `y` between a top left origin and this convention, in a window that is
+    /// `window_height` high. Converting twice returns the original value.
+    #[inline]
+    pub fn convert_y(self, y: f32, window_height: f32) -> f32 {
+        match self {
+            WindowCoordinateConvention::TopLeft => y,
+            WindowCoordinateConvention::BottomLeft => window_height - y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_y_round_trips() {
+        let convention = WindowCoordinateConvention::BottomLeft;
+        assert_eq!(convention.convert_y(20.0, 720.0), 700.0);
+        assert_eq!(convention.convert_y(700.0, 720.0), 20.0);
+        assert_eq!(
+            WindowCoordinateConvention::TopLeft.convert_y(20.0, 720.0),
+            20.0
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use bevy_math::Vec2;
+use bevy_utils::{Duration, Instant};
+use std::collections::VecDeque;
+
+/// The icon to display for a window's cursor.
+///
+/// Examples of all of these cursors can be found
+/// [here](https://www.w3schools.com/cssref/playit.asp?filename=playcss_cursor). The names match
+/// the CSS cursor property, with [CursorIcon::Default] being `auto`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CursorIcon {
+    /// The platform-dependent default cursor, usually an arrow.
+    #[default]
+    Default,
+    Crosshair,
+    /// A pointing hand, usually shown over links and buttons.
+    Hand,
+    Arrow,
+    Move,
+    /// An I-beam, shown over editable or selectable text.
+    Text,
+    Wait,
+    Help,
+    Progress,
+    NotAllowed,
+    ContextMenu,
+    Cell,
+    VerticalText,
+    Alias,
+    Copy,
+    NoDrop,
+    Grab,
+    Grabbing,
+    AllScroll,
+    ZoomIn,
+    ZoomOut,
+    EResize,
+    NResize,
+    NeResize,
+    NwResize,
+    SResize,
+    SeResize,
+    SwResize,
+    WResize,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+}
+
+/// Samples older than this do not contribute to the velocity of the cursor.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(50);
+/// The furthest the cursor is extrapolated beyond its last known position.
+const MAX_EXTRAPOLATION: Duration = Duration::from_millis(50);
+
+/// Extrapolates the cursor position of a window from its recent movement, so that UI following
+/// the cursor does not visibly lag behind it when frames take long.
+#[derive(Debug, Clone, Default)]
+pub struct CursorPredictor {
+    samples: VecDeque<(Instant, Vec2)>,
+}
+
+impl CursorPredictor {
+    pub fn push(&mut self, time: Instant, position: Vec2) {
+        self.samples.push_back((time, position));
+        while let Some((oldest, _)) = self.samples.front() {
+            if time.saturating_duration_since(*oldest) > VELOCITY_WINDOW {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// The expected cursor position at `at`, clamped to a window of the given logical `size`.
+    /// A cursor that has not moved recently is expected to stay where it is.
+    pub fn predict(&self, at: Instant, size: Vec2) -> Option<Vec2> {
+        let (newest_time, newest) = *self.samples.back()?;
+        let (oldest_time, oldest) = *self.samples.front()?;
+        let since_newest = at.saturating_duration_since(newest_time);
+        let sampled = newest_time.saturating_duration_since(oldest_time);
+        if since_newest > VELOCITY_WINDOW || sampled.as_secs_f32() == 0.0 {
+            return Some(newest);
+        }
+        let velocity = (newest - oldest) / sampled.as_secs_f32();
+        let ahead = since_newest.min(MAX_EXTRAPOLATION).as_secs_f32();
+        Some((newest + velocity * ahead).clamp(Vec2::ZERO, size.max(Vec2::ZERO)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_is_extrapolated_within_bounds() {
+        let start = Instant::now();
+        let mut predictor = CursorPredictor::default();
+        assert_eq!(predictor.predict(start, Vec2::new(100.0, 100.0)), None);
+
+        predictor.push(start, Vec2::new(10.0, 10.0));
+        predictor.push(start + Duration::from_millis(10), Vec2::new(20.0, 10.0));
+        let size = Vec2::new(100.0, 100.0);
+        let predicted = predictor
+            .predict(start + Duration::from_millis(20), size)
+            .unwrap();
+        assert!((predicted - Vec2::new(30.0, 10.0)).length() < 0.01);
+
+        // clamped to the window
+        let predicted = predictor
+            .predict(start + Duration::from_millis(20), Vec2::new(25.0, 100.0))
+            .unwrap();
+        assert!((predicted - Vec2::new(25.0, 10.0)).length() < 0.01);
+
+        // a cursor at rest stays where it is
+        let predicted = predictor
+            .predict(start + Duration::from_millis(500), size)
+            .unwrap();
+        assert_eq!(predicted, Vec2::new(20.0, 10.0));
+    }
+}
@@ -0,0 +1,184 @@
+use std::path::PathBuf;
+
+use crate::{FileDragAndDrop, WindowId, Windows};
+use bevy_app::{EventReader, EventWriter};
+use bevy_ecs::system::{Local, Res};
+use bevy_math::Vec2;
+use bevy_utils::HashMap;
+
+/// Identifies a drop zone registered in [DropZones].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DropZoneId(u64);
+
+/// A rectangular region of a window that can receive dropped files.
+///
+/// Coordinates are in logical pixels in the
+/// [WindowCoordinateConvention](crate::WindowCoordinateConvention), matching
+/// [Window::cursor_position](crate::Window::cursor_position).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DropZone {
+    pub window: WindowId,
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl DropZone {
+    pub fn contains(&self, position: Vec2) -> bool {
+        position.x >= self.min.x
+            && position.x < self.max.x
+            && position.y >= self.min.y
+            && position.y < self.max.y
+    }
+}
+
+/// The set of regions that receive [DropZoneEvent]s while a file is hovered over a window.
+#[derive(Debug, Default)]
+pub struct DropZones {
+    zones: Vec<(DropZoneId, DropZone)>,
+    next_id: u64,
+}
+
+impl DropZones {
+    pub fn register(&mut self, zone: DropZone) -> DropZoneId {
+        let id = DropZoneId(self.next_id);
+        self.next_id += 1;
+        self.zones.push((id, zone));
+        id
+    }
+
+    /// Moves or resizes an already registered zone. Returns `false` if the zone is unknown.
+    pub fn update(&mut self, id: DropZoneId, zone: DropZone) -> bool {
+        match self.zones.iter_mut().find(|(zone_id, _)| *zone_id == id) {
+            Some((_, old)) => {
+                *old = zone;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn unregister(&mut self, id: DropZoneId) -> Option<DropZone> {
+        let index = self.zones.iter().position(|(zone_id, _)| *zone_id == id)?;
+        Some(self.zones.remove(index).1)
+    }
+
+    pub fn get(&self, id: DropZoneId) -> Option<&DropZone> {
+        self.zones
+            .iter()
+            .find(|(zone_id, _)| *zone_id == id)
+            .map(|(_, zone)| zone)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (DropZoneId, &DropZone)> {
+        self.zones.iter().map(|(id, zone)| (*id, zone))
+    }
+
+    /// The zone of `window` that would receive a drop at `position`. When zones overlap, the
+    /// most recently registered one wins.
+    pub fn zone_at(&self, window: WindowId, position: Vec2) -> Option<DropZoneId> {
+        self.zones
+            .iter()
+            .rev()
+            .find(|(_, zone)| zone.window == window && zone.contains(position))
+            .map(|(id, _)| *id)
+    }
+}
+
+/// Sent when the cursor enters or leaves a [DropZone] while a file is hovered over its window.
+#[derive(Debug, Clone)]
+pub enum DropZoneEvent {
+    Entered {
+        id: WindowId,
+        zone: DropZoneId,
+        path_buf: PathBuf,
+    },
+    Left {
+        id: WindowId,
+        zone: DropZoneId,
+    },
+}
+
+/// The file currently hovered over each window and the zone it is over.
+#[derive(Default)]
+pub struct DropZoneHoverState {
+    hovered: HashMap<WindowId, (PathBuf, Option<DropZoneId>)>,
+}
+
+pub fn drop_zone_system(
+    windows: Res<Windows>,
+    drop_zones: Res<DropZones>,
+    mut state: Local<DropZoneHoverState>,
+    mut file_drag_and_drop_events: EventReader<FileDragAndDrop>,
+    mut drop_zone_events: EventWriter<DropZoneEvent>,
+) {
+    for event in file_drag_and_drop_events.iter() {
+        match event {
+            FileDragAndDrop::HoveredFile { id, path_buf } => {
+                state
+                    .hovered
+                    .entry(*id)
+                    .or_insert_with(|| (path_buf.clone(), None));
+            }
+            FileDragAndDrop::DroppedFile { id, .. }
+            | FileDragAndDrop::HoveredFileCancelled { id } => {
+                if let Some((_, Some(zone))) = state.hovered.remove(id) {
+                    drop_zone_events.send(DropZoneEvent::Left { id: *id, zone });
+                }
+            }
+        }
+    }
+
+    for (id, (path_buf, current)) in state.hovered.iter_mut() {
+        let next = windows
+            .get(*id)
+            .and_then(|window| window.cursor_position())
+            .and_then(|position| drop_zones.zone_at(*id, position));
+        if next == *current {
+            continue;
+        }
+
+        if let Some(zone) = *current {
+            drop_zone_events.send(DropZoneEvent::Left { id: *id, zone });
+        }
+        if let Some(zone) = next {
+            drop_zone_events.send(DropZoneEvent::Entered {
+                id: *id,
+                zone,
+                path_buf: path_buf.clone(),
+            });
+        }
+        *current = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zone_at_prefers_latest_registration() {
+        let window = WindowId::primary();
+        let mut drop_zones = DropZones::default();
+        let back = drop_zones.register(DropZone {
+            window,
+            min: Vec2::new(0., 0.),
+            max: Vec2::new(100., 100.),
+        });
+        let front = drop_zones.register(DropZone {
+            window,
+            min: Vec2::new(50., 50.),
+            max: Vec2::new(100., 100.),
+        });
+
+        assert_eq!(drop_zones.zone_at(window, Vec2::new(10., 10.)), Some(back));
+        assert_eq!(drop_zones.zone_at(window, Vec2::new(60., 60.)), Some(front));
+        assert_eq!(drop_zones.zone_at(window, Vec2::new(100., 10.)), None);
+        assert_eq!(
+            drop_zones.zone_at(WindowId::new(), Vec2::new(10., 10.)),
+            None
+        );
+
+        drop_zones.unregister(front);
+        assert_eq!(drop_zones.zone_at(window, Vec2::new(60., 60.)), Some(back));
+    }
+}
@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
-use super::{WindowDescriptor, WindowId};
+use super::{
+    SafeAreaInsets, SurfaceHints, SystemPreferences, WindowCommandError,
+    WindowCreationDeniedReason, WindowDescriptor, WindowId, WindowTheme,
+};
 use bevy_math::{IVec2, Vec2};
 
 /// A window event that is sent whenever a window has been resized.
@@ -24,10 +27,46 @@ pub struct CloseWindow {
     pub id: WindowId,
 }
 
-/// An event that is sent whenever a new window is created.
+/// An event that is sent whenever a new window is created. Fields may be added to it, so it is
+/// constructed with [WindowCreated::new] outside of this crate.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct WindowCreated {
     pub id: WindowId,
+    /// The surface configuration requested for the window.
+    pub surface_hints: SurfaceHints,
+}
+
+impl WindowCreated {
+    /// The event for the window `id`, without surface hints.
+    pub fn new(id: WindowId) -> Self {
+        WindowCreated {
+            id,
+            surface_hints: SurfaceHints::default(),
+        }
+    }
+}
+
+/// An event that is sent whenever a window has been closed and removed from
+/// [Windows](crate::Windows).
+#[derive(Debug, Clone)]
+pub struct WindowClosed {
+    pub id: WindowId,
+}
+
+/// An event that is sent at the start of the frame after a window was added to
+/// [Windows](crate::Windows), unlike [WindowCreated] also for windows that were not created
+/// through [CreateWindow].
+#[derive(Debug, Clone)]
+pub struct WindowAdded {
+    pub id: WindowId,
+}
+
+/// An event that is sent at the start of the frame after a window was removed from
+/// [Windows](crate::Windows), however it was removed.
+#[derive(Debug, Clone)]
+pub struct WindowRemoved {
+    pub id: WindowId,
 }
 
 /// An event that is sent whenever a close was requested for a window. For example: when the "close"
@@ -37,6 +76,13 @@ pub struct WindowCloseRequested {
     pub id: WindowId,
 }
 
+/// An event that is sent when a close request was received but has to be repeated before the
+/// window closes. See [CloseConfirmation](crate::CloseConfirmation).
+#[derive(Debug, Clone)]
+pub struct WindowCloseConfirmationRequested {
+    pub id: WindowId,
+}
+
 #[derive(Debug, Clone)]
 pub struct CursorMoved {
     pub id: WindowId,
@@ -80,6 +126,92 @@ pub struct WindowBackendScaleFactorChanged {
     pub scale_factor: f64,
 }
 
+/// An event that indicates a window's vsync setting has changed and its swap chain should be
+/// recreated with the new present mode.
+#[derive(Debug, Clone)]
+pub struct WindowVsyncChanged {
+    pub id: WindowId,
+    pub vsync: bool,
+}
+
+/// An event that is sent when the backend could not apply a command to a window, e.g. because
+/// no video mode matches a requested [WindowMode::Fullscreen](crate::WindowMode::Fullscreen),
+/// and the [BackendErrorPolicy](crate::BackendErrorPolicy) is `EmitEvent`.
+#[derive(Debug, Clone)]
+pub struct WindowCommandFailed {
+    pub id: WindowId,
+    pub error: WindowCommandError,
+}
+
+/// An event that is sent when a [WindowCommand](crate::WindowCommand), or the matching setting
+/// of a new window, needs something the backend does not support, see
+/// [WindowCapabilities](crate::WindowCapabilities). Commands that the backend rejects with
+/// [WindowCommandError::Unsupported](crate::WindowCommandError) are handled according to the
+/// [BackendErrorPolicy](crate::BackendErrorPolicy) as well, while those it still applies in part
+/// are only reported here.
+#[derive(Debug, Clone)]
+pub struct WindowCommandUnsupported {
+    pub id: WindowId,
+    /// The name of the command, e.g. `"SetTransparent"`.
+    pub command: &'static str,
+}
+
+/// An event that is sent when the backend failed to create the window of a [CreateWindow]
+/// request, e.g. because the platform supports only one window. The failure is also handled
+/// according to the [BackendErrorPolicy](crate::BackendErrorPolicy).
+#[derive(Debug, Clone)]
+pub struct WindowCreationFailed {
+    pub id: WindowId,
+    pub error: WindowCommandError,
+}
+
+/// An event that is sent when a [CreateWindow] request was dropped because it exceeded the
+/// [WindowCreationLimits](crate::WindowCreationLimits).
+#[derive(Debug, Clone)]
+pub struct WindowCreationDenied {
+    pub id: WindowId,
+    pub reason: WindowCreationDeniedReason,
+}
+
+/// An event that is sent when the backend asks for a window to be redrawn, either because the
+/// window was damaged or because a redraw was requested with
+/// [Window::request_redraw](crate::Window::request_redraw).
+#[derive(Debug, Clone)]
+pub struct WindowRedrawRequested {
+    pub id: WindowId,
+}
+
+/// An event that is sent when the system cancelled all touches on a window, e.g. because of palm
+/// rejection or a system gesture. It follows the `Cancelled` touch events of the individual
+/// touches, so interactions driven by them can be rolled back at once.
+#[derive(Debug, Clone)]
+pub struct TouchesCancelled {
+    pub window: WindowId,
+}
+
+/// An event that is sent when the accessibility preferences of the operating system changed. The
+/// [SystemPreferences](crate::SystemPreferences) resource is updated before it is sent.
+#[derive(Debug, Clone)]
+pub struct SystemPreferencesChanged {
+    pub preferences: SystemPreferences,
+}
+
+/// An event that is sent when the operating system suspends or resumes the application, or
+/// warns it about memory.
+///
+/// On Android, the native windows and with them all render surfaces are destroyed while the
+/// application is suspended, so renderers drop their surfaces on `Suspended` and create them
+/// again on `Resumed`. The backend keeps the native windows alive until the app was updated with
+/// `Suspended`. Other platforms only report `Resumed` once at startup, if at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplicationLifecycle {
+    Suspended,
+    Resumed,
+    /// The system is low on memory and will terminate the application unless it frees some,
+    /// e.g. by dropping caches. Only reported on iOS.
+    MemoryWarning,
+}
+
 /// Events related to files being dragged and dropped on a window.
 #[derive(Debug, Clone)]
 pub enum FileDragAndDrop {
@@ -90,6 +222,73 @@ pub enum FileDragAndDrop {
     HoveredFileCancelled { id: WindowId },
 }
 
+/// An event that is sent when the theme of a window changes, e.g. because the user switched
+/// the system between light and dark mode.
+#[derive(Debug, Clone)]
+pub struct WindowThemeChanged {
+    pub id: WindowId,
+    pub theme: WindowTheme,
+}
+
+/// An event that is sent when the [SafeAreaInsets](crate::SafeAreaInsets) of a window changed,
+/// e.g. because the device was rotated.
+#[derive(Debug, Clone)]
+pub struct SafeAreaChanged {
+    pub id: WindowId,
+    pub insets: SafeAreaInsets,
+}
+
+/// Why the render surface of a window was lost or has to be recreated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceChangeReason {
+    /// The window was resized to zero in either dimension, e.g. because it was minimized.
+    /// Swapchains cannot be created at that size.
+    ZeroSize,
+    /// The scale factor changed by a large ratio, e.g. when the window moved between a
+    /// standard and a high DPI monitor, which changes the physical size of the surface as much.
+    ScaleFactorChanged,
+    /// The application was suspended, which destroys the native windows on Android.
+    Suspended,
+}
+
+/// An event that is sent when the render surface of a window cannot be presented to until a
+/// [SurfaceRecreated] event is sent for the window. Renderers should drop the surface.
+#[derive(Debug, Clone)]
+pub struct SurfaceLost {
+    pub id: WindowId,
+    pub reason: SurfaceChangeReason,
+}
+
+/// An event that is sent when renderers have to rebuild the surface or swapchain of a window,
+/// after a [SurfaceLost] event with the same `reason` or for a drastic scale factor change.
+#[derive(Debug, Clone)]
+pub struct SurfaceRecreated {
+    pub id: WindowId,
+    pub reason: SurfaceChangeReason,
+}
+
+/// An event that is sent when the backend reports that a window was minimized, e.g. to pause
+/// the game. See [Window::is_minimized](crate::Window::is_minimized) for the platforms that
+/// report it.
+#[derive(Debug, Clone)]
+pub struct WindowMinimized {
+    pub id: WindowId,
+}
+
+/// An event that is sent when the backend reports that a window was maximized.
+#[derive(Debug, Clone)]
+pub struct WindowMaximized {
+    pub id: WindowId,
+}
+
+/// An event that is sent when a window is no longer minimized, or a maximized window is back to
+/// its normal size. A window that was maximized before it was minimized is still maximized
+/// after it is restored.
+#[derive(Debug, Clone)]
+pub struct WindowRestored {
+    pub id: WindowId,
+}
+
 /// An event that is sent when a window is repositioned in physical pixels.
 #[derive(Debug, Clone)]
 pub struct WindowMoved {
@@ -1,34 +1,67 @@
+mod backend;
+mod capabilities;
+mod coordinates;
+mod cursor;
+mod drop_zone;
 mod event;
+mod limits;
+mod monitor;
+mod preferences;
+mod raw_window_handle;
+mod reconcile;
+mod stats;
+mod surface_hints;
 mod system;
+mod throttle;
 mod window;
 mod windows;
 
+pub use backend::*;
 use bevy_ecs::system::IntoSystem;
+pub use capabilities::*;
+pub use coordinates::*;
+pub use cursor::*;
+pub use drop_zone::*;
 pub use event::*;
+pub use limits::*;
+pub use monitor::*;
+pub use preferences::*;
+pub use raw_window_handle::*;
+pub use reconcile::*;
+pub use stats::*;
+pub use surface_hints::*;
 pub use system::*;
+pub use throttle::*;
 pub use window::*;
 pub use windows::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, ReceivedCharacter, Window,
-        WindowDescriptor, WindowMoved, Windows,
+        CursorEntered, CursorIcon, CursorLeft, CursorMoved, FileDragAndDrop, ReceivedCharacter,
+        Window, WindowDescriptor, WindowMoved, Windows,
     };
 }
 
 use bevy_app::{prelude::*, Events};
 
 pub struct WindowPlugin {
-    pub add_primary_window: bool,
-    pub exit_on_close: bool,
+    /// Creates the primary window with this descriptor, or no window if `None`. A
+    /// [WindowDescriptor] inserted as a resource before the plugin is added takes precedence.
+    pub primary_window: Option<WindowDescriptor>,
+    /// When the app exits because its windows were closed.
+    pub exit_condition: ExitCondition,
+    /// Closes windows when a close is requested for them, with [close_when_requested_system].
+    /// Otherwise apps have to close them in reaction to [WindowCloseRequested] events.
+    pub close_when_requested: bool,
 }
 
 impl Default for WindowPlugin {
     fn default() -> Self {
         WindowPlugin {
-            add_primary_window: true,
-            exit_on_close: true,
+            primary_window: Some(WindowDescriptor::default()),
+            exit_condition: ExitCondition::default(),
+            close_when_requested: true,
         }
     }
 }
@@ -39,7 +72,11 @@ impl Plugin for WindowPlugin {
             .add_event::<CreateWindow>()
             .add_event::<WindowCreated>()
             .add_event::<WindowCloseRequested>()
+            .add_event::<WindowCloseConfirmationRequested>()
             .add_event::<CloseWindow>()
+            .add_event::<WindowClosed>()
+            .add_event::<WindowAdded>()
+            .add_event::<WindowRemoved>()
             .add_event::<CursorMoved>()
             .add_event::<CursorEntered>()
             .add_event::<CursorLeft>()
@@ -47,16 +84,52 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowFocused>()
             .add_event::<WindowScaleFactorChanged>()
             .add_event::<WindowBackendScaleFactorChanged>()
+            .add_event::<WindowVsyncChanged>()
+            .add_event::<WindowCommandFailed>()
+            .add_event::<WindowCommandUnsupported>()
+            .add_event::<WindowCreationDenied>()
+            .add_event::<WindowCreationFailed>()
+            .add_event::<WindowRedrawRequested>()
+            .add_event::<TouchesCancelled>()
+            .add_event::<SystemPreferencesChanged>()
+            .add_event::<ApplicationLifecycle>()
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
-            .init_resource::<Windows>();
+            .add_event::<WindowMinimized>()
+            .add_event::<WindowMaximized>()
+            .add_event::<WindowRestored>()
+            .add_event::<SafeAreaChanged>()
+            .add_event::<SurfaceLost>()
+            .add_event::<SurfaceRecreated>()
+            .add_event::<WindowThemeChanged>()
+            .add_event::<MonitorModeChanged>()
+            .add_event::<DropZoneEvent>()
+            .init_resource::<Windows>()
+            .init_resource::<FocusedWindow>()
+            .init_resource::<WindowStats>()
+            .init_resource::<DropZones>()
+            .init_resource::<WindowThrottle>()
+            .init_resource::<Monitors>()
+            .init_resource::<SystemPreferences>()
+            .init_resource::<SystemInfo>()
+            .init_resource::<BackendErrorPolicy>()
+            .init_resource::<WindowCapabilities>()
+            .init_resource::<RawWindowHandles>()
+            .init_resource::<WindowCoordinateConvention>()
+            .init_resource::<WindowCommandBudget>()
+            .init_resource::<WindowCreationLimits>()
+            .init_resource::<ScaleChangePolicy>()
+            .add_system(drop_zone_system.system())
+            .add_system(refit_fullscreen_windows_system.system())
+            .add_system_to_stage(CoreStage::First, window_list_changed_system.system())
+            .add_system_to_stage(CoreStage::First, reconcile_scale_change_system.system());
 
-        if self.add_primary_window {
+        if let Some(primary_window) = &self.primary_window {
             let world = app.world_mut();
             let window_descriptor = world
                 .get_resource::<WindowDescriptor>()
                 .map(|descriptor| (*descriptor).clone())
-                .unwrap_or_else(WindowDescriptor::default);
+                .unwrap_or_else(|| primary_window.clone());
             let mut create_window_event = world.get_resource_mut::<Events<CreateWindow>>().unwrap();
             create_window_event.send(CreateWindow {
                 id: WindowId::primary(),
@@ -64,8 +137,17 @@ impl Plugin for WindowPlugin {
             });
         }
 
-        if self.exit_on_close {
-            app.add_system(exit_on_window_close_system.system());
+        if self.close_when_requested {
+            app.add_system(close_when_requested_system.system());
+        }
+        match self.exit_condition {
+            ExitCondition::OnPrimaryClosed => {
+                app.add_system(exit_on_primary_closed_system.system());
+            }
+            ExitCondition::OnAllClosed => {
+                app.add_system(exit_on_all_closed_system.system());
+            }
+            ExitCondition::DontExit => {}
         }
     }
 }
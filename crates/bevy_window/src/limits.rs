@@ -0,0 +1,109 @@
+use bevy_utils::{Duration, Instant};
+use std::{collections::VecDeque, fmt};
+
+/// Safety limits for [CreateWindow](crate::CreateWindow) requests, so a system that sends them
+/// every frame cannot exhaust the resources of the OS. Requests over a limit are dropped and
+/// reported with a [WindowCreationDenied](crate::WindowCreationDenied) event. Inserted as a
+/// resource by the [WindowPlugin](crate::WindowPlugin).
+#[derive(Debug, Clone)]
+pub struct WindowCreationLimits {
+    /// How many windows may be open at the same time, `None` for no limit.
+    pub max_windows: Option<usize>,
+    /// How many windows may be created per second, `None` for no limit.
+    pub max_creations_per_second: Option<usize>,
+    recent_creations: VecDeque<Instant>,
+}
+
+impl Default for WindowCreationLimits {
+    fn default() -> Self {
+        WindowCreationLimits {
+            max_windows: Some(64),
+            max_creations_per_second: Some(64),
+            recent_creations: VecDeque::new(),
+        }
+    }
+}
+
+impl WindowCreationLimits {
+    /// Checks whether another window may be created while `open_windows` are open, and counts the
+    /// creation if so.
+    pub fn try_create(
+        &mut self,
+        open_windows: usize,
+        now: Instant,
+    ) -> Result<(), WindowCreationDeniedReason> {
+        if let Some(max_windows) = self.max_windows {
+            if open_windows >= max_windows {
+                return Err(WindowCreationDeniedReason::TooManyWindows { max_windows });
+            }
+        }
+
+        while let Some(created) = self.recent_creations.front() {
+            if now.saturating_duration_since(*created) < Duration::from_secs(1) {
+                break;
+            }
+            self.recent_creations.pop_front();
+        }
+        if let Some(max_creations_per_second) = self.max_creations_per_second {
+            if self.recent_creations.len() >= max_creations_per_second {
+                return Err(WindowCreationDeniedReason::RateLimited {
+                    max_creations_per_second,
+                });
+            }
+        }
+        self.recent_creations.push_back(now);
+        Ok(())
+    }
+}
+
+/// Which of the [WindowCreationLimits] a [CreateWindow](crate::CreateWindow) request exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowCreationDeniedReason {
+    TooManyWindows { max_windows: usize },
+    RateLimited { max_creations_per_second: usize },
+}
+
+impl fmt::Display for WindowCreationDeniedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowCreationDeniedReason::TooManyWindows { max_windows } => {
+                write!(f, "no more than {} windows may be open", max_windows)
+            }
+            WindowCreationDeniedReason::RateLimited {
+                max_creations_per_second,
+            } => write!(
+                f,
+                "no more than {} windows may be created per second",
+                max_creations_per_second
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creations_are_limited() {
+        let mut limits = WindowCreationLimits {
+            max_windows: Some(3),
+            max_creations_per_second: Some(2),
+            ..Default::default()
+        };
+        let start = Instant::now();
+        assert_eq!(limits.try_create(0, start), Ok(()));
+        assert_eq!(limits.try_create(1, start), Ok(()));
+        assert_eq!(
+            limits.try_create(2, start + Duration::from_millis(500)),
+            Err(WindowCreationDeniedReason::RateLimited {
+                max_creations_per_second: 2
+            })
+        );
+        assert_eq!(limits.try_create(2, start + Duration::from_secs(1)), Ok(()));
+        assert_eq!(
+            limits.try_create(3, start + Duration::from_secs(5)),
+            Err(WindowCreationDeniedReason::TooManyWindows { max_windows: 3 })
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{WindowMode, Windows};
+use bevy_app::EventReader;
+use bevy_ecs::system::ResMut;
+use bevy_math::{IVec2, UVec2};
+
+/// A display mode supported by a [Monitor].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoMode {
+    pub physical_size: UVec2,
+    pub bit_depth: u16,
+    /// In Hz.
+    pub refresh_rate: u16,
+}
+
+/// A monitor connected to the system, as reported by the backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    pub name: Option<String>,
+    pub physical_size: UVec2,
+    /// The position of the top left corner of the monitor on the desktop.
+    pub physical_position: IVec2,
+    pub scale_factor: f64,
+    /// The highest refresh rate supported at the current resolution, in Hz.
+    pub refresh_rate: Option<u16>,
+    /// In the order of the backend, which is the order expected by
+    /// [WindowMode::Fullscreen]'s `video_mode_index`.
+    pub video_modes: Vec<VideoMode>,
+    pub is_primary: bool,
+}
+
+impl Monitor {
+    /// Whether `position` lies on this monitor, in physical desktop coordinates.
+    pub fn contains(&self, position: IVec2) -> bool {
+        let end = self.physical_position + self.physical_size.as_i32();
+        position.cmpge(self.physical_position).all() && position.cmplt(end).all()
+    }
+
+    /// Whether `other` is most likely the same physical monitor, which is decided by name
+    /// where the backend reports names.
+    fn is_same_as(&self, other: &Monitor) -> bool {
+        match (&self.name, &other.name) {
+            (Some(name), Some(other_name)) => name == other_name,
+            _ => self.physical_position == other.physical_position,
+        }
+    }
+}
+
+/// An event that is sent when the resolution or scale factor of a connected monitor changes,
+/// e.g. because the user changed the display settings or a TV switched modes.
+#[derive(Debug, Clone)]
+pub struct MonitorModeChanged {
+    pub previous: Monitor,
+    pub monitor: Monitor,
+}
+
+/// The monitors connected to the system. Updated by the backend when it starts, whenever a
+/// window reports a scale factor change and when the backend notices that the monitor setup
+/// changed otherwise.
+#[derive(Debug, Default)]
+pub struct Monitors {
+    monitors: Vec<Monitor>,
+}
+
+impl Monitors {
+    /// Replaces the monitors and returns the changes of those that were already known.
+    pub fn set_from_backend(&mut self, monitors: Vec<Monitor>) -> Vec<MonitorModeChanged> {
+        let changes = monitors
+            .iter()
+            .filter_map(|monitor| {
+                let previous = self
+                    .monitors
+                    .iter()
+                    .find(|previous| previous.is_same_as(monitor))?;
+                #[allow(clippy::float_cmp)]
+                let changed = previous.physical_size != monitor.physical_size
+                    || previous.scale_factor != monitor.scale_factor;
+                if changed {
+                    Some(MonitorModeChanged {
+                        previous: previous.clone(),
+                        monitor: monitor.clone(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+        self.monitors = monitors;
+        changes
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Monitor> {
+        self.monitors.get(index)
+    }
+
+    pub fn primary(&self) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| monitor.is_primary)
+    }
+
+    /// The monitor chosen by `selection`. [MonitorSelection::Current] is the primary monitor,
+    /// since no window is involved here.
+    pub fn select(&self, selection: MonitorSelection) -> Option<&Monitor> {
+        match selection {
+            MonitorSelection::Primary | MonitorSelection::Current => self.primary(),
+            MonitorSelection::Index(index) => self.get(index),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Monitor> {
+        self.monitors.iter()
+    }
+}
+
+/// Applies the window mode of fullscreen windows again when the mode of their monitor changes,
+/// so that they cover the monitor at its new resolution.
+pub fn refit_fullscreen_windows_system(
+    mut windows: ResMut<Windows>,
+    mut monitor_mode_changed_events: EventReader<MonitorModeChanged>,
+) {
+    for event in monitor_mode_changed_events.iter() {
+        for window in windows.iter_mut() {
+            if window.mode() == WindowMode::Windowed {
+                continue;
+            }
+            // a window whose position is unknown may be on the monitor as well
+            let on_monitor = match window.position() {
+                Some(position) => {
+                    event.previous.contains(position) || event.monitor.contains(position)
+                }
+                None => true,
+            };
+            if on_monitor {
+                window.refit_mode(event.monitor.physical_size);
+            }
+        }
+    }
+}
+
+/// Refers to a monitor, e.g. to place a window on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorSelection {
+    /// The monitor the window is currently on, or the primary monitor for new windows.
+    Current,
+    Primary,
+    /// The monitor at this index of [Monitors].
+    Index(usize),
+}
+
+/// Where a window is placed when it is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowPosition {
+    /// Let the window manager decide.
+    #[default]
+    Automatic,
+    /// Centered on the monitor, like [Window::center](crate::Window::center). There is no
+    /// current monitor before the window exists, so `Current` is the primary monitor.
+    Centered(MonitorSelection),
+    /// The position of the top left corner of the window on the desktop, in physical pixels.
+    At(IVec2),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Window, WindowCommand, WindowDescriptor, WindowId};
+    use bevy_app::Events;
+    use bevy_ecs::{
+        system::{IntoSystem, System},
+        world::World,
+    };
+
+    fn monitor(name: &str, width: u32, scale_factor: f64) -> Monitor {
+        Monitor {
+            name: Some(name.to_string()),
+            physical_size: UVec2::new(width, width * 9 / 16),
+            physical_position: IVec2::ZERO,
+            scale_factor,
+            refresh_rate: Some(60),
+            video_modes: Vec::new(),
+            is_primary: true,
+        }
+    }
+
+    #[test]
+    fn mode_changes_are_reported_for_known_monitors() {
+        let mut monitors = Monitors::default();
+        assert!(monitors
+            .set_from_backend(vec![monitor("HDMI-1", 1920, 1.0)])
+            .is_empty());
+        assert!(monitors
+            .set_from_backend(vec![monitor("HDMI-1", 1920, 1.0)])
+            .is_empty());
+
+        let changes = monitors.set_from_backend(vec![
+            monitor("HDMI-1", 3840, 2.0),
+            monitor("DP-1", 2560, 1.0),
+        ]);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].previous.physical_size.x, 1920);
+        assert_eq!(changes[0].monitor.physical_size.x, 3840);
+        assert!(changes[0].monitor.contains(IVec2::new(3000, 100)));
+        assert!(!changes[0].previous.contains(IVec2::new(3000, 100)));
+    }
+
+    #[test]
+    fn fullscreen_windows_are_refit_to_the_new_resolution() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        let descriptor = WindowDescriptor {
+            mode: WindowMode::BorderlessFullscreen,
+            ..Default::default()
+        };
+        windows.add(Window::new(id, &descriptor, 1920, 1080, 1.0, None));
+        world.insert_resource(windows);
+        let mut events = Events::<MonitorModeChanged>::default();
+        events.send(MonitorModeChanged {
+            previous: monitor("HDMI-1", 1920, 1.0),
+            monitor: monitor("HDMI-1", 3840, 1.0),
+        });
+        world.insert_resource(events);
+
+        let mut system = refit_fullscreen_windows_system.system();
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        let mut windows = world.get_resource_mut::<Windows>().unwrap();
+        let commands = windows
+            .get_mut(id)
+            .unwrap()
+            .drain_commands()
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            commands[..],
+            [WindowCommand::SetWindowMode {
+                mode: WindowMode::BorderlessFullscreen,
+                resolution: (3840, 2160),
+            }]
+        ));
+    }
+}
@@ -0,0 +1,82 @@
+use bevy_utils::Duration;
+
+/// The accessibility preferences the user set in the operating system, as far as the backend
+/// can query them. Updated by the backend when it starts and when a window gains focus, since
+/// the settings may have been changed in the meantime. A
+/// [SystemPreferencesChanged](crate::SystemPreferencesChanged) event is sent when they change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemPreferences {
+    /// The user asked for animations and other motion to be reduced.
+    pub reduced_motion: bool,
+    pub contrast: ContrastPreference,
+    /// How much larger than normal the user wants text to be, `1.0` meaning normal size.
+    pub text_scale: f32,
+}
+
+impl Default for SystemPreferences {
+    fn default() -> Self {
+        SystemPreferences {
+            reduced_motion: false,
+            contrast: ContrastPreference::NoPreference,
+            text_scale: 1.0,
+        }
+    }
+}
+
+/// The contrast the user prefers for the content of windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContrastPreference {
+    #[default]
+    NoPreference,
+    More,
+    Less,
+}
+
+/// The locale and user interface settings of the operating system, as far as the backend can
+/// query them. Gathered by the backend when it starts, so UI defaults can match the user's
+/// configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemInfo {
+    /// The user's locale as a language tag like `en-US`.
+    pub locale: Option<String>,
+    pub color_scheme: ColorScheme,
+    /// The longest time between two clicks that still counts as a double click.
+    pub double_click_interval: Duration,
+    /// How many lines a single step of the mouse wheel should scroll.
+    pub scroll_lines: u32,
+    /// The kind of device the application runs on, only known on iOS.
+    pub device_idiom: DeviceIdiom,
+}
+
+impl Default for SystemInfo {
+    fn default() -> Self {
+        SystemInfo {
+            locale: None,
+            color_scheme: ColorScheme::NoPreference,
+            double_click_interval: Duration::from_millis(500),
+            scroll_lines: 3,
+            device_idiom: DeviceIdiom::Unspecified,
+        }
+    }
+}
+
+/// The kind of device in UIKit terms, so user interfaces can adapt their layout, e.g. to a
+/// phone or a tablet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeviceIdiom {
+    #[default]
+    Unspecified,
+    Phone,
+    Pad,
+    Tv,
+    CarPlay,
+}
+
+/// Whether the user prefers dark or light user interfaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorScheme {
+    #[default]
+    NoPreference,
+    Light,
+    Dark,
+}
@@ -0,0 +1,70 @@
+use crate::WindowId;
+use bevy_utils::HashMap;
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+
+/// A [RawWindowHandle] that can be stored in resources and sent to other threads, e.g. to
+/// create a surface for a custom wgpu, Vulkan or OpenGL renderer.
+///
+/// raw-window-handle 0.3 has no separate display handle: the handles of X11 and Wayland
+/// windows carry their display connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawWindowHandleWrapper(RawWindowHandle);
+
+impl RawWindowHandleWrapper {
+    pub fn new(handle: RawWindowHandle) -> Self {
+        RawWindowHandleWrapper(handle)
+    }
+
+    /// # Safety
+    ///
+    /// The window has to be open while the handle is used. Some platforms, like macOS and iOS,
+    /// only allow using it on the thread of the event loop.
+    pub unsafe fn get_handle(&self) -> HasRawWindowHandleWrapper {
+        HasRawWindowHandleWrapper(self.0)
+    }
+}
+
+// the handle is only a set of pointers, using them is unsafe in the first place
+unsafe impl Send for RawWindowHandleWrapper {}
+unsafe impl Sync for RawWindowHandleWrapper {}
+
+/// Passes a [RawWindowHandleWrapper] to APIs that take a [HasRawWindowHandle].
+pub struct HasRawWindowHandleWrapper(RawWindowHandle);
+
+unsafe impl HasRawWindowHandle for HasRawWindowHandleWrapper {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        self.0
+    }
+}
+
+/// The [RawWindowHandleWrapper]s of all created windows. Inserted as a resource by the
+/// [WindowPlugin](crate::WindowPlugin) and kept up to date by the backend: a handle is added
+/// when a [WindowCreated](crate::WindowCreated) event is sent, and removed when the window
+/// closes.
+#[derive(Debug, Default)]
+pub struct RawWindowHandles {
+    handles: HashMap<WindowId, RawWindowHandleWrapper>,
+}
+
+impl RawWindowHandles {
+    pub fn get(&self, id: WindowId) -> Option<&RawWindowHandleWrapper> {
+        self.handles.get(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (WindowId, &RawWindowHandleWrapper)> {
+        self.handles.iter().map(|(id, handle)| (*id, handle))
+    }
+
+    pub fn insert(&mut self, id: WindowId, handle: RawWindowHandleWrapper) {
+        self.handles.insert(id, handle);
+    }
+
+    pub fn remove(&mut self, id: WindowId) -> Option<RawWindowHandleWrapper> {
+        self.handles.remove(&id)
+    }
+
+    /// Keeps only the handles of the windows for which `f` returns `true`.
+    pub fn retain(&mut self, mut f: impl FnMut(WindowId) -> bool) {
+        self.handles.retain(|id, _| f(*id));
+    }
+}
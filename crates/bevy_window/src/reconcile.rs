@@ -0,0 +1,136 @@
+use crate::{WindowBackendScaleFactorChanged, WindowId, Windows};
+use bevy_app::EventReader;
+use bevy_ecs::system::{Local, Res, ResMut};
+use bevy_utils::HashMap;
+
+/// What happens when the backend resizes a window because its scale factor changed, e.g. when
+/// it is dragged to a monitor with a different DPI on Windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScaleChangePolicy {
+    /// Keeps the size chosen by the backend.
+    #[default]
+    FollowBackend,
+    /// Resizes the window back to its logical size from before the change, so it covers the
+    /// same area in logical pixels.
+    KeepLogicalSize,
+    /// Resizes the window back to its physical size from before the change, so it covers the
+    /// same number of pixels on the screen.
+    KeepPhysicalSize,
+}
+
+/// The logical and physical size of each window.
+type WindowSizes = HashMap<WindowId, ((f32, f32), (u32, u32))>;
+
+/// Resolves size changes made by the backend on scale factor changes according to the
+/// [ScaleChangePolicy] resource.
+pub fn reconcile_scale_change_system(
+    policy: Res<ScaleChangePolicy>,
+    mut windows: ResMut<Windows>,
+    mut scale_factor_changed_events: EventReader<WindowBackendScaleFactorChanged>,
+    mut last_sizes: Local<WindowSizes>,
+) {
+    if *policy == ScaleChangePolicy::FollowBackend {
+        scale_factor_changed_events.iter().for_each(drop);
+        last_sizes.clear();
+        return;
+    }
+
+    for event in scale_factor_changed_events.iter() {
+        let (window, (logical, physical)) =
+            match (windows.get_mut(event.id), last_sizes.get(&event.id)) {
+                (Some(window), Some(previous)) => (window, *previous),
+                _ => continue,
+            };
+        let (width, height) = match *policy {
+            ScaleChangePolicy::KeepLogicalSize => logical,
+            ScaleChangePolicy::KeepPhysicalSize => {
+                let scale_factor = window.scale_factor();
+                (
+                    (physical.0 as f64 / scale_factor) as f32,
+                    (physical.1 as f64 / scale_factor) as f32,
+                )
+            }
+            ScaleChangePolicy::FollowBackend => continue,
+        };
+        #[allow(clippy::float_cmp)]
+        if (window.width(), window.height()) != (width, height) {
+            window.reapply_resolution(width, height);
+        }
+    }
+
+    last_sizes.clear();
+    for window in windows.iter() {
+        last_sizes.insert(
+            window.id(),
+            (
+                (window.width(), window.height()),
+                (window.physical_width(), window.physical_height()),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Window, WindowCommand, WindowDescriptor};
+    use bevy_app::Events;
+    use bevy_ecs::{
+        system::{IntoSystem, System},
+        world::World,
+    };
+
+    #[test]
+    fn logical_size_is_restored_after_scale_change() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            id,
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            1.0,
+            None,
+        ));
+        world.insert_resource(windows);
+        world.insert_resource(ScaleChangePolicy::KeepLogicalSize);
+        world.insert_resource(Events::<WindowBackendScaleFactorChanged>::default());
+
+        let mut system = reconcile_scale_change_system.system();
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        // the backend keeps the physical size when the window moves to a 2x monitor
+        let window = world
+            .get_resource_mut::<Windows>()
+            .unwrap()
+            .into_inner()
+            .get_mut(id)
+            .unwrap();
+        window.update_scale_factor_from_backend(2.0);
+        world
+            .get_resource_mut::<Events<WindowBackendScaleFactorChanged>>()
+            .unwrap()
+            .send(WindowBackendScaleFactorChanged {
+                id,
+                scale_factor: 2.0,
+            });
+        system.run((), &mut world);
+
+        let mut windows = world.get_resource_mut::<Windows>().unwrap();
+        let commands = windows
+            .get_mut(id)
+            .unwrap()
+            .drain_commands()
+            .collect::<Vec<_>>();
+        // the logical resize constraints are applied again at the new scale factor, too
+        assert!(matches!(
+            commands.as_slice(),
+            [WindowCommand::SetResizeConstraints { .. }, WindowCommand::SetResolution {
+                logical_resolution,
+                scale_factor,
+            }] if *logical_resolution == (1280.0, 720.0) && *scale_factor == 2.0
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+use super::WindowId;
+use bevy_utils::{Duration, HashMap, Instant};
+
+/// Counters collected by the window backend over the lifetime of a single window.
+#[derive(Debug, Clone)]
+pub struct WindowStatistics {
+    created_at_frame: u64,
+    resizes: u64,
+    events_received: u64,
+    focused_duration: Duration,
+    focused_since: Option<Instant>,
+}
+
+impl WindowStatistics {
+    pub fn new(created_at_frame: u64, focused: bool) -> Self {
+        WindowStatistics {
+            created_at_frame,
+            resizes: 0,
+            events_received: 0,
+            focused_duration: Duration::default(),
+            focused_since: if focused { Some(Instant::now()) } else { None },
+        }
+    }
+
+    /// The number of app updates that had run when the window was created.
+    #[inline]
+    pub fn created_at_frame(&self) -> u64 {
+        self.created_at_frame
+    }
+
+    /// The number of times the backend reported a change of the window's physical size.
+    #[inline]
+    pub fn resizes(&self) -> u64 {
+        self.resizes
+    }
+
+    /// The number of backend events dispatched for the window.
+    #[inline]
+    pub fn events_received(&self) -> u64 {
+        self.events_received
+    }
+
+    /// The total time the window has had focus, including the current focus period.
+    pub fn time_focused(&self) -> Duration {
+        match self.focused_since {
+            Some(since) => self.focused_duration + since.elapsed(),
+            None => self.focused_duration,
+        }
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn record_event_from_backend(&mut self) {
+        self.events_received += 1;
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn record_resize_from_backend(&mut self) {
+        self.resizes += 1;
+    }
+
+    #[allow(missing_docs)]
+    pub fn record_focus_from_backend(&mut self, focused: bool) {
+        match (focused, self.focused_since) {
+            (true, None) => self.focused_since = Some(Instant::now()),
+            (false, Some(since)) => {
+                self.focused_duration += since.elapsed();
+                self.focused_since = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Per-window [WindowStatistics] for all open windows.
+#[derive(Debug, Default)]
+pub struct WindowStats {
+    windows: HashMap<WindowId, WindowStatistics>,
+}
+
+impl WindowStats {
+    pub fn insert(&mut self, id: WindowId, statistics: WindowStatistics) {
+        self.windows.insert(id, statistics);
+    }
+
+    pub fn remove(&mut self, id: WindowId) -> Option<WindowStatistics> {
+        self.windows.remove(&id)
+    }
+
+    pub fn get(&self, id: WindowId) -> Option<&WindowStatistics> {
+        self.windows.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: WindowId) -> Option<&mut WindowStatistics> {
+        self.windows.get_mut(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (WindowId, &WindowStatistics)> {
+        self.windows
+            .iter()
+            .map(|(id, statistics)| (*id, statistics))
+    }
+}
@@ -0,0 +1,56 @@
+use bevy_utils::HashMap;
+
+/// How a renderer presents frames to a window's surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PresentMode {
+    /// Frames are presented immediately, which may cause tearing.
+    Immediate,
+    /// Frames are presented at the next vertical blank, replacing any frame that is still
+    /// waiting, so there is no tearing and rendering is not blocked.
+    Mailbox,
+    /// Frames are queued and presented at each vertical blank (vsync).
+    Fifo,
+}
+
+/// Per-window surface configuration chosen by the app, which renderers read when they create
+/// the surface of a window.
+///
+/// The hints are only requests: a renderer ignores the ones it does not support, and may fall
+/// back to another value when the device does not support the requested one. Hints that are
+/// specific to a renderer go into `custom`, using keys documented by that renderer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SurfaceHints {
+    /// Overrides the present mode implied by [WindowDescriptor::vsync](crate::WindowDescriptor).
+    pub present_mode: Option<PresentMode>,
+    /// The number of samples per pixel used for multisample anti-aliasing of the window.
+    pub msaa_samples: Option<u32>,
+    /// Whether a high dynamic range surface format is preferred, if the display supports one.
+    pub hdr: Option<bool>,
+    pub custom: HashMap<String, String>,
+}
+
+impl SurfaceHints {
+    pub fn with_present_mode(mut self, present_mode: PresentMode) -> Self {
+        self.present_mode = Some(present_mode);
+        self
+    }
+
+    pub fn with_msaa_samples(mut self, samples: u32) -> Self {
+        self.msaa_samples = Some(samples);
+        self
+    }
+
+    pub fn with_hdr(mut self, hdr: bool) -> Self {
+        self.hdr = Some(hdr);
+        self
+    }
+
+    pub fn with_custom(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.custom.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn custom(&self, key: &str) -> Option<&str> {
+        self.custom.get(key).map(|value| value.as_str())
+    }
+}
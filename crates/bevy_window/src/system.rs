@@ -1,11 +1,226 @@
-use crate::WindowCloseRequested;
+use crate::{
+    WindowCloseConfirmationRequested, WindowCloseRequested, WindowClosed, WindowId, Windows,
+};
 use bevy_app::{AppExit, EventReader, EventWriter};
+use bevy_ecs::system::{Local, Res, ResMut};
+use bevy_utils::{Duration, HashMap, Instant};
 
-pub fn exit_on_window_close_system(
-    mut app_exit_events: EventWriter<AppExit>,
+/// When the app exits on its own because its windows were closed. Set with
+/// [WindowPlugin::exit_condition](crate::WindowPlugin::exit_condition).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExitCondition {
+    /// Exits once the primary window is closed, even if other windows are still open.
+    OnPrimaryClosed,
+    /// Exits once the last window is closed.
+    #[default]
+    OnAllClosed,
+    /// Keeps running without windows, until an [AppExit] event is sent.
+    DontExit,
+}
+
+/// When present as a resource, [close_when_requested_system] requires the close button to be
+/// pressed twice within `timeout` before the window closes, to avoid accidental quits. Each
+/// window is confirmed on its own.
+///
+/// The first request sends a [WindowCloseConfirmationRequested] event, which can be used to
+/// show a "press again to quit" hint.
+#[derive(Debug, Clone, Copy)]
+pub struct CloseConfirmation {
+    pub timeout: Duration,
+}
+
+impl Default for CloseConfirmation {
+    fn default() -> Self {
+        CloseConfirmation {
+            timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Closes windows when a close is requested for them, e.g. with their close button.
+pub fn close_when_requested_system(
+    mut windows: ResMut<Windows>,
     mut window_close_requested_events: EventReader<WindowCloseRequested>,
+    mut close_confirmation_requested_events: EventWriter<WindowCloseConfirmationRequested>,
+    close_confirmation: Option<Res<CloseConfirmation>>,
+    mut first_requests: Local<HashMap<WindowId, Instant>>,
+) {
+    for event in window_close_requested_events.iter() {
+        if let Some(close_confirmation) = &close_confirmation {
+            let now = Instant::now();
+            match first_requests.get(&event.id) {
+                Some(first) if now.duration_since(*first) <= close_confirmation.timeout => {
+                    first_requests.remove(&event.id);
+                }
+                _ => {
+                    first_requests.insert(event.id, now);
+                    close_confirmation_requested_events
+                        .send(WindowCloseConfirmationRequested { id: event.id });
+                    continue;
+                }
+            }
+        }
+        if let Some(window) = windows.get_mut(event.id) {
+            window.close();
+        }
+    }
+}
+
+/// Exits the app once the primary window is closed, for [ExitCondition::OnPrimaryClosed].
+pub fn exit_on_primary_closed_system(
+    mut app_exit_events: EventWriter<AppExit>,
+    mut window_closed_events: EventReader<WindowClosed>,
+) {
+    if window_closed_events
+        .iter()
+        .any(|event| event.id == WindowId::primary())
+    {
+        app_exit_events.send(AppExit);
+    }
+}
+
+/// Exits the app once the last window is closed, for [ExitCondition::OnAllClosed]. Only
+/// closing a window counts, so the app does not exit before its first window was created.
+pub fn exit_on_all_closed_system(
+    windows: Res<Windows>,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut window_closed_events: EventReader<WindowClosed>,
 ) {
-    if window_close_requested_events.iter().next().is_some() {
+    if window_closed_events.iter().count() > 0 && windows.iter().next().is_none() {
         app_exit_events.send(AppExit);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Window, WindowBackend, WindowCommand, WindowCommandError, WindowDescriptor, WindowPlugin,
+    };
+    use bevy_app::{App, CoreStage, Events};
+    use bevy_ecs::system::IntoExclusiveSystem;
+
+    struct TestBackend;
+
+    impl WindowBackend for TestBackend {
+        type Target = ();
+
+        fn create_window(
+            &mut self,
+            _target: &(),
+            _id: WindowId,
+            _descriptor: &WindowDescriptor,
+        ) -> Result<Window, WindowCommandError> {
+            unreachable!()
+        }
+
+        fn apply_command(
+            &mut self,
+            _id: WindowId,
+            _command: &WindowCommand,
+        ) -> Result<(), WindowCommandError> {
+            Ok(())
+        }
+
+        fn close_window(&mut self, _id: WindowId) {}
+    }
+
+    fn test_app(ids: &[WindowId]) -> App {
+        let mut app = App::build();
+        app.add_plugin(WindowPlugin {
+            primary_window: None,
+            ..Default::default()
+        })
+        .insert_resource(TestBackend)
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            crate::apply_window_commands::<TestBackend>.exclusive_system(),
+        );
+        let mut app = app.app;
+        for id in ids.iter().copied() {
+            let window = Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None);
+            app.world.get_resource_mut::<Windows>().unwrap().add(window);
+        }
+        app
+    }
+
+    fn request_close(app: &mut App, id: WindowId) {
+        app.world
+            .get_resource_mut::<Events<WindowCloseRequested>>()
+            .unwrap()
+            .send(WindowCloseRequested { id });
+        app.update();
+    }
+
+    #[test]
+    fn exits_once_the_last_window_is_closed() {
+        let secondary = WindowId::new();
+        let mut app = test_app(&[WindowId::primary(), secondary]);
+        let mut app_exit_reader = app
+            .world
+            .get_resource::<Events<AppExit>>()
+            .unwrap()
+            .get_reader();
+        let request_close = |app: &mut App, id| {
+            // the window closes at the end of the first update, the exit follows in the next
+            request_close(app, id);
+            app.update();
+        };
+
+        request_close(&mut app, secondary);
+        let windows = app.world.get_resource::<Windows>().unwrap();
+        assert!(windows.get(secondary).is_none());
+        assert!(windows.get(WindowId::primary()).is_some());
+        let app_exit_events = app.world.get_resource::<Events<AppExit>>().unwrap();
+        assert_eq!(app_exit_reader.iter(app_exit_events).count(), 0);
+
+        request_close(&mut app, WindowId::primary());
+        assert!(app
+            .world
+            .get_resource::<Windows>()
+            .unwrap()
+            .iter()
+            .next()
+            .is_none());
+        let app_exit_events = app.world.get_resource::<Events<AppExit>>().unwrap();
+        assert_eq!(app_exit_reader.iter(app_exit_events).count(), 1);
+    }
+
+    #[test]
+    fn confirms_each_window_on_its_own() {
+        let secondary = WindowId::new();
+        let mut app = test_app(&[WindowId::primary(), secondary]);
+        app.world.insert_resource(CloseConfirmation::default());
+        let mut confirmation_reader = app
+            .world
+            .get_resource::<Events<WindowCloseConfirmationRequested>>()
+            .unwrap()
+            .get_reader();
+        let is_open = |app: &App, id| {
+            app.world
+                .get_resource::<Windows>()
+                .unwrap()
+                .get(id)
+                .is_some()
+        };
+
+        request_close(&mut app, WindowId::primary());
+        request_close(&mut app, secondary);
+        assert!(is_open(&app, WindowId::primary()));
+        assert!(is_open(&app, secondary));
+        let confirmations = app
+            .world
+            .get_resource::<Events<WindowCloseConfirmationRequested>>()
+            .unwrap();
+        let confirmed = confirmation_reader
+            .iter(confirmations)
+            .map(|event| event.id)
+            .collect::<Vec<_>>();
+        assert_eq!(confirmed, [WindowId::primary(), secondary]);
+
+        // the request for the secondary window did not take over the one of the primary window
+        request_close(&mut app, WindowId::primary());
+        assert!(!is_open(&app, WindowId::primary()));
+        assert!(is_open(&app, secondary));
+    }
+}
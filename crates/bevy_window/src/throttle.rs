@@ -0,0 +1,92 @@
+use crate::{WindowId, Windows};
+use bevy_ecs::{
+    schedule::ShouldRun,
+    system::{Local, Res},
+};
+use bevy_utils::{Duration, Instant};
+
+/// How often systems gated with [throttle_for_window] run for low priority windows.
+#[derive(Debug, Clone)]
+pub struct WindowThrottle {
+    pub low_priority_interval: Duration,
+}
+
+impl Default for WindowThrottle {
+    fn default() -> Self {
+        WindowThrottle {
+            low_priority_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Run criteria for systems that work on a single window. They run every frame while the window
+/// has normal priority, and at most once per [WindowThrottle::low_priority_interval] while it is
+/// marked as [low priority](crate::Window::set_low_priority). They do not run once the window is
+/// gone.
+pub fn throttle_for_window(
+    id: WindowId,
+) -> impl FnMut(Res<Windows>, Res<WindowThrottle>, Local<Option<Instant>>) -> ShouldRun {
+    move |windows, throttle, mut last_run| {
+        let window = match windows.get(id) {
+            Some(window) => window,
+            None => return ShouldRun::No,
+        };
+
+        let now = Instant::now();
+        let due = match *last_run {
+            Some(last_run) if window.is_low_priority() => {
+                now.duration_since(last_run) >= throttle.low_priority_interval
+            }
+            _ => true,
+        };
+        if due {
+            *last_run = Some(now);
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Window, WindowDescriptor};
+    use bevy_ecs::{
+        system::{IntoSystem, System},
+        world::World,
+    };
+
+    #[test]
+    fn low_priority_windows_are_throttled() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            id,
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            1.0,
+            None,
+        ));
+        world.insert_resource(windows);
+        world.insert_resource(WindowThrottle::default());
+
+        let mut criteria = throttle_for_window(id).system();
+        criteria.initialize(&mut world);
+        assert_eq!(criteria.run((), &mut world), ShouldRun::Yes);
+        assert_eq!(criteria.run((), &mut world), ShouldRun::Yes);
+
+        world
+            .get_resource_mut::<Windows>()
+            .unwrap()
+            .get_mut(id)
+            .unwrap()
+            .set_low_priority(true);
+        assert_eq!(criteria.run((), &mut world), ShouldRun::No);
+
+        world.get_resource_mut::<Windows>().unwrap().remove(id);
+        assert_eq!(criteria.run((), &mut world), ShouldRun::No);
+    }
+}
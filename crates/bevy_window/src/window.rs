@@ -1,4 +1,5 @@
-use bevy_math::{IVec2, Vec2};
+use crate::{CursorIcon, MonitorSelection, SurfaceHints, WindowPosition};
+use bevy_math::{IVec2, UVec2, Vec2};
 use bevy_utils::{tracing::warn, Uuid};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -16,6 +17,16 @@ impl WindowId {
     pub fn is_primary(&self) -> bool {
         *self == WindowId::primary()
     }
+
+    /// The id as a number, e.g. to refer to the window outside of the app.
+    pub fn to_u128(&self) -> u128 {
+        self.0.as_u128()
+    }
+
+    /// The id with the number returned by [WindowId::to_u128].
+    pub fn from_u128(id: u128) -> Self {
+        WindowId(Uuid::from_u128(id))
+    }
 }
 
 use std::fmt;
@@ -32,9 +43,20 @@ impl Default for WindowId {
     }
 }
 
+/// The parts at the edges of a window that are covered by the notch, rounded display corners,
+/// the home indicator or a transparent titlebar, in logical pixels. UI should keep interactive
+/// elements out of them, see [Window::safe_area_insets].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SafeAreaInsets {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
 /// The size limits on a window.
-/// These values are measured in logical pixels, so the user's
-/// scale factor does affect the size limits on the window.
+/// These values are measured in logical pixels by default, so the user's
+/// scale factor does affect the size limits on the window, see [ResizeConstraintUnits].
 /// Please note that if the window is resizable, then when the window is
 /// maximized it may have a size outside of these limits. The functionality
 /// required to disable maximizing is not yet exposed by winit.
@@ -44,6 +66,7 @@ pub struct WindowResizeConstraints {
     pub min_height: f32,
     pub max_width: f32,
     pub max_height: f32,
+    pub units: ResizeConstraintUnits,
 }
 
 impl Default for WindowResizeConstraints {
@@ -53,10 +76,23 @@ impl Default for WindowResizeConstraints {
             min_height: 120.,
             max_width: f32::INFINITY,
             max_height: f32::INFINITY,
+            units: ResizeConstraintUnits::Logical,
         }
     }
 }
 
+/// The units of [WindowResizeConstraints].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResizeConstraintUnits {
+    /// Logical pixels, scaled by the [scale factor](Window::scale_factor) of the window. The
+    /// constraints are applied again whenever it changes, so they keep their size on screen
+    /// when the window moves to a monitor with a different pixel density.
+    #[default]
+    Logical,
+    /// Physical pixels, which do not depend on the scale factor.
+    Physical,
+}
+
 impl WindowResizeConstraints {
     pub fn check_constraints(&self) -> WindowResizeConstraints {
         let WindowResizeConstraints {
@@ -64,6 +100,7 @@ impl WindowResizeConstraints {
             mut min_height,
             mut max_width,
             mut max_height,
+            units,
         } = self;
         min_width = min_width.max(1.);
         min_height = min_height.max(1.);
@@ -86,6 +123,22 @@ impl WindowResizeConstraints {
             min_height,
             max_width,
             max_height,
+            units: *units,
+        }
+    }
+
+    /// The constraints in physical pixels at `scale_factor`.
+    pub fn to_physical(&self, scale_factor: f64) -> WindowResizeConstraints {
+        let scale_factor = match self.units {
+            ResizeConstraintUnits::Logical => scale_factor as f32,
+            ResizeConstraintUnits::Physical => 1.0,
+        };
+        WindowResizeConstraints {
+            min_width: self.min_width * scale_factor,
+            min_height: self.min_height * scale_factor,
+            max_width: self.max_width * scale_factor,
+            max_height: self.max_height * scale_factor,
+            units: ResizeConstraintUnits::Physical,
         }
     }
 }
@@ -114,19 +167,47 @@ pub struct Window {
     physical_height: u32,
     resize_constraints: WindowResizeConstraints,
     position: Option<IVec2>,
+    inner_position: Option<IVec2>,
+    outer_size: UVec2,
     scale_factor_override: Option<f64>,
     backend_scale_factor: f64,
     title: String,
     vsync: bool,
     resizable: bool,
     decorations: bool,
+    transparent: bool,
+    clear_alpha: Option<f32>,
+    background_effect: BackgroundEffect,
+    visible: bool,
+    enabled: bool,
+    content_protected: bool,
+    event_mask: WindowEventMask,
+    skip_taskbar: bool,
+    name: Option<String>,
+    macos: MacOsWindowConfig,
+    windows: WindowsConfig,
+    ios: IosWindowConfig,
+    minimized: bool,
+    maximized: bool,
     cursor_visible: bool,
-    cursor_locked: bool,
+    cursor_hit_test: bool,
+    cursor_grab_mode: CursorGrabMode,
+    cursor_icon: CursorIcon,
     cursor_position: Option<Vec2>,
+    predicted_cursor_position: Option<Vec2>,
+    ime_allowed: bool,
+    low_priority: bool,
     focused: bool,
     mode: WindowMode,
+    surface_hints: SurfaceHints,
+    theme: Option<WindowTheme>,
+    safe_area_insets: SafeAreaInsets,
+    resize_increments: Vec2,
+    aspect_ratio_lock: Option<f32>,
     #[cfg(target_arch = "wasm32")]
     pub canvas: Option<String>,
+    #[cfg(target_arch = "wasm32")]
+    pub fit_canvas_to_parent: bool,
     command_queue: Vec<WindowCommand>,
 }
 
@@ -142,6 +223,11 @@ pub enum WindowCommand {
     SetScaleFactor {
         scale_factor: f64,
     },
+    /// Sent along with [WindowCommand::SetScaleFactor] for backends that render at the scale
+    /// factor of the window, like UIKit. Others scale on their own and ignore it.
+    SetScaleFactorOverride {
+        scale_factor: Option<f64>,
+    },
     SetResolution {
         logical_resolution: (f32, f32),
         scale_factor: f64,
@@ -154,40 +240,205 @@ pub enum WindowCommand {
     },
     SetDecorations {
         decorations: bool,
+        /// Moves and resizes the window so its client area stays in place.
+        preserve_client_area: bool,
+    },
+    SetTransparent {
+        transparent: bool,
     },
-    SetCursorLockMode {
-        locked: bool,
+    SetCursorGrabMode {
+        mode: CursorGrabMode,
     },
     SetCursorVisibility {
         visible: bool,
     },
+    SetCursorHitTest {
+        enabled: bool,
+    },
     SetCursorPosition {
         position: Vec2,
     },
+    SetCursorIcon {
+        icon: CursorIcon,
+    },
     SetMaximized {
         maximized: bool,
     },
     SetMinimized {
         minimized: bool,
     },
+    SetVisible {
+        visible: bool,
+    },
+    SetEnabled {
+        enabled: bool,
+    },
+    SetContentProtected {
+        protected: bool,
+    },
+    SetEventMask {
+        mask: WindowEventMask,
+    },
     SetPosition {
         position: IVec2,
     },
+    RefreshGeometry,
+    Center {
+        monitor: MonitorSelection,
+    },
     SetResizeConstraints {
         resize_constraints: WindowResizeConstraints,
+        scale_factor: f64,
+    },
+    SetResizeIncrements {
+        x: f32,
+        y: f32,
+    },
+    LockAspectRatio {
+        ratio: Option<f32>,
+    },
+    SetImeAllowed {
+        allowed: bool,
+    },
+    SetImePosition {
+        position: Vec2,
+    },
+    DragWindow,
+    DragResizeWindow {
+        direction: ResizeDirection,
     },
+    RequestRedraw,
+    Announce {
+        text: String,
+        priority: AnnouncementPriority,
+    },
+    Close,
+}
+
+impl WindowCommand {
+    /// Whether the command changes something the user sees right away, like the size or the
+    /// title of the window. These are applied first when the
+    /// [WindowCommandBudget](crate::WindowCommandBudget) is limited.
+    pub fn is_user_visible(&self) -> bool {
+        matches!(
+            self,
+            WindowCommand::SetWindowMode { .. }
+                | WindowCommand::SetTitle { .. }
+                | WindowCommand::SetResolution { .. }
+                | WindowCommand::SetDecorations { .. }
+                | WindowCommand::SetCursorVisibility { .. }
+                | WindowCommand::SetCursorPosition { .. }
+                | WindowCommand::SetCursorIcon { .. }
+                | WindowCommand::SetMaximized { .. }
+                | WindowCommand::SetMinimized { .. }
+                | WindowCommand::SetVisible { .. }
+                | WindowCommand::SetPosition { .. }
+                | WindowCommand::Center { .. }
+                | WindowCommand::DragWindow
+                | WindowCommand::DragResizeWindow { .. }
+                | WindowCommand::Announce { .. }
+                | WindowCommand::Close
+        )
+    }
+}
+
+/// How urgently a [Window::announce]ment is conveyed to assistive technology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AnnouncementPriority {
+    /// Read out once the screen reader is idle.
+    #[default]
+    Polite,
+    /// Read out right away, and the window asks for the attention of the user.
+    Assertive,
+}
+
+/// How the cursor is constrained by a window.
+///
+/// Backends emulate a mode that the platform lacks where they can, e.g. `Locked` by confining
+/// the cursor and moving it back to the center of the window whenever it moves, and report a
+/// [WindowCommandError::Unsupported](crate::WindowCommandError) otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CursorGrabMode {
+    /// The cursor can move freely.
+    #[default]
+    None,
+    /// The cursor cannot leave the window.
+    Confined,
+    /// The cursor stays where it is, only relative mouse motion is reported.
+    Locked,
+}
+
+/// A platform effect applied to the desktop behind the transparent parts of a window, e.g. for
+/// launchers and overlays.
+///
+/// ## Platform-specific
+///
+/// - **Windows**: supported from Windows 10 on with the winit backend.
+/// - Other platforms need a `background_effect_hook` on the backend, e.g. to apply macOS
+///   vibrancy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackgroundEffect {
+    #[default]
+    None,
+    /// A plain blur of the content behind the window.
+    Blur,
+    /// A blur with noise and a tint, given as RGBA, like the Fluent acrylic material.
+    Acrylic { tint: [u8; 4] },
+}
+
+/// The color theme of a window, i.e. whether the platform draws its decorations light or dark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowTheme {
+    Light,
+    Dark,
+}
+
+/// The edge or corner of a window that is dragged by [Window::drag_resize_window].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeDirection {
+    East,
+    North,
+    NorthEast,
+    NorthWest,
+    South,
+    SouthEast,
+    SouthWest,
+    West,
 }
 
 /// Defines the way a window is displayed
 /// The use_size option that is used in the Fullscreen variant
-/// defines whether a videomode is chosen that best fits the width and height
+/// defines whether a videomode is chosen that has the width and height
 /// in the Window structure, or if these are ignored.
 /// E.g. when use_size is set to false the best video mode possible is chosen.
+///
+/// With `use_size`, the video mode closest to the physical size of the window is chosen.
+/// `refresh_rate` is a preference: among video modes of the same size, the one with the closest
+/// refresh rate is chosen, or the one with the highest refresh rate without `refresh_rate`. If
+/// `monitor` reports no video modes, the error is handled according to the
+/// [BackendErrorPolicy](crate::BackendErrorPolicy), and a window that is being created stays
+/// windowed.
+///
+/// `video_mode_index` selects a video mode from [Monitor::video_modes](crate::Monitor), e.g.
+/// from a resolution menu, instead of `use_size` and `refresh_rate`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WindowMode {
     Windowed,
     BorderlessFullscreen,
-    Fullscreen { use_size: bool },
+    Fullscreen {
+        use_size: bool,
+        monitor: MonitorSelection,
+        /// In Hz.
+        refresh_rate: Option<u16>,
+        video_mode_index: Option<usize>,
+    },
+    /// Exclusive fullscreen on the current monitor, with the video mode closest to the size of
+    /// the window.
+    SizedFullscreen,
+    /// A window without decorations that covers the work area of its monitor, so the taskbar
+    /// stays visible. Switching back to [WindowMode::Windowed] restores the decorations, the
+    /// position and the size of the window.
+    BorderlessWindowedMax,
 }
 
 impl Window {
@@ -204,6 +455,8 @@ impl Window {
             requested_width: window_descriptor.width,
             requested_height: window_descriptor.height,
             position,
+            inner_position: None,
+            outer_size: UVec2::new(physical_width, physical_height),
             physical_width,
             physical_height,
             resize_constraints: window_descriptor.resize_constraints,
@@ -213,13 +466,39 @@ impl Window {
             vsync: window_descriptor.vsync,
             resizable: window_descriptor.resizable,
             decorations: window_descriptor.decorations,
+            transparent: window_descriptor.transparent,
+            clear_alpha: window_descriptor.clear_alpha,
+            background_effect: window_descriptor.background_effect,
+            visible: window_descriptor.visible,
+            enabled: true,
+            content_protected: window_descriptor.content_protected,
+            event_mask: window_descriptor.event_mask,
+            skip_taskbar: window_descriptor.skip_taskbar,
+            name: window_descriptor.name.clone(),
+            macos: window_descriptor.macos.clone(),
+            windows: window_descriptor.windows.clone(),
+            ios: window_descriptor.ios.clone(),
+            minimized: false,
+            maximized: false,
             cursor_visible: window_descriptor.cursor_visible,
-            cursor_locked: window_descriptor.cursor_locked,
+            cursor_hit_test: window_descriptor.cursor_hit_test,
+            cursor_grab_mode: window_descriptor.cursor_grab_mode,
+            cursor_icon: CursorIcon::Default,
             cursor_position: None,
-            focused: true,
+            predicted_cursor_position: None,
+            ime_allowed: false,
+            low_priority: false,
+            focused: false,
             mode: window_descriptor.mode,
+            surface_hints: window_descriptor.surface_hints.clone(),
+            theme: window_descriptor.preferred_theme,
+            safe_area_insets: SafeAreaInsets::default(),
+            resize_increments: Vec2::ZERO,
+            aspect_ratio_lock: None,
             #[cfg(target_arch = "wasm32")]
             canvas: window_descriptor.canvas.clone(),
+            #[cfg(target_arch = "wasm32")]
+            fit_canvas_to_parent: window_descriptor.fit_canvas_to_parent,
             command_queue: Vec::new(),
         }
     }
@@ -229,6 +508,45 @@ impl Window {
         self.id
     }
 
+    /// A descriptor that creates a window in the current state of this one.
+    pub fn descriptor(&self) -> WindowDescriptor {
+        WindowDescriptor {
+            width: self.width(),
+            height: self.height(),
+            position: self
+                .position
+                .map_or(WindowPosition::Automatic, WindowPosition::At),
+            resize_constraints: self.resize_constraints,
+            scale_factor_override: self.scale_factor_override,
+            title: self.title.clone(),
+            vsync: self.vsync,
+            resizable: self.resizable,
+            decorations: self.decorations,
+            transparent: self.transparent,
+            clear_alpha: self.clear_alpha,
+            background_effect: self.background_effect,
+            visible: self.visible,
+            active: self.focused,
+            content_protected: self.content_protected,
+            event_mask: self.event_mask,
+            skip_taskbar: self.skip_taskbar,
+            name: self.name.clone(),
+            macos: self.macos.clone(),
+            windows: self.windows.clone(),
+            ios: self.ios.clone(),
+            cursor_visible: self.cursor_visible,
+            cursor_hit_test: self.cursor_hit_test,
+            cursor_grab_mode: self.cursor_grab_mode,
+            mode: self.mode,
+            surface_hints: self.surface_hints.clone(),
+            preferred_theme: self.theme,
+            #[cfg(target_arch = "wasm32")]
+            canvas: self.canvas.clone(),
+            #[cfg(target_arch = "wasm32")]
+            fit_canvas_to_parent: self.fit_canvas_to_parent,
+        }
+    }
+
     /// The current logical width of the window's client area.
     #[inline]
     pub fn width(&self) -> f32 {
@@ -279,12 +597,54 @@ impl Window {
         self.resize_constraints
     }
 
-    /// The window's client position in physical pixels.
+    /// The position of the top left corner of the window, including its decorations, in
+    /// physical pixels. The same as [Window::outer_position].
     #[inline]
     pub fn position(&self) -> Option<IVec2> {
         self.position
     }
 
+    /// The position of the top left corner of the window, including its decorations, in
+    /// physical pixels on the desktop. `None` where the platform does not report it.
+    #[inline]
+    pub fn outer_position(&self) -> Option<IVec2> {
+        self.position
+    }
+
+    /// The position of the top left corner of the client area in physical pixels on the
+    /// desktop. `None` where the platform does not report it.
+    #[inline]
+    pub fn inner_position(&self) -> Option<IVec2> {
+        self.inner_position
+    }
+
+    /// The size of the window including its decorations in physical pixels, e.g. to place
+    /// another window next to it.
+    #[inline]
+    pub fn outer_size(&self) -> UVec2 {
+        self.outer_size
+    }
+
+    /// Queries the position and size of the window from the backend again. The backend updates
+    /// them whenever the window is moved or resized, this is for changes it does not report,
+    /// like the size of the decorations.
+    #[inline]
+    pub fn refresh_geometry(&mut self) {
+        self.command_queue.push(WindowCommand::RefreshGeometry);
+    }
+
+    /// Whether the window is maximized, as far as the backend can tell.
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        self.maximized
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_maximized_from_backend(&mut self, maximized: bool) {
+        self.maximized = maximized;
+    }
+
     #[inline]
     pub fn set_maximized(&mut self, maximized: bool) {
         self.command_queue
@@ -302,6 +662,118 @@ impl Window {
             .push(WindowCommand::SetMinimized { minimized });
     }
 
+    /// Whether the window is minimized, as far as the backend can tell. Some platforms only
+    /// report this through a window size of zero, so with winit 0.25 the user minimizing a
+    /// window is only known on **Windows**, while [set_minimized](Self::set_minimized) updates
+    /// it everywhere once the backend applied it. Changes are sent as
+    /// [WindowMinimized](crate::WindowMinimized) and [WindowRestored](crate::WindowRestored)
+    /// events.
+    #[inline]
+    pub fn is_minimized(&self) -> bool {
+        self.minimized
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_minimized_from_backend(&mut self, minimized: bool) {
+        self.minimized = minimized;
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Shows or hides the window, e.g. to keep an app running in the system tray.
+    #[inline]
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        self.command_queue
+            .push(WindowCommand::SetVisible { visible });
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables input to the window without hiding it, e.g. to block a parent window
+    /// while a dialog is open. Where the platform cannot disable windows, the backend drops the
+    /// input events of a disabled window instead, except for releases of keys, buttons and
+    /// touches.
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.command_queue
+            .push(WindowCommand::SetEnabled { enabled });
+    }
+
+    /// Whether the window was created without a taskbar entry, see
+    /// [WindowDescriptor::skip_taskbar].
+    #[inline]
+    pub fn skip_taskbar(&self) -> bool {
+        self.skip_taskbar
+    }
+
+    /// The application name the window was created with, see [WindowDescriptor::name].
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The macOS specific options the window was created with.
+    #[inline]
+    pub fn macos_config(&self) -> &MacOsWindowConfig {
+        &self.macos
+    }
+
+    /// The Windows specific options the window was created with.
+    #[inline]
+    pub fn windows_config(&self) -> &WindowsConfig {
+        &self.windows
+    }
+
+    /// The iOS specific options the window was created with.
+    #[inline]
+    pub fn ios_config(&self) -> &IosWindowConfig {
+        &self.ios
+    }
+
+    #[inline]
+    pub fn is_content_protected(&self) -> bool {
+        self.content_protected
+    }
+
+    /// Prevents the contents of the window from being captured by screenshots and screen
+    /// recordings, e.g. for password managers or DRM protected video.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only supported on **Windows** with the winit backend, where captures show the window
+    ///   black. [is_content_protected](Self::is_content_protected) stays `false` elsewhere.
+    #[inline]
+    pub fn set_content_protected(&mut self, protected: bool) {
+        self.command_queue
+            .push(WindowCommand::SetContentProtected { protected });
+    }
+
+    #[inline]
+    pub fn event_mask(&self) -> WindowEventMask {
+        self.event_mask
+    }
+
+    /// Chooses which categories of events the backend forwards for this window, e.g. no cursor
+    /// events for a thumbnail window. Masked events are dropped by the backend before they reach
+    /// the app, so they do not wake it up either. Events that manage the window itself, like
+    /// resizing, focus and close requests, are always forwarded, and so are modifier changes
+    /// and releases of keys, buttons and touches, so the app does not consider them held.
+    #[inline]
+    pub fn set_event_mask(&mut self, mask: WindowEventMask) {
+        self.event_mask = mask;
+        self.command_queue
+            .push(WindowCommand::SetEventMask { mask });
+    }
+
     /// Modifies the position of the window in physical pixels.
     ///
     /// Note that the top-left hand corner of the desktop is not necessarily the same as the screen.
@@ -321,11 +793,80 @@ impl Window {
             .push(WindowCommand::SetPosition { position })
     }
 
+    /// Moves the window to the center of `monitor`, within the area that is not covered by
+    /// taskbars and docks where the backend knows it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - The work area is only known on **Windows** with the winit backend, elsewhere the
+    ///   window is centered on the whole monitor.
+    /// - Android / Wayland: Unsupported, like [Window::set_position].
+    #[inline]
+    pub fn center(&mut self, monitor: MonitorSelection) {
+        self.command_queue.push(WindowCommand::Center { monitor })
+    }
+
+    /// The steps the client area is resized in, in logical pixels. `0` leaves an axis free.
+    #[inline]
+    pub fn resize_increments(&self) -> Vec2 {
+        self.resize_increments
+    }
+
+    /// Makes the client area resize in steps of `x` by `y` logical pixels, e.g. for tools that
+    /// show a pixel grid. An increment of `0` leaves that axis free.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - The winit backend resizes the window back to the closest allowed size after the user
+    ///   resized it, as winit 0.25 cannot restrict resizing while it happens.
+    #[inline]
+    pub fn set_resize_increments(&mut self, x: f32, y: f32) {
+        self.resize_increments = Vec2::new(x, y);
+        self.command_queue
+            .push(WindowCommand::SetResizeIncrements { x, y });
+    }
+
+    /// The locked ratio of width to height of the client area, if any.
+    #[inline]
+    pub fn aspect_ratio_lock(&self) -> Option<f32> {
+        self.aspect_ratio_lock
+    }
+
+    /// Keeps the width of the client area divided by its height at `ratio`, e.g. for video
+    /// players. `None` unlocks it. The aspect ratio wins over the
+    /// [resize increments](Window::set_resize_increments).
+    ///
+    /// ## Platform-specific
+    ///
+    /// - The winit backend resizes the window back to the ratio after the user resized it, as
+    ///   winit 0.25 cannot restrict resizing while it happens.
+    #[inline]
+    pub fn lock_aspect_ratio(&mut self, ratio: Option<f32>) {
+        self.aspect_ratio_lock = ratio;
+        self.command_queue
+            .push(WindowCommand::LockAspectRatio { ratio });
+    }
+
     /// Modifies the minimum and maximum window bounds for resizing in logical pixels.
     #[inline]
     pub fn set_resize_constraints(&mut self, resize_constraints: WindowResizeConstraints) {
+        self.resize_constraints = resize_constraints;
         self.command_queue
-            .push(WindowCommand::SetResizeConstraints { resize_constraints });
+            .push(WindowCommand::SetResizeConstraints {
+                resize_constraints,
+                scale_factor: self.scale_factor(),
+            });
+    }
+
+    /// Applies logical resize constraints again after the scale factor changed.
+    fn reapply_logical_resize_constraints(&mut self) {
+        if self.resize_constraints.units == ResizeConstraintUnits::Logical {
+            self.command_queue
+                .push(WindowCommand::SetResizeConstraints {
+                    resize_constraints: self.resize_constraints,
+                    scale_factor: self.scale_factor(),
+                });
+        }
     }
 
     /// Request the OS to resize the window such the the client area matches the
@@ -344,6 +885,17 @@ impl Window {
         });
     }
 
+    /// Requests the given logical size even if it is already the requested one, e.g. because
+    /// the backend changed the size on its own.
+    pub(crate) fn reapply_resolution(&mut self, width: f32, height: f32) {
+        self.requested_width = width;
+        self.requested_height = height;
+        self.command_queue.push(WindowCommand::SetResolution {
+            logical_resolution: (width, height),
+            scale_factor: self.scale_factor(),
+        });
+    }
+
     /// Override the os-reported scaling factor
     #[allow(clippy::float_cmp)]
     pub fn set_scale_factor_override(&mut self, scale_factor: Option<f64>) {
@@ -355,16 +907,24 @@ impl Window {
         self.command_queue.push(WindowCommand::SetScaleFactor {
             scale_factor: self.scale_factor(),
         });
+        self.command_queue
+            .push(WindowCommand::SetScaleFactorOverride { scale_factor });
         self.command_queue.push(WindowCommand::SetResolution {
             logical_resolution: (self.requested_width, self.requested_height),
             scale_factor: self.scale_factor(),
         });
+        self.reapply_logical_resize_constraints();
     }
 
     #[allow(missing_docs)]
     #[inline]
     pub fn update_scale_factor_from_backend(&mut self, scale_factor: f64) {
+        let old_scale_factor = self.scale_factor();
         self.backend_scale_factor = scale_factor;
+        #[allow(clippy::float_cmp)]
+        if self.scale_factor() != old_scale_factor {
+            self.reapply_logical_resize_constraints();
+        }
     }
 
     #[allow(missing_docs)]
@@ -380,6 +940,19 @@ impl Window {
         self.position = Some(position);
     }
 
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_geometry_from_backend(
+        &mut self,
+        outer_position: Option<IVec2>,
+        inner_position: Option<IVec2>,
+        outer_size: UVec2,
+    ) {
+        self.position = outer_position.or(self.position);
+        self.inner_position = inner_position;
+        self.outer_size = outer_size;
+    }
+
     /// The ratio of physical pixels to logical pixels
     ///
     /// `physical_pixels = logical_pixels * scale_factor`
@@ -439,19 +1012,59 @@ impl Window {
 
     pub fn set_decorations(&mut self, decorations: bool) {
         self.decorations = decorations;
+        self.command_queue.push(WindowCommand::SetDecorations {
+            decorations,
+            preserve_client_area: false,
+        });
+    }
+
+    /// Like [set_decorations](Self::set_decorations), but keeps the size and the position of the
+    /// client area, so the content does not shift when the title bar and borders appear or
+    /// disappear. The outer size and [position](Self::position) change instead.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11**: the window manager updates the frame after the change, so the position may be
+    ///   off by the size of the frame.
+    /// - **Wayland**: only the size is kept, windows cannot be moved.
+    pub fn set_decorations_preserving_client_area(&mut self, decorations: bool) {
+        self.decorations = decorations;
+        self.command_queue.push(WindowCommand::SetDecorations {
+            decorations,
+            preserve_client_area: true,
+        });
+    }
+
+    #[inline]
+    pub fn cursor_grab_mode(&self) -> CursorGrabMode {
+        self.cursor_grab_mode
+    }
+
+    pub fn set_cursor_grab_mode(&mut self, mode: CursorGrabMode) {
+        self.cursor_grab_mode = mode;
         self.command_queue
-            .push(WindowCommand::SetDecorations { decorations });
+            .push(WindowCommand::SetCursorGrabMode { mode });
     }
 
+    /// Whether the cursor is [CursorGrabMode::Locked].
     #[inline]
     pub fn cursor_locked(&self) -> bool {
-        self.cursor_locked
+        self.cursor_grab_mode == CursorGrabMode::Locked
     }
 
+    /// Switches between [CursorGrabMode::Locked] and [CursorGrabMode::None].
     pub fn set_cursor_lock_mode(&mut self, lock_mode: bool) {
-        self.cursor_locked = lock_mode;
-        self.command_queue
-            .push(WindowCommand::SetCursorLockMode { locked: lock_mode });
+        self.set_cursor_grab_mode(if lock_mode {
+            CursorGrabMode::Locked
+        } else {
+            CursorGrabMode::None
+        });
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_cursor_grab_mode_from_backend(&mut self, mode: CursorGrabMode) {
+        self.cursor_grab_mode = mode;
     }
 
     #[inline]
@@ -459,6 +1072,12 @@ impl Window {
         self.cursor_visible
     }
 
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_cursor_visible_from_backend(&mut self, visible: bool) {
+        self.cursor_visible = visible;
+    }
+
     pub fn set_cursor_visibility(&mut self, visibile_mode: bool) {
         self.cursor_visible = visibile_mode;
         self.command_queue.push(WindowCommand::SetCursorVisibility {
@@ -466,17 +1085,94 @@ impl Window {
         });
     }
 
+    /// Whether the window receives mouse input, see [Window::set_cursor_hit_test].
+    #[inline]
+    pub fn cursor_hit_test(&self) -> bool {
+        self.cursor_hit_test
+    }
+
+    /// Turning hit testing off lets clicks and other mouse input pass through the window to
+    /// whatever is behind it, e.g. for transparent overlays. The window gets no cursor events
+    /// while it is off.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only supported on **Windows** with the winit backend.
+    pub fn set_cursor_hit_test(&mut self, enabled: bool) {
+        self.cursor_hit_test = enabled;
+        self.command_queue
+            .push(WindowCommand::SetCursorHitTest { enabled });
+    }
+
+    #[inline]
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.cursor_icon
+    }
+
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.cursor_icon = icon;
+        self.command_queue
+            .push(WindowCommand::SetCursorIcon { icon });
+    }
+
+    /// The cursor position in logical pixels in the
+    /// [WindowCoordinateConvention](crate::WindowCoordinateConvention).
     #[inline]
     #[doc(alias = "mouse position")]
     pub fn cursor_position(&self) -> Option<Vec2> {
         self.cursor_position
     }
 
+    /// The cursor position extrapolated to when the current frame is expected to be shown,
+    /// if the backend predicts it (see `WinitConfig::cursor_prediction`), otherwise the same as
+    /// [cursor_position](Self::cursor_position). Meant for UI that follows the cursor, input
+    /// handling should use the actual position.
+    #[inline]
+    pub fn predicted_cursor_position(&self) -> Option<Vec2> {
+        self.cursor_position
+            .and(self.predicted_cursor_position.or(self.cursor_position))
+    }
+
+    /// Moves the cursor to `position`, in logical pixels in the
+    /// [WindowCoordinateConvention](crate::WindowCoordinateConvention).
     pub fn set_cursor_position(&mut self, position: Vec2) {
         self.command_queue
             .push(WindowCommand::SetCursorPosition { position });
     }
 
+    #[inline]
+    pub fn ime_allowed(&self) -> bool {
+        self.ime_allowed
+    }
+
+    /// Allows input methods to compose text in this window. Enable this while a text field has
+    /// focus. [ime_allowed](Self::ime_allowed) only changes once the backend applied it.
+    pub fn set_ime_allowed(&mut self, allowed: bool) {
+        self.command_queue
+            .push(WindowCommand::SetImeAllowed { allowed });
+    }
+
+    /// Sets where the input method shows its candidate box, in logical pixels in the
+    /// [WindowCoordinateConvention](crate::WindowCoordinateConvention). Usually the position of
+    /// the text cursor.
+    pub fn set_ime_position(&mut self, position: Vec2) {
+        self.command_queue
+            .push(WindowCommand::SetImePosition { position });
+    }
+
+    #[inline]
+    pub fn is_low_priority(&self) -> bool {
+        self.low_priority
+    }
+
+    /// Marks the window as a background window, e.g. a tool window of an editor. Its events do
+    /// not wake a reactive app on their own, and of its resizes, moves, cursor movements and
+    /// redraw requests only the latest of each frame is sent. Systems gated with
+    /// [throttle_for_window](crate::throttle_for_window) run at a reduced rate for it.
+    pub fn set_low_priority(&mut self, low_priority: bool) {
+        self.low_priority = low_priority;
+    }
+
     #[allow(missing_docs)]
     #[inline]
     pub fn update_focused_status_from_backend(&mut self, focused: bool) {
@@ -489,17 +1185,167 @@ impl Window {
         self.cursor_position = cursor_position;
     }
 
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_predicted_cursor_position_from_backend(&mut self, position: Option<Vec2>) {
+        self.predicted_cursor_position = position;
+    }
+
     #[inline]
     pub fn mode(&self) -> WindowMode {
         self.mode
     }
 
     pub fn set_mode(&mut self, mode: WindowMode) {
+        let previous = self.mode;
         self.mode = mode;
         self.command_queue.push(WindowCommand::SetWindowMode {
             mode,
             resolution: (self.physical_width, self.physical_height),
         });
+        // the backend removes the decorations for this mode without changing `decorations`
+        if previous == WindowMode::BorderlessWindowedMax && mode != previous {
+            self.command_queue.push(WindowCommand::SetDecorations {
+                decorations: self.decorations,
+                preserve_client_area: false,
+            });
+        }
+    }
+
+    /// Applies the current mode again for a monitor that changed its resolution to
+    /// `physical_resolution`, so that a fullscreen window picks the video mode for the new
+    /// resolution instead of its current size.
+    pub fn refit_mode(&mut self, physical_resolution: UVec2) {
+        self.command_queue.push(WindowCommand::SetWindowMode {
+            mode: self.mode,
+            resolution: (physical_resolution.x, physical_resolution.y),
+        });
+    }
+
+    /// The surface configuration requested for this window. It only takes effect when the
+    /// renderer creates the surface of the window.
+    #[inline]
+    pub fn surface_hints(&self) -> &SurfaceHints {
+        &self.surface_hints
+    }
+
+    /// The current theme of the window, `None` if the platform does not report it.
+    #[inline]
+    pub fn theme(&self) -> Option<WindowTheme> {
+        self.theme
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_theme_from_backend(&mut self, theme: Option<WindowTheme>) {
+        self.theme = theme;
+    }
+
+    /// The insets UI should keep clear of. They are zero until the backend reports them shortly
+    /// after the window was created, this and later changes are sent as
+    /// [SafeAreaChanged](crate::SafeAreaChanged) events.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Reported on **iOS**, and on **macOS** for the titlebar of windows with
+    ///   [MacOsWindowConfig::fullsize_content_view], with the winit backend. Zero elsewhere.
+    #[inline]
+    pub fn safe_area_insets(&self) -> SafeAreaInsets {
+        self.safe_area_insets
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_safe_area_insets_from_backend(&mut self, insets: SafeAreaInsets) {
+        self.safe_area_insets = insets;
+    }
+
+    /// Updates the state that only changes once the backend applied `command`, because not
+    /// every backend supports it.
+    pub fn update_from_applied_command(&mut self, command: &WindowCommand) {
+        match *command {
+            WindowCommand::SetTransparent { transparent } => self.transparent = transparent,
+            WindowCommand::SetImeAllowed { allowed } => self.ime_allowed = allowed,
+            WindowCommand::SetMinimized { minimized } => self.minimized = minimized,
+            WindowCommand::SetContentProtected { protected } => self.content_protected = protected,
+            _ => {}
+        }
+    }
+
+    /// Asks the backend to redraw the window, which results in a
+    /// [WindowRedrawRequested](crate::WindowRedrawRequested) event. Useful to drive rendering on
+    /// demand when the app only updates in reaction to events.
+    #[inline]
+    pub fn request_redraw(&mut self) {
+        self.command_queue.push(WindowCommand::RequestRedraw);
+    }
+
+    #[inline]
+    pub fn transparent(&self) -> bool {
+        self.transparent
+    }
+
+    /// The alpha that replaces the alpha of the clear color for this window, see
+    /// [WindowDescriptor::clear_alpha].
+    #[inline]
+    pub fn clear_alpha(&self) -> Option<f32> {
+        self.clear_alpha
+    }
+
+    /// Takes effect when the window is rendered next, without a command to the backend.
+    #[inline]
+    pub fn set_clear_alpha(&mut self, clear_alpha: Option<f32>) {
+        self.clear_alpha = clear_alpha;
+    }
+
+    /// The background effect the window was created with.
+    #[inline]
+    pub fn background_effect(&self) -> BackgroundEffect {
+        self.background_effect
+    }
+
+    /// Not every backend can change the transparency of an existing window, which is then
+    /// handled according to the [BackendErrorPolicy](crate::BackendErrorPolicy) and reported
+    /// with a [WindowCommandUnsupported](crate::WindowCommandUnsupported) event.
+    /// [transparent](Self::transparent) only changes once the backend applied it.
+    pub fn set_transparent(&mut self, transparent: bool) {
+        self.command_queue
+            .push(WindowCommand::SetTransparent { transparent });
+    }
+
+    /// Starts moving the window with the mouse until the button is released, e.g. when a
+    /// custom title bar of a window without decorations is pressed.
+    pub fn drag_window(&mut self) {
+        self.command_queue.push(WindowCommand::DragWindow);
+    }
+
+    /// Conveys `text` to assistive technology such as screen readers, e.g. when the state of
+    /// the game changed in a way that is only visible on screen.
+    ///
+    /// Backends without an accessibility adapter show `text` as the title of the window for a
+    /// moment, which screen readers read out, and then restore the [title](Self::title).
+    pub fn announce(&mut self, text: impl Into<String>, priority: AnnouncementPriority) {
+        self.command_queue.push(WindowCommand::Announce {
+            text: text.into(),
+            priority,
+        });
+    }
+
+    /// Starts resizing the window from the edge or corner in `direction` with the mouse until
+    /// the button is released. Not every backend supports this, see
+    /// [WindowCommandUnsupported](crate::WindowCommandUnsupported).
+    pub fn drag_resize_window(&mut self, direction: ResizeDirection) {
+        self.command_queue
+            .push(WindowCommand::DragResizeWindow { direction });
+    }
+
+    /// Closes the window and destroys its backend window.
+    ///
+    /// The window is removed from [Windows](crate::Windows) once the command is applied, at
+    /// which point a [WindowClosed](crate::WindowClosed) event is sent.
+    #[inline]
+    pub fn close(&mut self) {
+        self.command_queue.push(WindowCommand::Close);
     }
 
     #[inline]
@@ -507,6 +1353,23 @@ impl Window {
         self.command_queue.drain(..)
     }
 
+    /// Puts `commands` back in front of the command queue, e.g. when they did not fit into the
+    /// [WindowCommandBudget](crate::WindowCommandBudget).
+    #[inline]
+    pub(crate) fn requeue_commands(&mut self, commands: impl IntoIterator<Item = WindowCommand>) {
+        self.command_queue.splice(0..0, commands);
+    }
+
+    /// Whether a [WindowCommand::Close] is waiting in the command queue.
+    #[inline]
+    pub(crate) fn is_close_queued(&self) -> bool {
+        self.command_queue
+            .iter()
+            .any(|command| matches!(command, WindowCommand::Close))
+    }
+
+    /// Whether the window has the keyboard focus, `false` until the backend reports that it
+    /// gained it, also for windows created [active](WindowDescriptor::active).
     #[inline]
     pub fn is_focused(&self) -> bool {
         self.focused
@@ -517,17 +1380,75 @@ impl Window {
 pub struct WindowDescriptor {
     pub width: f32,
     pub height: f32,
+    pub position: WindowPosition,
     pub resize_constraints: WindowResizeConstraints,
     pub scale_factor_override: Option<f64>,
     pub title: String,
     pub vsync: bool,
     pub resizable: bool,
     pub decorations: bool,
+    /// Lets the desktop show through where the window is cleared with a transparent color,
+    /// e.g. for overlays. Needs a clear color with an alpha below 1.0.
+    pub transparent: bool,
+    /// Replaces the alpha of the clear color for this window, so a transparent window can be
+    /// cleared to the desktop while other windows stay opaque. `None` keeps the alpha of the
+    /// clear color.
+    pub clear_alpha: Option<f32>,
+    /// Blurs the desktop behind the transparent parts of the window, see [BackgroundEffect].
+    /// Only visible together with `transparent`.
+    pub background_effect: BackgroundEffect,
+    pub visible: bool,
+    /// Whether the window is activated and takes focus when it is shown. Tool palettes and
+    /// other secondary windows can be opened in the background instead, so they do not steal
+    /// focus from the main window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only supported on **Windows** with the winit backend, other platforms always activate
+    ///   new windows.
+    pub active: bool,
+    /// Whether the window is excluded from screen capture, see
+    /// [Window::set_content_protected].
+    pub content_protected: bool,
+    /// Which events of the window the backend forwards, see [Window::set_event_mask].
+    pub event_mask: WindowEventMask,
+    /// Hides the window from the taskbar, e.g. for tool palettes and splash screens.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only supported on **Windows** with the winit backend, where such windows are also
+    ///   left out of Alt+Tab and get a smaller title bar.
+    pub skip_taskbar: bool,
+    /// The name desktop shells use to group the window with its application and to find its
+    /// desktop entry, i.e. the app id on Wayland and the instance and class of `WM_CLASS` on
+    /// X11. Ignored on other platforms.
+    pub name: Option<String>,
+    /// Options for the window chrome that only exist on macOS, ignored on other platforms.
+    pub macos: MacOsWindowConfig,
+    /// Options that only exist on Windows, ignored on other platforms.
+    pub windows: WindowsConfig,
+    /// Options that only exist on iOS, ignored on other platforms.
+    pub ios: IosWindowConfig,
     pub cursor_visible: bool,
-    pub cursor_locked: bool,
+    /// Whether the window receives mouse input, see [Window::set_cursor_hit_test].
+    pub cursor_hit_test: bool,
+    pub cursor_grab_mode: CursorGrabMode,
     pub mode: WindowMode,
+    /// Surface configuration for renderers, see [SurfaceHints].
+    pub surface_hints: SurfaceHints,
+    /// The theme to create the window with, `None` follows the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only supported on **Windows** with the winit backend.
+    pub preferred_theme: Option<WindowTheme>,
+    /// The CSS selector of the canvas to render into, `None` appends a new canvas to the body.
     #[cfg(target_arch = "wasm32")]
     pub canvas: Option<String>,
+    /// Keeps the canvas at the size of its parent element, so the page layout decides the size
+    /// of the window instead of `width` and `height`.
+    #[cfg(target_arch = "wasm32")]
+    pub fit_canvas_to_parent: bool,
 }
 
 impl Default for WindowDescriptor {
@@ -536,16 +1457,121 @@ impl Default for WindowDescriptor {
             title: "bevy".to_string(),
             width: 1280.,
             height: 720.,
+            position: WindowPosition::default(),
             resize_constraints: WindowResizeConstraints::default(),
             scale_factor_override: None,
             vsync: true,
             resizable: true,
             decorations: true,
-            cursor_locked: false,
+            transparent: false,
+            clear_alpha: None,
+            background_effect: BackgroundEffect::None,
+            visible: true,
+            active: true,
+            content_protected: false,
+            event_mask: WindowEventMask::default(),
+            skip_taskbar: false,
+            name: None,
+            macos: MacOsWindowConfig::default(),
+            windows: WindowsConfig::default(),
+            ios: IosWindowConfig::default(),
+            cursor_grab_mode: CursorGrabMode::None,
             cursor_visible: true,
+            cursor_hit_test: true,
             mode: WindowMode::Windowed,
+            surface_hints: SurfaceHints::default(),
+            preferred_theme: None,
             #[cfg(target_arch = "wasm32")]
             canvas: None,
+            #[cfg(target_arch = "wasm32")]
+            fit_canvas_to_parent: false,
         }
     }
 }
+
+/// Window chrome options that only exist on macOS, see [WindowDescriptor::macos]. They are
+/// applied when the window is created.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MacOsWindowConfig {
+    /// Makes the titlebar transparent, so that the content shows through it together with
+    /// `fullsize_content_view`.
+    pub titlebar_transparent: bool,
+    /// Hides the title text, but keeps the titlebar and its buttons.
+    pub title_hidden: bool,
+    /// Extends the content view below the titlebar.
+    pub fullsize_content_view: bool,
+    /// Lets the user move the window by dragging anywhere in its background.
+    pub movable_by_window_background: bool,
+}
+
+/// Options that only exist on iOS, see [WindowDescriptor::ios]. They are applied when the
+/// window is created.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IosWindowConfig {
+    /// Hides the home indicator at the bottom of devices without a home button. It shows again
+    /// when the user touches the screen.
+    pub prefers_home_indicator_hidden: bool,
+    /// Hides the status bar with the time and battery level.
+    pub prefers_status_bar_hidden: bool,
+}
+
+/// The categories of events the backend forwards for a window, see [Window::set_event_mask].
+/// All of them are forwarded by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowEventMask {
+    /// [CursorMoved](crate::CursorMoved), [CursorEntered](crate::CursorEntered) and
+    /// [CursorLeft](crate::CursorLeft).
+    pub cursor: bool,
+    /// Mouse button and mouse wheel input.
+    pub mouse_buttons: bool,
+    /// Keyboard input, [ReceivedCharacter](crate::ReceivedCharacter) and pasted text.
+    pub keyboard: bool,
+    /// Touch and pen input.
+    pub touch: bool,
+    /// [FileDragAndDrop](crate::FileDragAndDrop).
+    pub file_drop: bool,
+}
+
+impl WindowEventMask {
+    /// Forwards every category.
+    pub const ALL: WindowEventMask = WindowEventMask {
+        cursor: true,
+        mouse_buttons: true,
+        keyboard: true,
+        touch: true,
+        file_drop: true,
+    };
+
+    /// Only forwards the events that manage the window itself.
+    pub const NONE: WindowEventMask = WindowEventMask {
+        cursor: false,
+        mouse_buttons: false,
+        keyboard: false,
+        touch: false,
+        file_drop: false,
+    };
+}
+
+impl Default for WindowEventMask {
+    fn default() -> Self {
+        WindowEventMask::ALL
+    }
+}
+
+/// Window options that only exist on Windows, see [WindowDescriptor::windows]. They are applied
+/// when the window is created.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WindowsConfig {
+    /// Embeds the window as a child of this existing window, given as a raw `HWND`, e.g. to
+    /// render into a panel of a Win32 host application. The child is placed relative to the
+    /// client area of its parent.
+    pub parent_window: Option<isize>,
+    /// Creates the window without a redirection bitmap, which saves memory when the window is
+    /// only ever presented to through DirectComposition.
+    pub no_redirection_bitmap: bool,
+    /// Registers the window for OLE drag and drop, which is required for
+    /// [FileDragAndDrop](crate::FileDragAndDrop) events. It initializes COM in the
+    /// single-threaded apartment mode on the winit thread, which conflicts with libraries that
+    /// need a multi-threaded apartment there, so it is off by default.
+    pub drag_and_drop: bool,
+}
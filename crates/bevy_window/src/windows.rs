@@ -1,14 +1,37 @@
-use super::{Window, WindowId};
+use super::{Window, WindowAdded, WindowId, WindowRemoved};
+use bevy_app::EventWriter;
+use bevy_ecs::system::ResMut;
 use bevy_utils::HashMap;
 
 #[derive(Debug, Default)]
 pub struct Windows {
     windows: HashMap<WindowId, Window>,
+    /// Additions and removals since the last run of [window_list_changed_system].
+    changes: Vec<WindowListChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WindowListChange {
+    Added(WindowId),
+    Removed(WindowId),
 }
 
 impl Windows {
+    /// Adds a window, replacing the window with the same id if there is one. Only a window with
+    /// a new id is reported as a [WindowAdded] event.
     pub fn add(&mut self, window: Window) {
-        self.windows.insert(window.id(), window);
+        let id = window.id();
+        if self.windows.insert(id, window).is_none() {
+            self.changes.push(WindowListChange::Added(id));
+        }
+    }
+
+    pub fn remove(&mut self, id: WindowId) -> Option<Window> {
+        let window = self.windows.remove(&id);
+        if window.is_some() {
+            self.changes.push(WindowListChange::Removed(id));
+        }
+        window
     }
 
     pub fn get(&self, id: WindowId) -> Option<&Window> {
@@ -35,3 +58,122 @@ impl Windows {
         self.windows.values_mut()
     }
 }
+
+/// The window that has keyboard focus, if any.
+///
+/// Input events that are not associated with a window, such as `KeyboardInput` and
+/// `MouseMotion`, are meant for this window, which lets multi-window apps route them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FocusedWindow {
+    id: Option<WindowId>,
+}
+
+impl FocusedWindow {
+    #[inline]
+    pub fn get(&self) -> Option<WindowId> {
+        self.id
+    }
+
+    #[inline]
+    pub fn is_focused(&self, id: WindowId) -> bool {
+        self.id == Some(id)
+    }
+
+    /// Records a focus change of window `id` reported by the backend. Platforms differ in
+    /// whether the old window loses focus before or after the new one gains it, so losing focus
+    /// only clears the focused window if it still is `id`.
+    pub fn update_from_backend(&mut self, id: WindowId, focused: bool) {
+        if focused {
+            self.id = Some(id);
+        } else if self.id == Some(id) {
+            self.id = None;
+        }
+    }
+}
+
+/// Sends [WindowAdded] and [WindowRemoved] events for the windows that were added to or removed
+/// from [Windows] since the last run.
+pub fn window_list_changed_system(
+    mut windows: ResMut<Windows>,
+    mut window_added_events: EventWriter<WindowAdded>,
+    mut window_removed_events: EventWriter<WindowRemoved>,
+) {
+    if windows.changes.is_empty() {
+        return;
+    }
+    for change in windows.changes.drain(..) {
+        match change {
+            WindowListChange::Added(id) => window_added_events.send(WindowAdded { id }),
+            WindowListChange::Removed(id) => window_removed_events.send(WindowRemoved { id }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WindowDescriptor;
+    use bevy_app::{Events, ManualEventReader};
+    use bevy_ecs::{
+        system::{IntoSystem, System},
+        world::World,
+    };
+
+    fn new_window(id: WindowId) -> Window {
+        Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None)
+    }
+
+    #[test]
+    fn focus_changes_in_any_order() {
+        let (a, b) = (WindowId::new(), WindowId::new());
+        let mut focused = FocusedWindow::default();
+        focused.update_from_backend(a, true);
+        focused.update_from_backend(b, true);
+        focused.update_from_backend(a, false);
+        assert!(focused.is_focused(b));
+        focused.update_from_backend(b, false);
+        assert_eq!(focused.get(), None);
+    }
+
+    #[test]
+    fn only_actual_changes_are_reported() {
+        let id = WindowId::new();
+        let mut world = World::default();
+        world.insert_resource(Windows::default());
+        world.insert_resource(Events::<WindowAdded>::default());
+        world.insert_resource(Events::<WindowRemoved>::default());
+        let mut system = window_list_changed_system.system();
+        system.initialize(&mut world);
+        let mut added_reader = ManualEventReader::<WindowAdded>::default();
+        let mut removed_reader = ManualEventReader::<WindowRemoved>::default();
+
+        {
+            let mut windows = world.get_resource_mut::<Windows>().unwrap();
+            windows.add(new_window(id));
+            // replacing the window keeps the list unchanged
+            windows.add(new_window(id));
+            assert!(windows.remove(WindowId::new()).is_none());
+        }
+        system.run((), &mut world);
+        let added = world.get_resource::<Events<WindowAdded>>().unwrap();
+        assert_eq!(
+            added_reader.iter(added).map(|e| e.id).collect::<Vec<_>>(),
+            vec![id]
+        );
+        let removed = world.get_resource::<Events<WindowRemoved>>().unwrap();
+        assert_eq!(removed_reader.iter(removed).count(), 0);
+
+        world.get_resource_mut::<Windows>().unwrap().remove(id);
+        system.run((), &mut world);
+        let added = world.get_resource::<Events<WindowAdded>>().unwrap();
+        assert_eq!(added_reader.iter(added).count(), 0);
+        let removed = world.get_resource::<Events<WindowRemoved>>().unwrap();
+        assert_eq!(
+            removed_reader
+                .iter(removed)
+                .map(|e| e.id)
+                .collect::<Vec<_>>(),
+            vec![id]
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use crate::DisplayBackend;
+use bevy_window::WindowCapabilities;
+use std::fmt;
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+use winit::event_loop::EventLoop;
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+use winit::platform::unix::EventLoopExtUnix;
+#[cfg(target_os = "windows")]
+use winit::platform::windows::EventLoopExtWindows;
+
+/// The windowing backend the winit event loop runs on, available as a resource once the
+/// runner has started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinitBackend {
+    X11,
+    Wayland,
+    /// The only backend of the target platform, e.g. Win32 or Cocoa.
+    Native,
+}
+
+/// Why no windowing backend could be started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WinitBackendError {
+    /// Neither the `x11` nor the `wayland` feature is enabled.
+    NoBackendCompiled,
+    /// The backend requested by [WinitConfig::display_backend](crate::WinitConfig::display_backend)
+    /// is not compiled in.
+    NotCompiled(WinitBackend),
+    /// None of the compiled backends has a display server to connect to.
+    NoDisplay { compiled: Vec<WinitBackend> },
+    /// winit failed to create its event loop.
+    EventLoopCreation(String),
+}
+
+impl fmt::Display for WinitBackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WinitBackendError::NoBackendCompiled => write!(
+                f,
+                "no windowing backend compiled in, enable the `x11` or `wayland` feature"
+            ),
+            WinitBackendError::NotCompiled(backend) => write!(
+                f,
+                "the {:?} backend is not compiled in, enable its feature",
+                backend
+            ),
+            WinitBackendError::NoDisplay { compiled } => write!(
+                f,
+                "no display server found for the compiled backends {:?}, \
+                 check that DISPLAY or WAYLAND_DISPLAY is set",
+                compiled
+            ),
+            WinitBackendError::EventLoopCreation(reason) => {
+                write!(f, "failed to create the event loop: {}", reason)
+            }
+        }
+    }
+}
+
+/// An event that is sent when the winit runner could not start a windowing backend.
+///
+/// The app is updated once after this event is sent so it can report the error, then
+/// [run](bevy_app::App::run) returns.
+#[derive(Debug, Clone)]
+pub struct WinitBackendFailed {
+    pub error: WinitBackendError,
+}
+
+/// Send this event to destroy all windows, restart the winit event loop and recreate the
+/// windows in their current state, e.g. to recover from a GPU driver reset or to switch
+/// backends after changing `WINIT_UNIX_BACKEND`.
+///
+/// Windows are recreated with their old [WindowId](bevy_window::WindowId)s, and a
+/// [WindowClosed](bevy_window::WindowClosed) and a [WindowCreated](bevy_window::WindowCreated)
+/// event is sent for each of them. Only supported with
+/// [WinitConfig::return_from_run](crate::WinitConfig::return_from_run).
+#[derive(Debug, Clone, Default)]
+pub struct RestartBackend;
+
+/// Picks the backend winit will use, mirroring its own selection: `WINIT_UNIX_BACKEND` if set,
+/// otherwise Wayland before X11.
+pub fn detect_backend() -> Result<WinitBackend, WinitBackendError> {
+    select_backend(DisplayBackend::Auto)
+}
+
+/// Picks the backend the runner starts, `preferred` overrides `WINIT_UNIX_BACKEND`.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub fn select_backend(preferred: DisplayBackend) -> Result<WinitBackend, WinitBackendError> {
+    let compiled = [
+        (
+            WinitBackend::Wayland,
+            cfg!(feature = "wayland"),
+            "WAYLAND_DISPLAY",
+        ),
+        (WinitBackend::X11, cfg!(feature = "x11"), "DISPLAY"),
+    ];
+    let forced = match preferred {
+        DisplayBackend::Auto => std::env::var("WINIT_UNIX_BACKEND").ok(),
+        DisplayBackend::X11 if !cfg!(feature = "x11") => {
+            return Err(WinitBackendError::NotCompiled(WinitBackend::X11))
+        }
+        DisplayBackend::Wayland if !cfg!(feature = "wayland") => {
+            return Err(WinitBackendError::NotCompiled(WinitBackend::Wayland))
+        }
+        DisplayBackend::X11 => Some("x11".to_string()),
+        DisplayBackend::Wayland => Some("wayland".to_string()),
+    };
+
+    let mut available = compiled
+        .iter()
+        .filter(|(_, enabled, _)| *enabled)
+        .peekable();
+    if available.peek().is_none() {
+        return Err(WinitBackendError::NoBackendCompiled);
+    }
+
+    available
+        .clone()
+        .find(|(backend, _, display_var)| {
+            let allowed = match forced.as_deref() {
+                Some("x11") => *backend == WinitBackend::X11,
+                Some("wayland") => *backend == WinitBackend::Wayland,
+                _ => true,
+            };
+            allowed && std::env::var_os(display_var).is_some()
+        })
+        .map(|(backend, _, _)| *backend)
+        .ok_or_else(|| WinitBackendError::NoDisplay {
+            compiled: available.map(|(backend, _, _)| *backend).collect(),
+        })
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+pub fn select_backend(preferred: DisplayBackend) -> Result<WinitBackend, WinitBackendError> {
+    if preferred != DisplayBackend::Auto {
+        bevy_utils::tracing::warn!(
+            "{:?} was requested, but this platform only has its native backend",
+            preferred
+        );
+    }
+    Ok(WinitBackend::Native)
+}
+
+/// What windows support on `backend` with winit 0.25. A lock of the cursor needs
+/// [WinitConfig::emulate_cursor_lock](crate::WinitConfig::emulate_cursor_lock) where it can
+/// only be confined natively.
+pub fn window_capabilities(backend: WinitBackend, emulate_cursor_lock: bool) -> WindowCapabilities {
+    let mobile_or_web = cfg!(any(
+        target_arch = "wasm32",
+        target_os = "ios",
+        target_os = "android"
+    ));
+    let native_lock = cfg!(any(target_os = "macos", target_arch = "wasm32"));
+    WindowCapabilities {
+        // Wayland compositors only offer borderless fullscreen
+        exclusive_fullscreen: backend != WinitBackend::Wayland
+            && !cfg!(any(target_arch = "wasm32", target_os = "android")),
+        // emulation moves the cursor, which Wayland does not allow
+        cursor_lock: native_lock || (emulate_cursor_lock && backend != WinitBackend::Wayland),
+        transparency: !mobile_or_web,
+        decorations_toggle: !mobile_or_web,
+    }
+}
+
+/// Creates the event loop on `backend`, off the main thread. winit may panic if it cannot
+/// connect to the display server.
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+pub(crate) fn create_event_loop(backend: WinitBackend) -> Result<EventLoop<()>, String> {
+    match backend {
+        #[cfg(all(
+            feature = "x11",
+            any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            )
+        ))]
+        WinitBackend::X11 => EventLoop::new_x11_any_thread().map_err(|e| e.to_string()),
+        #[cfg(all(
+            feature = "wayland",
+            any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            )
+        ))]
+        WinitBackend::Wayland => Ok(EventLoop::new_wayland_any_thread()),
+        _ => Ok(EventLoop::new_any_thread()),
+    }
+}
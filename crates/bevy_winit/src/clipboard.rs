@@ -0,0 +1,299 @@
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    ElementState,
+};
+use bevy_utils::{tracing::warn, Duration};
+use bevy_window::WindowId;
+use std::sync::{mpsc, Mutex};
+use winit::event::ModifiersState;
+#[cfg(not(target_arch = "wasm32"))]
+use winit::event_loop::EventLoopProxy;
+
+/// How long [Clipboard::get_text] waits for the clipboard to be read before giving up.
+const GET_TEXT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Reads and writes the text of the system clipboard. Inserted as a resource by the winit
+/// runner, the requests are carried out by a worker of the winit thread, as most platforms
+/// need subprocesses for them.
+pub struct Clipboard {
+    request_sender: Mutex<mpsc::Sender<ClipboardRequest>>,
+    // the proxy of the web cannot be shared, and there is no other thread to wake it from
+    #[cfg(not(target_arch = "wasm32"))]
+    event_loop_proxy: Mutex<Option<EventLoopProxy<()>>>,
+}
+
+/// An event that is sent when the content of the system clipboard changed. Only sent on
+/// Windows, the other platforms have no way to tell without reading the clipboard.
+#[derive(Debug, Clone, Default)]
+pub struct ClipboardChanged;
+
+/// An event that is sent when the user presses the paste shortcut of the platform in a window,
+/// with the text on the clipboard. The clipboard is read once the key was pressed, so this follows
+/// the [KeyboardInput] of the shortcut. Only sent with
+/// [WinitConfig::paste_events](crate::WinitConfig::paste_events).
+#[derive(Debug, Clone)]
+pub struct TextPasted {
+    pub id: WindowId,
+    pub text: String,
+}
+
+pub(crate) enum ClipboardRequest {
+    SetText(String),
+    GetText(mpsc::Sender<Option<String>>),
+}
+
+impl Clipboard {
+    pub(crate) fn new(request_sender: mpsc::Sender<ClipboardRequest>) -> Self {
+        Clipboard {
+            request_sender: Mutex::new(request_sender),
+            #[cfg(not(target_arch = "wasm32"))]
+            event_loop_proxy: Mutex::new(None),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn set_event_loop_proxy(&self, proxy: EventLoopProxy<()>) {
+        *self.event_loop_proxy.lock().unwrap() = Some(proxy);
+    }
+
+    pub fn set_text(&self, text: impl Into<String>) {
+        self.send(ClipboardRequest::SetText(text.into()));
+    }
+
+    /// The text on the clipboard, if there is any. Blocks until the winit thread has read it.
+    pub fn get_text(&self) -> Option<String> {
+        let (text_sender, text_receiver) = mpsc::channel();
+        self.send(ClipboardRequest::GetText(text_sender));
+        text_receiver
+            .recv_timeout(GET_TEXT_TIMEOUT)
+            .unwrap_or_else(|_| {
+                warn!("Timed out reading the clipboard");
+                None
+            })
+    }
+
+    fn send(&self, request: ClipboardRequest) {
+        // fails only after the event loop has stopped
+        let _ = self.request_sender.lock().unwrap().send(request);
+        // the event loop may be waiting for events, so wake it up to handle the request
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(proxy) = self.event_loop_proxy.lock().unwrap().as_ref() {
+            let _ = proxy.send_event(());
+        }
+    }
+}
+
+/// Carries out `request` on the clipboard worker of the winit thread.
+pub(crate) fn handle_request(request: ClipboardRequest) {
+    match request {
+        ClipboardRequest::SetText(text) => {
+            if !platform::set_text(&text) {
+                warn!("Unable to write to the clipboard");
+            }
+        }
+        ClipboardRequest::GetText(text_sender) => {
+            let _ = text_sender.send(platform::get_text());
+        }
+    }
+}
+
+/// Reads the text on the clipboard on the clipboard worker of the winit thread.
+pub(crate) fn get_text() -> Option<String> {
+    platform::get_text()
+}
+
+/// Whether `input` is the paste shortcut of the platform: Cmd+V on macOS, Ctrl+V or
+/// Shift+Insert elsewhere.
+pub(crate) fn is_paste_shortcut(input: &KeyboardInput, modifiers: ModifiersState) -> bool {
+    if input.state != ElementState::Pressed {
+        return false;
+    }
+    let command = if cfg!(target_os = "macos") {
+        modifiers.logo()
+    } else {
+        modifiers.ctrl()
+    };
+    match input.key_code {
+        Some(KeyCode::V) => command && !modifiers.alt(),
+        Some(KeyCode::Insert) => !cfg!(target_os = "macos") && modifiers.shift(),
+        _ => false,
+    }
+}
+
+/// A number that changes whenever the clipboard content changes, on platforms that have one.
+pub(crate) fn sequence_number() -> Option<u32> {
+    platform::sequence_number()
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::ptr;
+    use winapi::um::{
+        winbase::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE},
+        winuser::{
+            CloseClipboard, EmptyClipboard, GetClipboardData, GetClipboardSequenceNumber,
+            OpenClipboard, SetClipboardData, CF_UNICODETEXT,
+        },
+    };
+
+    pub fn set_text(text: &str) -> bool {
+        let wide = text.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
+        unsafe {
+            if OpenClipboard(ptr::null_mut()) == 0 {
+                return false;
+            }
+            EmptyClipboard();
+            let handle = GlobalAlloc(GMEM_MOVEABLE, wide.len() * 2);
+            let written = !handle.is_null() && {
+                let data = GlobalLock(handle) as *mut u16;
+                if !data.is_null() {
+                    ptr::copy_nonoverlapping(wide.as_ptr(), data, wide.len());
+                    GlobalUnlock(handle);
+                }
+                // the clipboard owns the memory from here on
+                !data.is_null() && !SetClipboardData(CF_UNICODETEXT, handle).is_null()
+            };
+            CloseClipboard();
+            written
+        }
+    }
+
+    pub fn get_text() -> Option<String> {
+        unsafe {
+            if OpenClipboard(ptr::null_mut()) == 0 {
+                return None;
+            }
+            let handle = GetClipboardData(CF_UNICODETEXT);
+            let data = if handle.is_null() {
+                ptr::null_mut()
+            } else {
+                GlobalLock(handle) as *const u16
+            };
+            let text = if data.is_null() {
+                None
+            } else {
+                let len = (0..).take_while(|&i| *data.add(i) != 0).count();
+                let text = String::from_utf16_lossy(std::slice::from_raw_parts(data, len));
+                GlobalUnlock(handle);
+                Some(text)
+            };
+            CloseClipboard();
+            text
+        }
+    }
+
+    pub fn sequence_number() -> Option<u32> {
+        Some(unsafe { GetClipboardSequenceNumber() })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use std::{
+        io::Write,
+        process::{Command, Stdio},
+    };
+
+    /// The command line tools that write and read the clipboard on this platform, in order of
+    /// preference.
+    #[cfg(target_os = "macos")]
+    fn tools() -> Vec<(&'static [&'static str], &'static [&'static str])> {
+        vec![(&["pbcopy"], &["pbpaste"])]
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn tools() -> Vec<(&'static [&'static str], &'static [&'static str])> {
+        let mut tools: Vec<(&'static [&'static str], &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            tools.push((&["wl-copy"], &["wl-paste", "--no-newline"]));
+        }
+        tools.push((
+            &["xclip", "-selection", "clipboard", "-in"],
+            &["xclip", "-selection", "clipboard", "-out"],
+        ));
+        tools.push((
+            &["xsel", "--clipboard", "--input"],
+            &["xsel", "--clipboard", "--output"],
+        ));
+        tools
+    }
+
+    pub fn set_text(text: &str) -> bool {
+        tools().iter().any(|(copy, _)| {
+            let child = Command::new(copy[0])
+                .args(&copy[1..])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            let mut child = match child {
+                Ok(child) => child,
+                Err(_) => return false,
+            };
+            let written = child
+                .stdin
+                .take()
+                .map_or(false, |mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+            // the tools fork to keep serving the clipboard, so this does not block for long
+            child.wait().map_or(false, |status| status.success()) && written
+        })
+    }
+
+    pub fn get_text() -> Option<String> {
+        tools().iter().find_map(|(_, paste)| {
+            let output = Command::new(paste[0])
+                .args(&paste[1..])
+                .stderr(Stdio::null())
+                .output()
+                .ok()?;
+            if output.status.success() {
+                Some(String::from_utf8_lossy(&output.stdout).into_owned())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn sequence_number() -> Option<u32> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(key_code: KeyCode) -> KeyboardInput {
+        KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state: ElementState::Pressed,
+            repeat: false,
+            text: None,
+            received_at: None,
+        }
+    }
+
+    #[test]
+    fn paste_shortcut() {
+        let command = if cfg!(target_os = "macos") {
+            ModifiersState::LOGO
+        } else {
+            ModifiersState::CTRL
+        };
+        assert!(is_paste_shortcut(&press(KeyCode::V), command));
+        assert!(!is_paste_shortcut(
+            &press(KeyCode::V),
+            ModifiersState::empty()
+        ));
+        assert!(!is_paste_shortcut(
+            &press(KeyCode::V),
+            command | ModifiersState::ALT
+        ));
+        assert!(!is_paste_shortcut(&press(KeyCode::C), command));
+
+        let mut release = press(KeyCode::V);
+        release.state = ElementState::Released;
+        assert!(!is_paste_shortcut(&release, command));
+    }
+}
@@ -1,16 +1,33 @@
 use bevy_input::{
-    keyboard::{KeyCode, KeyboardInput},
-    mouse::MouseButton,
+    keyboard::{KeyCode, KeyboardInput, ModifierKeysState},
+    mouse::{MouseButton, MouseScrollUnit, MouseWheel},
     touch::{ForceTouch, TouchInput, TouchPhase},
     ElementState,
 };
-use bevy_math::Vec2;
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_utils::Instant;
+use bevy_window::{
+    CursorIcon, Monitor, VideoMode, WindowCoordinateConvention, WindowId, WindowTheme,
+};
+use winit::dpi::LogicalPosition;
 
 pub fn convert_keyboard_input(keyboard_input: &winit::event::KeyboardInput) -> KeyboardInput {
     KeyboardInput {
         scan_code: keyboard_input.scancode,
         state: convert_element_state(keyboard_input.state),
         key_code: keyboard_input.virtual_keycode.map(convert_virtual_key_code),
+        repeat: false,
+        text: None,
+        received_at: None,
+    }
+}
+
+pub fn convert_modifiers_state(modifiers: winit::event::ModifiersState) -> ModifierKeysState {
+    ModifierKeysState {
+        shift: modifiers.shift(),
+        ctrl: modifiers.ctrl(),
+        alt: modifiers.alt(),
+        logo: modifiers.logo(),
     }
 }
 
@@ -30,18 +47,55 @@ pub fn convert_mouse_button(mouse_button: winit::event::MouseButton) -> MouseBut
     }
 }
 
+pub fn convert_mouse_scroll_delta(delta: winit::event::MouseScrollDelta) -> MouseWheel {
+    match delta {
+        winit::event::MouseScrollDelta::LineDelta(x, y) => MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x,
+            y,
+            received_at: None,
+        },
+        winit::event::MouseScrollDelta::PixelDelta(p) => MouseWheel {
+            unit: MouseScrollUnit::Pixel,
+            x: p.x as f32,
+            y: p.y as f32,
+            received_at: None,
+        },
+    }
+}
+
+/// Converts a position in a window that is `window_height` high from the top left origin of
+/// winit to `convention`.
+pub fn convert_window_position(
+    position: LogicalPosition<f32>,
+    window_height: f32,
+    convention: WindowCoordinateConvention,
+) -> Vec2 {
+    Vec2::new(position.x, convention.convert_y(position.y, window_height))
+}
+
+/// Converts a position in a window that is `window_height` high from `convention` to the top
+/// left origin of winit.
+pub fn convert_to_winit_position(
+    position: Vec2,
+    window_height: f32,
+    convention: WindowCoordinateConvention,
+) -> LogicalPosition<f32> {
+    LogicalPosition::new(position.x, convention.convert_y(position.y, window_height))
+}
+
+/// Converts `touch_input` on `window`, with its location already converted to a `position` in
+/// the window.
 pub fn convert_touch_input(
     touch_input: winit::event::Touch,
-    location: winit::dpi::LogicalPosition<f32>,
+    window: WindowId,
+    position: Vec2,
+    received_at: Instant,
 ) -> TouchInput {
     TouchInput {
-        phase: match touch_input.phase {
-            winit::event::TouchPhase::Started => TouchPhase::Started,
-            winit::event::TouchPhase::Moved => TouchPhase::Moved,
-            winit::event::TouchPhase::Ended => TouchPhase::Ended,
-            winit::event::TouchPhase::Cancelled => TouchPhase::Cancelled,
-        },
-        position: Vec2::new(location.x as f32, location.y as f32),
+        phase: convert_touch_phase(touch_input.phase),
+        position,
+        window,
         force: touch_input.force.map(|f| match f {
             winit::event::Force::Calibrated {
                 force,
@@ -55,6 +109,16 @@ pub fn convert_touch_input(
             winit::event::Force::Normalized(x) => ForceTouch::Normalized(x),
         }),
         id: touch_input.id,
+        received_at: Some(received_at),
+    }
+}
+
+pub fn convert_touch_phase(phase: winit::event::TouchPhase) -> TouchPhase {
+    match phase {
+        winit::event::TouchPhase::Started => TouchPhase::Started,
+        winit::event::TouchPhase::Moved => TouchPhase::Moved,
+        winit::event::TouchPhase::Ended => TouchPhase::Ended,
+        winit::event::TouchPhase::Cancelled => TouchPhase::Cancelled,
     }
 }
 
@@ -225,3 +289,99 @@ pub fn convert_virtual_key_code(virtual_key_code: winit::event::VirtualKeyCode)
         winit::event::VirtualKeyCode::Cut => KeyCode::Cut,
     }
 }
+
+pub fn convert_cursor_icon(cursor_icon: CursorIcon) -> winit::window::CursorIcon {
+    match cursor_icon {
+        CursorIcon::Default => winit::window::CursorIcon::Default,
+        CursorIcon::Crosshair => winit::window::CursorIcon::Crosshair,
+        CursorIcon::Hand => winit::window::CursorIcon::Hand,
+        CursorIcon::Arrow => winit::window::CursorIcon::Arrow,
+        CursorIcon::Move => winit::window::CursorIcon::Move,
+        CursorIcon::Text => winit::window::CursorIcon::Text,
+        CursorIcon::Wait => winit::window::CursorIcon::Wait,
+        CursorIcon::Help => winit::window::CursorIcon::Help,
+        CursorIcon::Progress => winit::window::CursorIcon::Progress,
+        CursorIcon::NotAllowed => winit::window::CursorIcon::NotAllowed,
+        CursorIcon::ContextMenu => winit::window::CursorIcon::ContextMenu,
+        CursorIcon::Cell => winit::window::CursorIcon::Cell,
+        CursorIcon::VerticalText => winit::window::CursorIcon::VerticalText,
+        CursorIcon::Alias => winit::window::CursorIcon::Alias,
+        CursorIcon::Copy => winit::window::CursorIcon::Copy,
+        CursorIcon::NoDrop => winit::window::CursorIcon::NoDrop,
+        CursorIcon::Grab => winit::window::CursorIcon::Grab,
+        CursorIcon::Grabbing => winit::window::CursorIcon::Grabbing,
+        CursorIcon::AllScroll => winit::window::CursorIcon::AllScroll,
+        CursorIcon::ZoomIn => winit::window::CursorIcon::ZoomIn,
+        CursorIcon::ZoomOut => winit::window::CursorIcon::ZoomOut,
+        CursorIcon::EResize => winit::window::CursorIcon::EResize,
+        CursorIcon::NResize => winit::window::CursorIcon::NResize,
+        CursorIcon::NeResize => winit::window::CursorIcon::NeResize,
+        CursorIcon::NwResize => winit::window::CursorIcon::NwResize,
+        CursorIcon::SResize => winit::window::CursorIcon::SResize,
+        CursorIcon::SeResize => winit::window::CursorIcon::SeResize,
+        CursorIcon::SwResize => winit::window::CursorIcon::SwResize,
+        CursorIcon::WResize => winit::window::CursorIcon::WResize,
+        CursorIcon::EwResize => winit::window::CursorIcon::EwResize,
+        CursorIcon::NsResize => winit::window::CursorIcon::NsResize,
+        CursorIcon::NeswResize => winit::window::CursorIcon::NeswResize,
+        CursorIcon::NwseResize => winit::window::CursorIcon::NwseResize,
+        CursorIcon::ColResize => winit::window::CursorIcon::ColResize,
+        CursorIcon::RowResize => winit::window::CursorIcon::RowResize,
+    }
+}
+
+pub fn convert_theme(theme: winit::window::Theme) -> WindowTheme {
+    match theme {
+        winit::window::Theme::Light => WindowTheme::Light,
+        winit::window::Theme::Dark => WindowTheme::Dark,
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn convert_window_theme(theme: WindowTheme) -> winit::window::Theme {
+    match theme {
+        WindowTheme::Light => winit::window::Theme::Light,
+        WindowTheme::Dark => winit::window::Theme::Dark,
+    }
+}
+
+#[cfg(target_os = "ios")]
+pub fn convert_idiom(idiom: winit::platform::ios::Idiom) -> bevy_window::DeviceIdiom {
+    use bevy_window::DeviceIdiom;
+    use winit::platform::ios::Idiom;
+
+    match idiom {
+        Idiom::Unspecified => DeviceIdiom::Unspecified,
+        Idiom::Phone => DeviceIdiom::Phone,
+        Idiom::Pad => DeviceIdiom::Pad,
+        Idiom::TV => DeviceIdiom::Tv,
+        Idiom::CarPlay => DeviceIdiom::CarPlay,
+    }
+}
+
+pub fn convert_monitor(monitor: &winit::monitor::MonitorHandle, is_primary: bool) -> Monitor {
+    let size = monitor.size();
+    let position = monitor.position();
+    let video_modes = monitor
+        .video_modes()
+        .map(|video_mode| VideoMode {
+            physical_size: UVec2::new(video_mode.size().width, video_mode.size().height),
+            bit_depth: video_mode.bit_depth(),
+            refresh_rate: video_mode.refresh_rate(),
+        })
+        .collect::<Vec<_>>();
+    let physical_size = UVec2::new(size.width, size.height);
+    Monitor {
+        name: monitor.name(),
+        physical_size,
+        physical_position: IVec2::new(position.x, position.y),
+        scale_factor: monitor.scale_factor(),
+        refresh_rate: video_modes
+            .iter()
+            .filter(|video_mode| video_mode.physical_size == physical_size)
+            .map(|video_mode| video_mode.refresh_rate)
+            .max(),
+        video_modes,
+        is_primary,
+    }
+}
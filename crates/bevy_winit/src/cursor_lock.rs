@@ -0,0 +1,78 @@
+use bevy_math::Vec2;
+use winit::dpi::PhysicalPosition;
+
+/// A cursor lock that the runner emulates by moving the cursor back to the center of the window
+/// whenever it moves, on platforms that can only confine the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct EmulatedCursorLock {
+    /// Where the cursor was last reported, which its motion is measured from.
+    last_position: Option<PhysicalPosition<f64>>,
+    /// Where the cursor was moved back to, until the platform reports that movement.
+    warped_to: Option<PhysicalPosition<f64>>,
+}
+
+impl EmulatedCursorLock {
+    /// How far the cursor moved when it is reported at `position`, in physical pixels with y
+    /// pointing down like raw mouse motion. The movement back to the center is no motion, as
+    /// it was not made by the user.
+    pub fn motion(&mut self, position: PhysicalPosition<f64>) -> Vec2 {
+        if let Some(warped_to) = self.warped_to {
+            // the platform may round the position it reports
+            if (position.x - warped_to.x).abs() < 1.0 && (position.y - warped_to.y).abs() < 1.0 {
+                self.warped_to = None;
+                self.last_position = Some(position);
+                return Vec2::ZERO;
+            }
+        }
+        let delta = self.last_position.map_or(Vec2::ZERO, |last| {
+            Vec2::new((position.x - last.x) as f32, (position.y - last.y) as f32)
+        });
+        self.last_position = Some(position);
+        delta
+    }
+
+    /// Whether the cursor has to be moved back to `center`, which it is not while it is
+    /// already on its way there.
+    pub fn needs_warp(&self, center: PhysicalPosition<f64>) -> bool {
+        self.warped_to.is_none() && self.last_position != Some(center)
+    }
+
+    /// Called once the cursor was moved back to `center`.
+    pub fn warped(&mut self, center: PhysicalPosition<f64>) {
+        self.warped_to = Some(center);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CENTER: PhysicalPosition<f64> = PhysicalPosition::new(50.0, 50.0);
+
+    #[test]
+    fn drops_the_movement_back_to_the_center() {
+        let mut lock = EmulatedCursorLock::default();
+        assert_eq!(lock.motion(CENTER), Vec2::ZERO);
+        assert!(!lock.needs_warp(CENTER));
+
+        assert_eq!(
+            lock.motion(PhysicalPosition::new(53.0, 48.0)),
+            Vec2::new(3.0, -2.0)
+        );
+        assert!(lock.needs_warp(CENTER));
+        lock.warped(CENTER);
+        assert!(!lock.needs_warp(CENTER));
+
+        // reported before the warp was carried out
+        assert_eq!(
+            lock.motion(PhysicalPosition::new(55.0, 48.0)),
+            Vec2::new(2.0, 0.0)
+        );
+        assert_eq!(lock.motion(CENTER), Vec2::ZERO);
+        assert_eq!(
+            lock.motion(PhysicalPosition::new(49.0, 50.0)),
+            Vec2::new(-1.0, 0.0)
+        );
+        assert!(lock.needs_warp(CENTER));
+    }
+}
@@ -0,0 +1,50 @@
+use bevy_input::device::DeviceId;
+use bevy_utils::HashMap;
+
+/// Assigns a [DeviceId] to each of the opaque device ids reported by winit.
+///
+/// Ids are never reused, so events of a device that was removed cannot be mistaken for events
+/// of a device that was added later.
+#[derive(Default)]
+pub(crate) struct DeviceIds {
+    ids: HashMap<winit::event::DeviceId, DeviceId>,
+    next: u64,
+}
+
+impl DeviceIds {
+    pub fn get(&mut self, device_id: winit::event::DeviceId) -> DeviceId {
+        let next = &mut self.next;
+        *self.ids.entry(device_id).or_insert_with(|| {
+            let id = DeviceId(*next);
+            *next += 1;
+            id
+        })
+    }
+
+    pub fn remove(&mut self, device_id: winit::event::DeviceId) -> DeviceId {
+        let id = self.get(device_id);
+        self.ids.remove(&device_id);
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_never_reused() {
+        let mut ids = DeviceIds::default();
+        let device = unsafe { winit::event::DeviceId::dummy() };
+
+        let first = ids.get(device);
+        assert_eq!(ids.get(device), first);
+        assert_eq!(ids.remove(device), first);
+        // the same winit id after a removal is a new device
+        let second = ids.get(device);
+        assert_ne!(second, first);
+        assert_eq!(ids.remove(device), second);
+        let third = ids.get(device);
+        assert!(third != first && third != second);
+    }
+}
@@ -0,0 +1,127 @@
+use bevy_app::{AppBuilder, Plugin};
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::system::{IntoSystem, Res, ResMut};
+use bevy_utils::Duration;
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+use bevy_utils::Instant;
+
+/// What went through the channel from the winit thread to the app since the previous update.
+/// Updated by the runner of the [WinitPlugin](crate::WinitPlugin), and reported by the
+/// [WinitDiagnosticsPlugin].
+#[derive(Debug, Clone, Default)]
+pub struct WinitChannelStats {
+    /// How many events the app received.
+    pub events_drained: usize,
+    /// The most events that were waiting in the channel at once.
+    pub peak_depth: usize,
+    /// The time the received events spent between being sent by the winit thread and being
+    /// received by the app, added up.
+    pub total_event_age: Duration,
+    /// How many events were dropped on the way, because their window masked them or because
+    /// they duplicated an earlier event, like repeated close requests.
+    pub dropped_events: usize,
+}
+
+impl WinitChannelStats {
+    /// The average time an event spent in the channel.
+    pub fn average_event_age(&self) -> Option<Duration> {
+        if self.events_drained == 0 {
+            return None;
+        }
+        Some(self.total_event_age / self.events_drained as u32)
+    }
+
+    /// Records a batch of events that were waiting in the channel together, sent at `sent_at`
+    /// and received at `now`.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    pub(crate) fn record_batch(
+        &mut self,
+        sent_at: impl ExactSizeIterator<Item = Instant>,
+        now: Instant,
+    ) {
+        self.events_drained += sent_at.len();
+        self.peak_depth = self.peak_depth.max(sent_at.len());
+        for sent_at in sent_at {
+            self.total_event_age += now.saturating_duration_since(sent_at);
+        }
+    }
+}
+
+/// Adds diagnostics for the channel between the winit thread and the app, to profile how
+/// events pile up and how late they arrive: "winit_events_drained", "winit_peak_channel_depth",
+/// "winit_event_age" and "winit_dropped_events".
+#[derive(Default)]
+pub struct WinitDiagnosticsPlugin;
+
+impl Plugin for WinitDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<WinitChannelStats>()
+            .add_startup_system(Self::setup_system.system())
+            .add_system(Self::diagnostic_system.system());
+    }
+}
+
+impl WinitDiagnosticsPlugin {
+    pub const EVENTS_DRAINED: DiagnosticId =
+        DiagnosticId::from_u128(198475630028839273001937456810293847561);
+    pub const PEAK_CHANNEL_DEPTH: DiagnosticId =
+        DiagnosticId::from_u128(91827364510293847560019283746501928374);
+    pub const EVENT_AGE: DiagnosticId =
+        DiagnosticId::from_u128(283746510928374650192837465019283746510);
+    pub const DROPPED_EVENTS: DiagnosticId =
+        DiagnosticId::from_u128(12039485761029384756102938475610293847);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::EVENTS_DRAINED,
+            "winit_events_drained",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::PEAK_CHANNEL_DEPTH,
+            "winit_peak_channel_depth",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(Self::EVENT_AGE, "winit_event_age", 20).with_suffix("s"));
+        diagnostics.add(Diagnostic::new(
+            Self::DROPPED_EVENTS,
+            "winit_dropped_events",
+            20,
+        ));
+    }
+
+    pub fn diagnostic_system(mut diagnostics: ResMut<Diagnostics>, stats: Res<WinitChannelStats>) {
+        diagnostics.add_measurement(Self::EVENTS_DRAINED, stats.events_drained as f64);
+        diagnostics.add_measurement(Self::PEAK_CHANNEL_DEPTH, stats.peak_depth as f64);
+        if let Some(event_age) = stats.average_event_age() {
+            diagnostics.add_measurement(Self::EVENT_AGE, event_age.as_secs_f64());
+        }
+        diagnostics.add_measurement(Self::DROPPED_EVENTS, stats.dropped_events as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    fn batches_are_recorded() {
+        let now = Instant::now();
+        let mut stats = WinitChannelStats::default();
+        assert_eq!(stats.average_event_age(), None);
+
+        stats.record_batch(
+            vec![
+                now - Duration::from_millis(4),
+                now - Duration::from_millis(2),
+            ]
+            .into_iter(),
+            now,
+        );
+        stats.record_batch(vec![now].into_iter(), now);
+        assert_eq!(stats.events_drained, 3);
+        assert_eq!(stats.peak_depth, 2);
+        assert_eq!(stats.average_event_age(), Some(Duration::from_millis(2)));
+    }
+}
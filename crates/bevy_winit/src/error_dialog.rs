@@ -0,0 +1,127 @@
+use bevy_utils::tracing::error;
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+use std::any::Any;
+
+/// Shows a native message box with an error and blocks until the user dismisses it.
+///
+/// Meant for fatal errors right before the process exits, so that users who did not start the
+/// app from a terminal learn why its window vanished. The message is logged as well, and if no
+/// message box can be shown on this platform, that is all that happens. It does not depend on
+/// a running event loop and can be called from any thread.
+pub fn show_error_dialog(title: &str, message: &str) {
+    error!("{}: {}", title, message);
+    if !show_native_dialog(title, message) {
+        error!("Unable to show an error dialog on this platform");
+    }
+}
+
+/// The message a panic was started with, if it was a string.
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "windows")]
+fn show_native_dialog(title: &str, message: &str) -> bool {
+    use std::{ffi::OsStr, iter, os::windows::ffi::OsStrExt, ptr};
+    use winapi::um::winuser::{MessageBoxW, MB_ICONERROR, MB_OK, MB_SETFOREGROUND};
+
+    fn to_wide(text: &str) -> Vec<u16> {
+        OsStr::new(text)
+            .encode_wide()
+            .chain(iter::once(0))
+            .collect()
+    }
+
+    let title = to_wide(title);
+    let message = to_wide(message);
+    let result = unsafe {
+        MessageBoxW(
+            ptr::null_mut(),
+            message.as_ptr(),
+            title.as_ptr(),
+            MB_OK | MB_ICONERROR | MB_SETFOREGROUND,
+        )
+    };
+    result != 0
+}
+
+#[cfg(target_os = "macos")]
+fn show_native_dialog(title: &str, message: &str) -> bool {
+    // the texts are passed as arguments so they need no escaping
+    std::process::Command::new("osascript")
+        .args(&[
+            "-e",
+            "on run argv",
+            "-e",
+            "display alert (item 1 of argv) message (item 2 of argv) as critical",
+            "-e",
+            "end run",
+            title,
+            message,
+        ])
+        .status()
+        .is_ok()
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn show_native_dialog(title: &str, message: &str) -> bool {
+    use std::process::Command;
+
+    // there is no message box in the system libraries, so try the dialog tools of the common
+    // desktops, falling back to the one that comes with X11
+    let text = format!("{}\n\n{}", title, message);
+    let dialogs: [(&str, &[&str]); 3] = [
+        (
+            "zenity",
+            &[
+                "--error",
+                "--no-markup",
+                "--title",
+                title,
+                "--text",
+                message,
+            ],
+        ),
+        ("kdialog", &["--title", title, "--error", message]),
+        ("xmessage", &["-center", &text]),
+    ];
+    dialogs
+        .iter()
+        .any(|(program, args)| Command::new(program).args(*args).status().is_ok())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn show_native_dialog(title: &str, message: &str) -> bool {
+    web_sys::window()
+        .map(|window| {
+            window
+                .alert_with_message(&format!("{}\n\n{}", title, message))
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_arch = "wasm32"
+)))]
+fn show_native_dialog(_title: &str, _message: &str) -> bool {
+    false
+}
@@ -0,0 +1,208 @@
+use crate::TextPasted;
+use bevy_app::Events;
+use bevy_ecs::{component::Component, world::WorldCell};
+use bevy_input::{
+    keyboard::{KeyboardInput, KeyboardModifiers},
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::{PenInput, TouchInput},
+};
+use bevy_utils::HashMap;
+use bevy_window::{
+    CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, ReceivedCharacter, SafeAreaChanged,
+    SurfaceLost, SurfaceRecreated, TouchesCancelled, WindowBackendScaleFactorChanged,
+    WindowCloseRequested, WindowFocused, WindowId, WindowMaximized, WindowMinimized, WindowMoved,
+    WindowRedrawRequested, WindowResized, WindowRestored, WindowScaleFactorChanged,
+    WindowThemeChanged,
+};
+
+/// The events produced by a batch of window events, grouped by type so that each `Events<T>`
+/// resource is looked up once per batch instead of once per event. Kept by each app, so the
+/// buffers are reused across batches.
+#[derive(Default)]
+pub(crate) struct WindowEventBuffers {
+    pub resized: Vec<WindowResized>,
+    pub close_requested: Vec<WindowCloseRequested>,
+    pub keyboard_input: Vec<KeyboardInput>,
+    pub text_pasted: Vec<TextPasted>,
+    pub keyboard_modifiers: Vec<KeyboardModifiers>,
+    pub mouse_motion: Vec<MouseMotion>,
+    pub cursor_moved: Vec<CursorMoved>,
+    pub cursor_entered: Vec<CursorEntered>,
+    pub cursor_left: Vec<CursorLeft>,
+    pub mouse_button_input: Vec<MouseButtonInput>,
+    pub mouse_wheel: Vec<MouseWheel>,
+    pub touch_input: Vec<TouchInput>,
+    pub pen_input: Vec<PenInput>,
+    pub touches_cancelled: Vec<TouchesCancelled>,
+    pub received_character: Vec<ReceivedCharacter>,
+    pub backend_scale_factor_changed: Vec<WindowBackendScaleFactorChanged>,
+    pub scale_factor_changed: Vec<WindowScaleFactorChanged>,
+    pub focused: Vec<WindowFocused>,
+    pub file_drag_and_drop: Vec<FileDragAndDrop>,
+    pub moved: Vec<WindowMoved>,
+    pub minimized: Vec<WindowMinimized>,
+    pub maximized: Vec<WindowMaximized>,
+    pub restored: Vec<WindowRestored>,
+    pub safe_area_changed: Vec<SafeAreaChanged>,
+    pub surface_lost: Vec<SurfaceLost>,
+    pub surface_recreated: Vec<SurfaceRecreated>,
+    pub theme_changed: Vec<WindowThemeChanged>,
+    pub redraw_requested: Vec<WindowRedrawRequested>,
+}
+
+impl WindowEventBuffers {
+    /// Sends the buffered events, keeping their order within each type.
+    pub fn flush(&mut self, world: &WorldCell) {
+        send(world, &mut self.resized);
+        send(world, &mut self.close_requested);
+        send(world, &mut self.keyboard_input);
+        send(world, &mut self.text_pasted);
+        send(world, &mut self.keyboard_modifiers);
+        send(world, &mut self.mouse_motion);
+        send(world, &mut self.cursor_moved);
+        send(world, &mut self.cursor_entered);
+        send(world, &mut self.cursor_left);
+        send(world, &mut self.mouse_button_input);
+        send(world, &mut self.mouse_wheel);
+        send(world, &mut self.touch_input);
+        send(world, &mut self.pen_input);
+        send(world, &mut self.touches_cancelled);
+        send(world, &mut self.received_character);
+        send(world, &mut self.backend_scale_factor_changed);
+        send(world, &mut self.scale_factor_changed);
+        send(world, &mut self.focused);
+        send(world, &mut self.file_drag_and_drop);
+        send(world, &mut self.moved);
+        send(world, &mut self.minimized);
+        send(world, &mut self.maximized);
+        send(world, &mut self.restored);
+        send(world, &mut self.safe_area_changed);
+        send(world, &mut self.surface_lost);
+        send(world, &mut self.surface_recreated);
+        send(world, &mut self.theme_changed);
+        send(world, &mut self.redraw_requested);
+    }
+}
+
+/// The latest resize, move, cursor movement and redraw request of each
+/// [low priority](bevy_window::Window::set_low_priority) window, which replace the earlier ones
+/// instead of being sent right away. Sent before the next update, so a low priority window
+/// reports at most one of each per frame.
+#[derive(Default)]
+pub(crate) struct CoalescedWindowEvents {
+    resized: HashMap<WindowId, WindowResized>,
+    moved: HashMap<WindowId, WindowMoved>,
+    cursor_moved: HashMap<WindowId, CursorMoved>,
+    redraw_requested: HashMap<WindowId, WindowRedrawRequested>,
+}
+
+impl CoalescedWindowEvents {
+    /// Takes the events of the window `id` that can be coalesced out of `buffers`, which hold a
+    /// batch of that window only.
+    pub fn coalesce(&mut self, id: WindowId, buffers: &mut WindowEventBuffers) {
+        keep_latest(&mut self.resized, id, &mut buffers.resized);
+        keep_latest(&mut self.moved, id, &mut buffers.moved);
+        keep_latest(&mut self.cursor_moved, id, &mut buffers.cursor_moved);
+        keep_latest(
+            &mut self.redraw_requested,
+            id,
+            &mut buffers.redraw_requested,
+        );
+    }
+
+    /// Sends the coalesced events of the window `id`, so that they are not sent after its newer
+    /// events once its priority was raised.
+    pub fn flush_window(&mut self, world: &WorldCell, id: WindowId) {
+        send_window(world, &mut self.resized, id);
+        send_window(world, &mut self.moved, id);
+        send_window(world, &mut self.cursor_moved, id);
+        send_window(world, &mut self.redraw_requested, id);
+    }
+
+    /// Sends the coalesced events of all windows.
+    pub fn flush(&mut self, world: &WorldCell) {
+        send_latest(world, &mut self.resized);
+        send_latest(world, &mut self.moved);
+        send_latest(world, &mut self.cursor_moved);
+        send_latest(world, &mut self.redraw_requested);
+    }
+}
+
+fn keep_latest<T>(latest: &mut HashMap<WindowId, T>, id: WindowId, events: &mut Vec<T>) {
+    if let Some(event) = events.pop() {
+        latest.insert(id, event);
+    }
+    events.clear();
+}
+
+fn send_window<T: Component>(world: &WorldCell, latest: &mut HashMap<WindowId, T>, id: WindowId) {
+    if let Some(event) = latest.remove(&id) {
+        world.get_resource_mut::<Events<T>>().unwrap().send(event);
+    }
+}
+
+fn send_latest<T: Component>(world: &WorldCell, latest: &mut HashMap<WindowId, T>) {
+    if !latest.is_empty() {
+        world
+            .get_resource_mut::<Events<T>>()
+            .unwrap()
+            .extend(latest.drain().map(|(_, event)| event));
+    }
+}
+
+fn send<T: Component>(world: &WorldCell, events: &mut Vec<T>) {
+    if !events.is_empty() {
+        world
+            .get_resource_mut::<Events<T>>()
+            .unwrap()
+            .extend(events.drain(..));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::world::World;
+
+    #[test]
+    fn keeps_the_latest_event_of_each_window() {
+        let (low, normal) = (WindowId::new(), WindowId::new());
+        let mut world = World::default();
+        world.insert_resource(Events::<WindowResized>::default());
+        let resized = |id, width| WindowResized {
+            id,
+            width,
+            height: 100.0,
+        };
+        let mut buffers = WindowEventBuffers::default();
+        let mut coalesced = CoalescedWindowEvents::default();
+
+        buffers.resized.push(resized(low, 100.0));
+        buffers.resized.push(resized(low, 200.0));
+        coalesced.coalesce(low, &mut buffers);
+        buffers.flush(&world.cell());
+        buffers.resized.push(resized(low, 300.0));
+        coalesced.coalesce(low, &mut buffers);
+        buffers.resized.push(resized(normal, 400.0));
+        buffers.resized.push(resized(normal, 500.0));
+        buffers.flush(&world.cell());
+
+        let sent = |world: &World| {
+            let events = world.get_resource::<Events<WindowResized>>().unwrap();
+            events
+                .get_reader()
+                .iter(events)
+                .map(|event| (event.id, event.width))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sent(&world), [(normal, 400.0), (normal, 500.0)]);
+
+        coalesced.flush(&world.cell());
+        assert_eq!(
+            sent(&world),
+            [(normal, 400.0), (normal, 500.0), (low, 300.0)]
+        );
+        coalesced.flush(&world.cell());
+        assert_eq!(sent(&world).len(), 3);
+    }
+}
@@ -0,0 +1,411 @@
+use crate::{
+    clipboard, converters, device_ids, gamepads, get_monitors, monitor_watch,
+    raw_input::RawInputSender, safe_area, system_settings, touch_ids, worker::Worker, EventMasks,
+    WinitConfig, WinitDeviceEvent, WinitEvent, WinitWindowEvent,
+};
+use bevy_input::{
+    device::{RawKeyboardInput, RawMouseButtonInput, RawMouseWheel},
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion},
+    ElementState,
+};
+use bevy_math::Vec2;
+use bevy_utils::{Duration, HashSet, Instant};
+use bevy_window::{ApplicationLifecycle, DeviceIdiom};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc, Arc,
+};
+use winit::{
+    event::{self, DeviceEvent, Event, WindowEvent},
+    event_loop::{ControlFlow, EventLoopProxy, EventLoopWindowTarget},
+    window::WindowId,
+};
+
+/// Converts winit events into [WinitEvent]s for the apps, keeping the state that has to live on
+/// the thread of the event loop. Shared by the threaded runner and the single-threaded web
+/// runner.
+pub(crate) struct EventConverter {
+    keyboard_input_senders: Vec<RawInputSender<KeyboardInput>>,
+    clipboard_request_receiver: mpsc::Receiver<clipboard::ClipboardRequest>,
+    paste_events: bool,
+    pressed_scan_codes: HashSet<u32>,
+    pending_key_press: Option<(KeyboardInput, WindowId)>,
+    modifiers: event::ModifiersState,
+    clipboard_sequence_number: Option<u32>,
+    touch_ids: touch_ids::TouchIdAllocator,
+    touch_cancellation: touch_ids::TouchCancellation,
+    device_ids: device_ids::DeviceIds,
+    gamepad_poller: gamepads::GamepadPoller,
+    monitor_watcher: monitor_watch::MonitorWatcher,
+    event_masks: EventMasks,
+    safe_area_watcher: safe_area::SafeAreaWatcher,
+    settings_worker: Worker,
+    clipboard_worker: Worker,
+    /// Set by runners that can query it from their event loop.
+    pub device_idiom: DeviceIdiom,
+    /// Counts the events dropped by event masks, shared with the app loop of runners that
+    /// report them in the [WinitChannelStats](crate::WinitChannelStats).
+    pub dropped_events: Arc<AtomicUsize>,
+}
+
+impl EventConverter {
+    pub fn new(
+        config: &WinitConfig,
+        keyboard_input_senders: Vec<RawInputSender<KeyboardInput>>,
+        clipboard_request_receiver: mpsc::Receiver<clipboard::ClipboardRequest>,
+        event_masks: EventMasks,
+        safe_area_views: safe_area::SafeAreaViews,
+        event_loop_proxy: EventLoopProxy<()>,
+    ) -> Self {
+        EventConverter {
+            keyboard_input_senders,
+            clipboard_request_receiver,
+            paste_events: config.paste_events,
+            pressed_scan_codes: HashSet::default(),
+            pending_key_press: None,
+            modifiers: event::ModifiersState::empty(),
+            clipboard_sequence_number: clipboard::sequence_number(),
+            touch_ids: touch_ids::TouchIdAllocator::default(),
+            touch_cancellation: touch_ids::TouchCancellation::default(),
+            device_ids: device_ids::DeviceIds::default(),
+            gamepad_poller: gamepads::GamepadPoller::new(config.poll_gamepads),
+            monitor_watcher: monitor_watch::MonitorWatcher::new(config.monitor_poll_interval),
+            event_masks,
+            safe_area_watcher: safe_area::SafeAreaWatcher::new(safe_area_views),
+            settings_worker: Worker::new("system settings", Some(event_loop_proxy.clone())),
+            clipboard_worker: Worker::new("clipboard", Some(event_loop_proxy)),
+            device_idiom: DeviceIdiom::Unspecified,
+            dropped_events: Default::default(),
+        }
+    }
+
+    /// How long the event loop may wait for new events, given the longest wait of the update
+    /// mode. Gamepads and monitors that are polled wake it up earlier.
+    pub fn control_flow(&self, max_wait: Option<Duration>) -> ControlFlow {
+        let mut control_flow = match max_wait {
+            None => ControlFlow::Poll,
+            Some(max_wait) => match Instant::now().checked_add(max_wait) {
+                Some(deadline) => ControlFlow::WaitUntil(deadline),
+                None => ControlFlow::Wait,
+            },
+        };
+        let gamepad_poll = if self.gamepad_poller.needs_regular_polls() {
+            Some(Instant::now() + gamepads::GAMEPAD_POLL_INTERVAL)
+        } else {
+            None
+        };
+        for poll_deadline in gamepad_poll
+            .into_iter()
+            .chain(self.monitor_watcher.next_poll())
+        {
+            match control_flow {
+                ControlFlow::Wait => control_flow = ControlFlow::WaitUntil(poll_deadline),
+                ControlFlow::WaitUntil(deadline) if deadline > poll_deadline => {
+                    control_flow = ControlFlow::WaitUntil(poll_deadline)
+                }
+                _ => {}
+            }
+        }
+        control_flow
+    }
+
+    /// Converts `event` and pushes the resulting events to `out`, along with the events of
+    /// polled devices and settings that are due. Window events that are masked by their window
+    /// are dropped.
+    pub fn convert(
+        &mut self,
+        event: Event<()>,
+        event_loop: &EventLoopWindowTarget<()>,
+        out: &mut Vec<WinitEvent>,
+    ) {
+        let start = out.len();
+        self.convert_unmasked(event, event_loop, out);
+
+        let event_masks = self.event_masks.read().unwrap();
+        if event_masks.is_empty() {
+            return;
+        }
+        let mut index = start;
+        while index < out.len() {
+            let forwarded = match &out[index] {
+                WinitEvent::WindowEvent(e, winit_window_id) => event_masks
+                    .get(winit_window_id)
+                    .map_or(true, |mask| e.is_forwarded(mask)),
+                _ => true,
+            };
+            if forwarded {
+                index += 1;
+            } else {
+                out.remove(index);
+                self.dropped_events.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Sends `input` to the raw input channel of the app that owns the window.
+    fn send_raw_input(&self, input: &KeyboardInput, winit_window_id: WindowId) {
+        for keyboard_input_sender in self.keyboard_input_senders.iter() {
+            if keyboard_input_sender.is_for(winit_window_id) {
+                keyboard_input_sender.send(input.clone());
+            }
+        }
+    }
+
+    fn convert_unmasked(
+        &mut self,
+        event: Event<()>,
+        event_loop: &EventLoopWindowTarget<()>,
+        out: &mut Vec<WinitEvent>,
+    ) {
+        if self.gamepad_poller.is_active() {
+            for gamepad_event in self.gamepad_poller.poll() {
+                out.push(WinitEvent::Gamepad(gamepad_event));
+            }
+        }
+
+        #[cfg(target_os = "ios")]
+        if crate::ios::take_memory_warning() {
+            out.push(WinitEvent::Lifecycle(ApplicationLifecycle::MemoryWarning));
+        }
+
+        // the clipboard is read and written by subprocesses on most platforms
+        while let Ok(request) = self.clipboard_request_receiver.try_recv() {
+            self.clipboard_worker.run(move || {
+                clipboard::handle_request(request);
+                None
+            });
+        }
+        self.clipboard_worker.take_results(out);
+        let sequence_number = clipboard::sequence_number();
+        if sequence_number != self.clipboard_sequence_number {
+            self.clipboard_sequence_number = sequence_number;
+            out.push(WinitEvent::ClipboardChanged);
+        }
+
+        // winit reports the text of a key press as a separate `ReceivedCharacter` right
+        // after it, so presses are held back by one event to attach that text
+        if let Some((mut input, winit_window_id)) = self.pending_key_press.take() {
+            if let event::Event::WindowEvent {
+                event: WindowEvent::ReceivedCharacter(c),
+                window_id,
+            } = &event
+            {
+                if *window_id == winit_window_id && !c.is_control() {
+                    input.text = Some(c.to_string());
+                }
+            }
+
+            self.send_raw_input(&input, winit_window_id);
+            // the text follows the key press once it was read
+            if self.paste_events && clipboard::is_paste_shortcut(&input, self.modifiers) {
+                self.clipboard_worker.run(move || {
+                    let text = clipboard::get_text()?;
+                    Some(WinitEvent::WindowEvent(
+                        WinitWindowEvent::TextPasted(text),
+                        winit_window_id,
+                    ))
+                });
+            }
+            let e =
+                WinitEvent::WindowEvent(WinitWindowEvent::KeyboardInput(input), winit_window_id);
+            out.push(e);
+        }
+
+        // resolution changes of monitors are only noticed by polling
+        let monitors_changed = self.monitor_watcher.poll(event_loop, Instant::now());
+        if monitors_changed
+            || matches!(
+                event,
+                event::Event::NewEvents(event::StartCause::Init)
+                    | event::Event::WindowEvent {
+                        event: WindowEvent::ScaleFactorChanged { .. },
+                        ..
+                    }
+            )
+        {
+            if !monitors_changed {
+                self.monitor_watcher.update(event_loop);
+            }
+            let e = WinitEvent::Monitors(get_monitors(event_loop));
+            out.push(e);
+        }
+
+        if let event::Event::NewEvents(event::StartCause::Init) = event {
+            let device_idiom = self.device_idiom;
+            self.settings_worker.run(move || {
+                let mut info = system_settings::query_system_info();
+                info.device_idiom = device_idiom;
+                Some(WinitEvent::SystemInfo(info))
+            });
+        }
+
+        // there is no notification for changed accessibility settings, so they are queried
+        // again whenever the user returns to a window
+        if matches!(
+            event,
+            event::Event::NewEvents(event::StartCause::Init)
+                | event::Event::WindowEvent {
+                    event: WindowEvent::Focused(true),
+                    ..
+                }
+        ) {
+            self.settings_worker.run(|| {
+                Some(WinitEvent::SystemPreferences(
+                    system_settings::query_system_preferences(),
+                ))
+            });
+        }
+        self.settings_worker.take_results(out);
+
+        let e = match event {
+            event::Event::WindowEvent {
+                event,
+                window_id: winit_window_id,
+                ..
+            } => {
+                let safe_area = self.safe_area_watcher.poll(winit_window_id, &event);
+                let e = match event {
+                    WindowEvent::Resized(size) => WinitWindowEvent::Resized(size),
+                    WindowEvent::CloseRequested => WinitWindowEvent::CloseRequested,
+                    WindowEvent::KeyboardInput { ref input, .. } => {
+                        let mut input = converters::convert_keyboard_input(input);
+                        input.received_at = Some(Instant::now());
+
+                        match input.state {
+                            ElementState::Pressed => {
+                                input.repeat = !self.pressed_scan_codes.insert(input.scan_code);
+                                self.pending_key_press = Some((input, winit_window_id));
+                                WinitWindowEvent::None
+                            }
+                            ElementState::Released => {
+                                self.pressed_scan_codes.remove(&input.scan_code);
+                                self.send_raw_input(&input, winit_window_id);
+                                WinitWindowEvent::KeyboardInput(input)
+                            }
+                        }
+                    }
+                    WindowEvent::ModifiersChanged(state) => {
+                        self.modifiers = state;
+                        WinitWindowEvent::ModifiersChanged(state)
+                    }
+                    WindowEvent::CursorMoved { position, .. } => {
+                        WinitWindowEvent::CursorMoved(position, Instant::now())
+                    }
+                    WindowEvent::CursorEntered { .. } => WinitWindowEvent::CursorEntered,
+                    WindowEvent::CursorLeft { .. } => WinitWindowEvent::CursorLeft,
+                    WindowEvent::MouseInput { state, button, .. } => {
+                        WinitWindowEvent::MouseInput(MouseButtonInput {
+                            button: converters::convert_mouse_button(button),
+                            state: converters::convert_element_state(state),
+                            received_at: Some(Instant::now()),
+                        })
+                    }
+                    WindowEvent::MouseWheel { delta, .. } => {
+                        let mut input = converters::convert_mouse_scroll_delta(delta);
+                        input.received_at = Some(Instant::now());
+                        WinitWindowEvent::MouseWheel(input)
+                    }
+                    WindowEvent::Touch(mut touch) => {
+                        let received_at = Instant::now();
+                        touch.id = self.touch_ids.map(touch.device_id, touch.id, touch.phase);
+                        if self
+                            .touch_cancellation
+                            .process(winit_window_id, touch.id, touch.phase)
+                        {
+                            let e = WinitEvent::WindowEvent(
+                                WinitWindowEvent::Touch(touch, received_at),
+                                winit_window_id,
+                            );
+                            out.push(e);
+                            WinitWindowEvent::TouchesCancelled
+                        } else {
+                            WinitWindowEvent::Touch(touch, received_at)
+                        }
+                    }
+                    WindowEvent::ReceivedCharacter(c) => WinitWindowEvent::ReceivedCharacter(c),
+                    WindowEvent::ScaleFactorChanged {
+                        scale_factor,
+                        new_inner_size,
+                    } => WinitWindowEvent::ScaleFactorChanged(scale_factor, *new_inner_size),
+                    WindowEvent::Focused(focused) => {
+                        if !focused {
+                            // releases are not reported while unfocused
+                            self.pressed_scan_codes.clear();
+                        }
+                        WinitWindowEvent::Focused(focused)
+                    }
+                    WindowEvent::DroppedFile(path_buf) => WinitWindowEvent::DroppedFile(path_buf),
+                    WindowEvent::HoveredFile(path_buf) => WinitWindowEvent::HoveredFile(path_buf),
+                    WindowEvent::HoveredFileCancelled => WinitWindowEvent::HoveredFileCancelled,
+                    WindowEvent::Moved(position) => WinitWindowEvent::Moved(position),
+                    WindowEvent::ThemeChanged(theme) => {
+                        WinitWindowEvent::ThemeChanged(converters::convert_theme(theme))
+                    }
+                    _ => WinitWindowEvent::None,
+                };
+
+                match safe_area {
+                    Some(insets) => {
+                        out.push(WinitEvent::WindowEvent(e, winit_window_id));
+                        WinitEvent::WindowEvent(
+                            WinitWindowEvent::SafeAreaChanged(insets),
+                            winit_window_id,
+                        )
+                    }
+                    None => WinitEvent::WindowEvent(e, winit_window_id),
+                }
+            }
+            event::Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => WinitEvent::MouseMotion(MouseMotion {
+                delta: Vec2::new(delta.0 as f32, delta.1 as f32),
+            }),
+            event::Event::DeviceEvent { device_id, event } => {
+                let e = match event {
+                    DeviceEvent::Added => WinitDeviceEvent::Added(self.device_ids.get(device_id)),
+                    DeviceEvent::Removed => {
+                        WinitDeviceEvent::Removed(self.device_ids.remove(device_id))
+                    }
+                    DeviceEvent::Button { button, state } => {
+                        WinitDeviceEvent::Button(RawMouseButtonInput {
+                            device: self.device_ids.get(device_id),
+                            button,
+                            state: converters::convert_element_state(state),
+                        })
+                    }
+                    DeviceEvent::Key(input) => WinitDeviceEvent::Key(RawKeyboardInput {
+                        device: self.device_ids.get(device_id),
+                        scan_code: input.scancode,
+                        key_code: input
+                            .virtual_keycode
+                            .map(converters::convert_virtual_key_code),
+                        state: converters::convert_element_state(input.state),
+                    }),
+                    DeviceEvent::MouseWheel { delta } => {
+                        let wheel = converters::convert_mouse_scroll_delta(delta);
+                        WinitDeviceEvent::MouseWheel(RawMouseWheel {
+                            device: self.device_ids.get(device_id),
+                            unit: wheel.unit,
+                            x: wheel.x,
+                            y: wheel.y,
+                        })
+                    }
+                    _ => WinitDeviceEvent::None,
+                };
+                WinitEvent::DeviceEvent(e)
+            }
+            event::Event::RedrawRequested(winit_window_id) => {
+                WinitEvent::WindowEvent(WinitWindowEvent::RedrawRequested, winit_window_id)
+            }
+            event::Event::Suspended => WinitEvent::Lifecycle(ApplicationLifecycle::Suspended),
+            event::Event::Resumed => WinitEvent::Lifecycle(ApplicationLifecycle::Resumed),
+            event::Event::MainEventsCleared => WinitEvent::MainEventsCleared(
+                event_loop as *const EventLoopWindowTarget<()> as usize,
+            ),
+            _ => WinitEvent::None,
+        };
+        out.push(e);
+    }
+}
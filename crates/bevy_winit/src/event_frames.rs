@@ -0,0 +1,172 @@
+use crate::TextPasted;
+use bevy_app::{EventId, Events, ManualEventReader};
+use bevy_ecs::{component::Component, world::World};
+use bevy_input::{
+    gamepad::GamepadEventRaw,
+    keyboard::{KeyboardInput, KeyboardModifiers},
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::{PenInput, TouchInput},
+};
+use bevy_utils::HashMap;
+use bevy_window::{
+    CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, ReceivedCharacter,
+    WindowCloseRequested, WindowFocused, WindowMoved, WindowResized, WindowScaleFactorChanged,
+};
+use std::{any::TypeId, collections::VecDeque, ops::RangeInclusive};
+
+/// How many batches of events are remembered per event type. Events only live for two updates,
+/// and each update records up to two batches.
+const REMEMBERED_BATCHES: usize = 8;
+
+/// Tags events with the index of the frame they were dispatched in, for replay, debugging and
+/// network code that has to correlate input with simulation frames.
+///
+/// This is a parallel resource to the [Events] resources: read events with
+/// [EventReader::iter_with_id](bevy_app::EventReader::iter_with_id) and look up their frame
+/// with [frame_of](Self::frame_of). Events injected by the runner between two updates belong to
+/// the frame of the following update. The input and window events of the runner are tracked by
+/// default, others can be added with [track](Self::track).
+#[derive(Default)]
+pub struct EventFrames {
+    current_frame: u64,
+    /// The first and last event id of each batch, with the frame of the batch.
+    batches: HashMap<TypeId, VecDeque<(RangeInclusive<usize>, u64)>>,
+    trackers: Vec<Box<dyn EventFrameTracker>>,
+}
+
+impl EventFrames {
+    /// Tracks the input and window events that the winit runner dispatches.
+    pub(crate) fn with_winit_events() -> Self {
+        let mut event_frames = EventFrames::default();
+        event_frames.track::<KeyboardInput>();
+        event_frames.track::<KeyboardModifiers>();
+        event_frames.track::<ReceivedCharacter>();
+        event_frames.track::<TextPasted>();
+        event_frames.track::<MouseButtonInput>();
+        event_frames.track::<MouseMotion>();
+        event_frames.track::<MouseWheel>();
+        event_frames.track::<CursorMoved>();
+        event_frames.track::<CursorEntered>();
+        event_frames.track::<CursorLeft>();
+        event_frames.track::<TouchInput>();
+        event_frames.track::<PenInput>();
+        event_frames.track::<GamepadEventRaw>();
+        event_frames.track::<FileDragAndDrop>();
+        event_frames.track::<WindowResized>();
+        event_frames.track::<WindowMoved>();
+        event_frames.track::<WindowFocused>();
+        event_frames.track::<WindowScaleFactorChanged>();
+        event_frames.track::<WindowCloseRequested>();
+        event_frames
+    }
+
+    /// Starts tagging events of type `T`. Events sent before are not tagged.
+    pub fn track<T: Component>(&mut self) {
+        let type_id = TypeId::of::<T>();
+        if self.batches.contains_key(&type_id) {
+            return;
+        }
+        self.batches.insert(type_id, VecDeque::new());
+        self.trackers
+            .push(Box::new(TypedTracker::<T> { reader: None }));
+    }
+
+    /// The index of the frame that is currently being updated, or the next one between updates.
+    #[inline]
+    pub fn current_frame(&self) -> u64 {
+        self.current_frame
+    }
+
+    /// The frame the event was dispatched in, if its type is tracked and it is recent enough.
+    pub fn frame_of<T: Component>(&self, id: EventId<T>) -> Option<u64> {
+        self.batches
+            .get(&TypeId::of::<T>())?
+            .iter()
+            .find(|(ids, _)| ids.contains(&id.id))
+            .map(|(_, frame)| *frame)
+    }
+
+    /// Tags the events of the tracked types that were sent since the previous call with `frame`.
+    pub(crate) fn record(world: &mut World, frame: u64) {
+        let mut event_frames = match world.remove_resource::<EventFrames>() {
+            Some(event_frames) => event_frames,
+            None => return,
+        };
+        event_frames.current_frame = frame;
+        for tracker in event_frames.trackers.iter_mut() {
+            if let Some(ids) = tracker.new_events(world) {
+                let batches = event_frames.batches.get_mut(&tracker.type_id()).unwrap();
+                if batches.len() == REMEMBERED_BATCHES {
+                    batches.pop_front();
+                }
+                batches.push_back((ids, frame));
+            }
+        }
+        world.insert_resource(event_frames);
+    }
+}
+
+trait EventFrameTracker: Send + Sync {
+    fn type_id(&self) -> TypeId;
+
+    /// The ids of the events sent since the previous call, if any.
+    fn new_events(&mut self, world: &World) -> Option<RangeInclusive<usize>>;
+}
+
+struct TypedTracker<T> {
+    reader: Option<ManualEventReader<T>>,
+}
+
+impl<T: Component> EventFrameTracker for TypedTracker<T> {
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    fn new_events(&mut self, world: &World) -> Option<RangeInclusive<usize>> {
+        let events = world.get_resource::<Events<T>>()?;
+        let reader = self
+            .reader
+            .get_or_insert_with(|| events.get_reader_current());
+        let mut ids = reader.iter_with_id(events).map(|(_, id)| id.id);
+        let first = ids.next()?;
+        let last = ids.next_back().unwrap_or(first);
+        Some(first..=last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_tagged_with_their_frame() {
+        let mut world = World::default();
+        world.insert_resource(Events::<u32>::default());
+        let mut event_frames = EventFrames::default();
+        event_frames.track::<u32>();
+        world.insert_resource(event_frames);
+        EventFrames::record(&mut world, 0);
+
+        let mut events = world.get_resource_mut::<Events<u32>>().unwrap();
+        events.send(1);
+        events.send(2);
+        EventFrames::record(&mut world, 1);
+        EventFrames::record(&mut world, 2);
+        world.get_resource_mut::<Events<u32>>().unwrap().send(3);
+        EventFrames::record(&mut world, 3);
+        world.get_resource_mut::<Events<u32>>().unwrap().send(4);
+
+        let events = world.get_resource::<Events<u32>>().unwrap();
+        let event_frames = world.get_resource::<EventFrames>().unwrap();
+        let frames = events
+            .get_reader()
+            .iter_with_id(events)
+            .map(|(event, id)| (*event, event_frames.frame_of(id)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            vec![(1, Some(1)), (2, Some(1)), (3, Some(3)), (4, None)]
+        );
+        assert_eq!(event_frames.current_frame(), 3);
+    }
+}
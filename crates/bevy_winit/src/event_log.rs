@@ -0,0 +1,160 @@
+use bevy_app::{AppBuilder, CoreStage, EventReader, Plugin};
+use bevy_ecs::system::{IntoSystem, Res, ResMut};
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    Input,
+};
+use bevy_log::info;
+use bevy_utils::Instant;
+use bevy_window::{
+    CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, ReceivedCharacter,
+    WindowCloseRequested, WindowFocused, WindowId, WindowMoved, WindowResized,
+    WindowScaleFactorChanged,
+};
+use std::fmt::Debug;
+
+/// An App Plugin that logs the input and window events dispatched from the winit thread, with
+/// the time since startup and the id of the window they belong to.
+///
+/// Logging is toggled at runtime by pressing all keys of `toggle_chord` together.
+pub struct EventLogPlugin {
+    pub enabled: bool,
+    pub toggle_chord: Vec<KeyCode>,
+}
+
+impl Default for EventLogPlugin {
+    fn default() -> Self {
+        EventLogPlugin {
+            enabled: false,
+            toggle_chord: vec![KeyCode::LControl, KeyCode::LShift, KeyCode::F12],
+        }
+    }
+}
+
+/// State used by the [EventLogPlugin]
+pub struct EventLogState {
+    pub enabled: bool,
+    pub toggle_chord: Vec<KeyCode>,
+    started: Instant,
+}
+
+impl EventLogState {
+    fn log(&self, window: Option<WindowId>, event: &impl Debug) {
+        if !self.enabled {
+            return;
+        }
+        let elapsed = self.started.elapsed().as_secs_f64();
+        match window {
+            Some(window) => {
+                info!(target: "bevy event log", "[{:>10.3}s] {}: {:?}", elapsed, window, event)
+            }
+            None => info!(target: "bevy event log", "[{:>10.3}s] -: {:?}", elapsed, event),
+        }
+    }
+}
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(EventLogState {
+            enabled: self.enabled,
+            toggle_chord: self.toggle_chord.clone(),
+            started: Instant::now(),
+        })
+        .add_system_to_stage(CoreStage::PreUpdate, toggle_event_log_system.system())
+        .add_system_to_stage(CoreStage::PostUpdate, log_input_events_system.system())
+        .add_system_to_stage(CoreStage::PostUpdate, log_window_events_system.system());
+    }
+}
+
+pub fn toggle_event_log_system(mut state: ResMut<EventLogState>, keys: Res<Input<KeyCode>>) {
+    let chord = &state.toggle_chord;
+    if !chord.is_empty()
+        && chord.iter().all(|key| keys.pressed(*key))
+        && chord.iter().any(|key| keys.just_pressed(*key))
+    {
+        state.enabled = !state.enabled;
+        info!(
+            target: "bevy event log",
+            "Event log {}",
+            if state.enabled { "enabled" } else { "disabled" }
+        );
+    }
+}
+
+pub fn log_input_events_system(
+    state: Res<EventLogState>,
+    mut keyboard_input_events: EventReader<KeyboardInput>,
+    mut mouse_button_input_events: EventReader<MouseButtonInput>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut touch_input_events: EventReader<TouchInput>,
+    mut received_character_events: EventReader<ReceivedCharacter>,
+) {
+    for event in keyboard_input_events.iter() {
+        state.log(None, event);
+    }
+    for event in mouse_button_input_events.iter() {
+        state.log(None, event);
+    }
+    for event in mouse_motion_events.iter() {
+        state.log(None, event);
+    }
+    for event in mouse_wheel_events.iter() {
+        state.log(None, event);
+    }
+    for event in touch_input_events.iter() {
+        state.log(None, event);
+    }
+    for event in received_character_events.iter() {
+        state.log(Some(event.id), event);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn log_window_events_system(
+    state: Res<EventLogState>,
+    mut cursor_moved_events: EventReader<CursorMoved>,
+    mut cursor_entered_events: EventReader<CursorEntered>,
+    mut cursor_left_events: EventReader<CursorLeft>,
+    mut window_resized_events: EventReader<WindowResized>,
+    mut window_moved_events: EventReader<WindowMoved>,
+    mut window_focused_events: EventReader<WindowFocused>,
+    mut window_close_requested_events: EventReader<WindowCloseRequested>,
+    mut window_scale_factor_changed_events: EventReader<WindowScaleFactorChanged>,
+    mut file_drag_and_drop_events: EventReader<FileDragAndDrop>,
+) {
+    for event in cursor_moved_events.iter() {
+        state.log(Some(event.id), event);
+    }
+    for event in cursor_entered_events.iter() {
+        state.log(Some(event.id), event);
+    }
+    for event in cursor_left_events.iter() {
+        state.log(Some(event.id), event);
+    }
+    for event in window_resized_events.iter() {
+        state.log(Some(event.id), event);
+    }
+    for event in window_moved_events.iter() {
+        state.log(Some(event.id), event);
+    }
+    for event in window_focused_events.iter() {
+        state.log(Some(event.id), event);
+    }
+    for event in window_close_requested_events.iter() {
+        state.log(Some(event.id), event);
+    }
+    for event in window_scale_factor_changed_events.iter() {
+        state.log(Some(event.id), event);
+    }
+    for event in file_drag_and_drop_events.iter() {
+        let id = match event {
+            FileDragAndDrop::DroppedFile { id, .. }
+            | FileDragAndDrop::HoveredFile { id, .. }
+            | FileDragAndDrop::HoveredFileCancelled { id } => *id,
+        };
+        state.log(Some(id), event);
+    }
+}
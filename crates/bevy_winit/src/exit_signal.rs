@@ -0,0 +1,80 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Tells the winit thread to leave its event loop.
+///
+/// Unlike a channel, the request cannot be lost, consumed by the wrong event or block the
+/// sender: it is a flag that the winit thread checks on every event, and only the first
+/// request reports that the event loop still has to be woken up.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExitSignal {
+    requested: Arc<AtomicBool>,
+}
+
+impl ExitSignal {
+    /// Requests the exit. Returns `true` only for the first request.
+    pub fn request(&self) -> bool {
+        !self.requested.swap(true, Ordering::AcqRel)
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExitSignal;
+    use std::{sync::mpsc, thread};
+
+    #[test]
+    fn exit_is_requested_exactly_once() {
+        let signal = ExitSignal::default();
+        let first_requests = (0..8)
+            .map(|_| {
+                let signal = signal.clone();
+                thread::spawn(move || signal.request())
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|first| *first)
+            .count();
+        assert_eq!(first_requests, 1);
+        assert!(signal.is_requested());
+        assert!(!signal.request());
+    }
+
+    #[test]
+    fn exit_during_heavy_event_traffic() {
+        let signal = ExitSignal::default();
+        let (sender, receiver) = mpsc::channel::<u64>();
+
+        // stands in for the winit thread, which sends events until it notices the exit request
+        let event_thread = {
+            let signal = signal.clone();
+            thread::spawn(move || {
+                let mut sent = 0;
+                while !signal.is_requested() {
+                    if sender.send(sent).is_err() {
+                        break;
+                    }
+                    sent += 1;
+                }
+                sent
+            })
+        };
+
+        for _ in 0..10_000 {
+            receiver.recv().unwrap();
+        }
+        assert!(signal.request());
+        assert!(!signal.request());
+
+        let sent = event_thread.join().unwrap();
+        assert!(sent >= 10_000);
+        assert_eq!(receiver.iter().count() as u64, sent - 10_000);
+    }
+}
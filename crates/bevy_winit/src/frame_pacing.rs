@@ -0,0 +1,56 @@
+use bevy_utils::{Duration, Instant};
+use std::hint;
+
+/// How much of a wait is spent spinning instead of sleeping, since sleeping may overshoot by
+/// about a scheduler tick.
+const SPIN_DURATION: Duration = Duration::from_millis(2);
+
+/// When the next update of an app that was last updated at `last_update` is allowed by
+/// [WinitConfig::frame_rate_limit](crate::WinitConfig::frame_rate_limit).
+pub(crate) fn next_frame_at(
+    frame_rate_limit: Option<f64>,
+    last_update: Instant,
+) -> Option<Instant> {
+    let frame_rate_limit = frame_rate_limit.filter(|limit| *limit > 0.0)?;
+    Some(last_update + Duration::from_secs_f64(1.0 / frame_rate_limit))
+}
+
+/// How long the app loop may wait for events before it has to start spinning towards
+/// `deadline`.
+pub(crate) fn time_until_spin(deadline: Instant) -> Duration {
+    deadline
+        .saturating_duration_since(Instant::now())
+        .saturating_sub(SPIN_DURATION)
+}
+
+/// Spins until `deadline` if it is close, so it is not overshot. Returns whether `deadline`
+/// has been reached, otherwise the app loop should wait for events in the meantime.
+pub(crate) fn spin_until(deadline: Instant) -> bool {
+    if time_until_spin(deadline) > Duration::ZERO {
+        return false;
+    }
+    while Instant::now() < deadline {
+        hint::spin_loop();
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_are_limited() {
+        let last_update = Instant::now();
+        assert_eq!(next_frame_at(None, last_update), None);
+        assert_eq!(next_frame_at(Some(0.0), last_update), None);
+        let deadline = next_frame_at(Some(100.0), last_update).unwrap();
+        assert_eq!(deadline - last_update, Duration::from_millis(10));
+
+        assert!(!spin_until(deadline));
+        assert!(time_until_spin(deadline) <= Duration::from_millis(8));
+        std::thread::sleep(time_until_spin(deadline));
+        assert!(spin_until(deadline));
+        assert!(Instant::now() >= deadline);
+    }
+}
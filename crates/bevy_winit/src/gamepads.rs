@@ -0,0 +1,189 @@
+use bevy_ecs::world::World;
+use bevy_input::gamepad::{Gamepad, GamepadEventRaw, GamepadEventType};
+use bevy_utils::{Duration, HashSet};
+
+/// How often gamepads are polled while the event loop would otherwise wait for window events.
+pub(crate) const GAMEPAD_POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Polls gamepads on the winit thread, so that gamepad input wakes reactive apps like any
+/// other input.
+pub(crate) struct GamepadPoller {
+    inner: platform::Poller,
+    connected: ConnectedGamepads,
+}
+
+impl GamepadPoller {
+    pub fn new(enabled: bool) -> Self {
+        GamepadPoller {
+            inner: platform::Poller::new(enabled),
+            connected: ConnectedGamepads::default(),
+        }
+    }
+
+    /// Whether gamepads are polled, whenever the event loop wakes up.
+    pub fn is_active(&self) -> bool {
+        self.inner.is_active()
+    }
+
+    /// Whether the event loop has to wake up regularly to poll gamepads, which is only the case
+    /// while any are connected. Newly connected gamepads are noticed with the next event.
+    pub fn needs_regular_polls(&self) -> bool {
+        self.is_active() && !self.connected.is_empty()
+    }
+
+    /// Gamepad events since the last call. The first call reports already connected gamepads.
+    pub fn poll(&mut self) -> Vec<GamepadEventRaw> {
+        let events = self.inner.poll();
+        self.connected.track(&events);
+        events
+    }
+}
+
+/// Whether one of `worlds` polls gamepads itself with the `GilrsPlugin`, in which case the
+/// winit thread must not poll them as well, or every gamepad event would be reported twice.
+pub(crate) fn polled_by_apps<'a>(worlds: impl IntoIterator<Item = &'a World>) -> bool {
+    worlds.into_iter().any(platform::polled_by_app)
+}
+
+/// The gamepads that are connected according to their events.
+#[derive(Debug, Default)]
+struct ConnectedGamepads(HashSet<Gamepad>);
+
+impl ConnectedGamepads {
+    fn track(&mut self, events: &[GamepadEventRaw]) {
+        for GamepadEventRaw(gamepad, event_type) in events {
+            match event_type {
+                GamepadEventType::Connected => {
+                    self.0.insert(*gamepad);
+                }
+                GamepadEventType::Disconnected => {
+                    self.0.remove(gamepad);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(feature = "gilrs")]
+mod platform {
+    use bevy_ecs::world::World;
+    use bevy_gilrs::Gilrs;
+    use bevy_input::gamepad::GamepadEventRaw;
+    use bevy_utils::tracing::{error, warn};
+
+    /// Whether the app of `world` polls gamepads with the `GilrsPlugin`.
+    pub fn polled_by_app(world: &World) -> bool {
+        let polled = world.get_non_send_resource::<Gilrs>().is_some();
+        if polled {
+            warn!("WinitConfig::poll_gamepads is ignored, because GilrsPlugin is added as well");
+        }
+        polled
+    }
+
+    pub struct Poller {
+        gilrs: Option<Gilrs>,
+        reported_connected: bool,
+    }
+
+    impl Poller {
+        pub fn new(enabled: bool) -> Self {
+            let gilrs = if enabled {
+                match bevy_gilrs::build_gilrs() {
+                    Ok(gilrs) => Some(gilrs),
+                    Err(err) => {
+                        error!("Failed to start Gilrs. {}", err);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            Poller {
+                gilrs,
+                reported_connected: false,
+            }
+        }
+
+        pub fn is_active(&self) -> bool {
+            self.gilrs.is_some()
+        }
+
+        pub fn poll(&mut self) -> Vec<GamepadEventRaw> {
+            let gilrs = match self.gilrs.as_mut() {
+                Some(gilrs) => gilrs,
+                None => return Vec::new(),
+            };
+            let mut events = Vec::new();
+            if !self.reported_connected {
+                self.reported_connected = true;
+                events.extend(bevy_gilrs::connected_gamepads(gilrs));
+            }
+            while let Some(gilrs_event) = gilrs.next_event() {
+                events.extend(bevy_gilrs::convert_event(&gilrs_event));
+            }
+            gilrs.inc();
+            events
+        }
+    }
+}
+
+#[cfg(not(feature = "gilrs"))]
+mod platform {
+    use bevy_ecs::world::World;
+    use bevy_input::gamepad::GamepadEventRaw;
+    use bevy_utils::tracing::warn;
+
+    pub fn polled_by_app(_world: &World) -> bool {
+        false
+    }
+
+    pub struct Poller;
+
+    impl Poller {
+        pub fn new(enabled: bool) -> Self {
+            if enabled {
+                warn!("WinitConfig::poll_gamepads requires the `gilrs` feature of bevy_winit");
+            }
+            Poller
+        }
+
+        pub fn is_active(&self) -> bool {
+            false
+        }
+
+        pub fn poll(&mut self) -> Vec<GamepadEventRaw> {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_connected_gamepads() {
+        let mut connected = ConnectedGamepads::default();
+        connected.track(&[
+            GamepadEventRaw(Gamepad(0), GamepadEventType::Connected),
+            GamepadEventRaw(Gamepad(1), GamepadEventType::Connected),
+        ]);
+        connected.track(&[GamepadEventRaw(Gamepad(0), GamepadEventType::Disconnected)]);
+        assert!(!connected.is_empty());
+        connected.track(&[GamepadEventRaw(Gamepad(1), GamepadEventType::Disconnected)]);
+        assert!(connected.is_empty());
+    }
+
+    #[test]
+    fn disabled_poller_does_not_wake_the_event_loop() {
+        let mut poller = GamepadPoller::new(false);
+        assert!(!poller.is_active());
+        assert!(poller.poll().is_empty());
+        assert!(!poller.needs_regular_polls());
+    }
+}
@@ -0,0 +1,344 @@
+use crate::{converters, EventFrames};
+use bevy_app::{App, AppBuilder, AppExit, CoreStage, Events, ManualEventReader, Plugin};
+use bevy_ecs::{system::IntoExclusiveSystem, world::World};
+use bevy_input::{
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseWheel},
+};
+use bevy_math::Vec2;
+use bevy_utils::{Duration, HashSet, Instant};
+use bevy_window::{
+    apply_window_commands, create_windows, poll_window_events, CreateWindow, CursorMoved,
+    ReceivedCharacter, Window, WindowBackend, WindowBackendEvent, WindowCloseRequested,
+    WindowCommand, WindowCommandError, WindowCoordinateConvention, WindowDescriptor, WindowId,
+    WindowResized, Windows,
+};
+use std::{collections::VecDeque, thread};
+use winit::dpi::LogicalPosition;
+
+/// Runs the app without a windowing system, for tests in CI and dedicated servers.
+///
+/// Windows are virtual: they get a [Window] entry of the requested size, and their
+/// [WindowCommand]s are consumed, but nothing is shown. Input can be scripted with
+/// [HeadlessInput]. Use it in place of the [WinitPlugin](crate::WinitPlugin).
+#[derive(Default)]
+pub struct HeadlessWinitPlugin;
+
+impl Plugin for HeadlessWinitPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<HeadlessWindows>()
+            .init_resource::<HeadlessConfig>()
+            .init_resource::<HeadlessInput>()
+            .insert_resource(EventFrames::with_winit_events())
+            .set_runner(headless_runner)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                apply_window_commands::<HeadlessWindows>.exclusive_system(),
+            );
+    }
+}
+
+/// Settings of the [HeadlessWinitPlugin].
+#[derive(Debug, Clone)]
+pub struct HeadlessConfig {
+    /// The scale factor the virtual windows report, like that of a monitor.
+    pub scale_factor: f64,
+    /// The time between two updates, `None` updates as fast as possible.
+    pub frame_interval: Option<Duration>,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        HeadlessConfig {
+            scale_factor: 1.0,
+            frame_interval: None,
+        }
+    }
+}
+
+/// An input event for [HeadlessInput], sent as if a windowing backend had reported it.
+#[derive(Debug, Clone)]
+pub enum HeadlessInputEvent {
+    Keyboard(KeyboardInput),
+    Character {
+        id: WindowId,
+        char: char,
+    },
+    MouseButton(MouseButtonInput),
+    MouseWheel(MouseWheel),
+    /// Moves the cursor to `position` in logical pixels, with the origin at the top left like
+    /// windowing backends report it. Converted to the [WindowCoordinateConvention] of the app.
+    CursorMoved {
+        id: WindowId,
+        position: Vec2,
+    },
+    /// Resizes the window as if the user had, in physical pixels.
+    Resized {
+        id: WindowId,
+        width: u32,
+        height: u32,
+    },
+    CloseRequested {
+        id: WindowId,
+    },
+}
+
+/// Scripted input for the [HeadlessWinitPlugin]. Events are sent right before the update of
+/// their frame.
+#[derive(Debug, Default)]
+pub struct HeadlessInput {
+    /// Events with the frame they are due in, in the order they were queued.
+    queue: VecDeque<(u64, HeadlessInputEvent)>,
+}
+
+impl HeadlessInput {
+    /// Sends `event` before the next update.
+    pub fn send(&mut self, event: HeadlessInputEvent) {
+        self.send_at(0, event);
+    }
+
+    /// Sends `event` before the update of `frame`, or before the next update if that has
+    /// already passed. Frames are counted from 0, like in [EventFrames].
+    pub fn send_at(&mut self, frame: u64, event: HeadlessInputEvent) {
+        self.queue.push_back((frame, event));
+    }
+
+    fn take_due(&mut self, frame: u64) -> Vec<HeadlessInputEvent> {
+        let (due, pending) = self
+            .queue
+            .drain(..)
+            .partition::<VecDeque<_>, _>(|(due_frame, _)| *due_frame <= frame);
+        self.queue = pending;
+        due.into_iter().map(|(_, event)| event).collect()
+    }
+}
+
+/// The virtual windows of the [HeadlessWinitPlugin].
+#[derive(Debug)]
+pub struct HeadlessWindows {
+    windows: HashSet<WindowId>,
+    scale_factor: f64,
+    /// Resizes requested through [WindowCommand::SetResolution], polled before the next update
+    /// like a resize reported by a real backend.
+    pending_resizes: Vec<WindowBackendEvent>,
+}
+
+impl Default for HeadlessWindows {
+    fn default() -> Self {
+        HeadlessWindows {
+            windows: HashSet::default(),
+            scale_factor: 1.0,
+            pending_resizes: Vec::new(),
+        }
+    }
+}
+
+impl WindowBackend for HeadlessWindows {
+    type Target = ();
+
+    fn create_window(
+        &mut self,
+        _target: &(),
+        id: WindowId,
+        descriptor: &WindowDescriptor,
+    ) -> Result<Window, WindowCommandError> {
+        let scale_factor = descriptor
+            .scale_factor_override
+            .unwrap_or(self.scale_factor);
+        self.windows.insert(id);
+        Ok(Window::new(
+            id,
+            descriptor,
+            (descriptor.width as f64 * scale_factor) as u32,
+            (descriptor.height as f64 * scale_factor) as u32,
+            self.scale_factor,
+            None,
+        ))
+    }
+
+    fn apply_command(
+        &mut self,
+        id: WindowId,
+        command: &WindowCommand,
+    ) -> Result<(), WindowCommandError> {
+        if !self.windows.contains(&id) {
+            return Err(WindowCommandError::UnknownWindow);
+        }
+        if let WindowCommand::SetResolution {
+            logical_resolution: (width, height),
+            scale_factor,
+        } = *command
+        {
+            self.pending_resizes.push(WindowBackendEvent::Resized {
+                id,
+                width: (width as f64 * scale_factor) as u32,
+                height: (height as f64 * scale_factor) as u32,
+            });
+        }
+        // everything else only changes the state of the `Window`, which its setters already did
+        Ok(())
+    }
+
+    fn close_window(&mut self, id: WindowId) {
+        self.windows.remove(&id);
+    }
+
+    fn has_window(&self, id: WindowId) -> bool {
+        self.windows.contains(&id)
+    }
+
+    fn poll_events(&mut self, events: &mut Vec<WindowBackendEvent>) {
+        events.append(&mut self.pending_resizes);
+    }
+}
+
+/// The runner of the [HeadlessWinitPlugin]. Updates the app in a loop until it sends
+/// [AppExit].
+pub fn headless_runner(mut app: App) {
+    let config = app
+        .world
+        .get_resource::<HeadlessConfig>()
+        .cloned()
+        .unwrap_or_default();
+    if let Some(mut headless_windows) = app.world.get_resource_mut::<HeadlessWindows>() {
+        headless_windows.scale_factor = config.scale_factor;
+    }
+    let mut create_window_event_reader = ManualEventReader::<CreateWindow>::default();
+    let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
+
+    for frame in 0.. {
+        let started_at = Instant::now();
+        create_windows::<HeadlessWindows>(
+            &mut app.world,
+            &(),
+            &mut create_window_event_reader,
+            frame,
+        );
+        poll_window_events::<HeadlessWindows>(&mut app.world);
+        send_headless_input(&mut app.world, frame);
+
+        EventFrames::record(&mut app.world, frame);
+        app.update();
+        EventFrames::record(&mut app.world, frame);
+
+        if let Some(app_exit_events) = app.world.get_resource::<Events<AppExit>>() {
+            if app_exit_event_reader
+                .iter(app_exit_events)
+                .next_back()
+                .is_some()
+            {
+                break;
+            }
+        }
+        if let Some(frame_interval) = config.frame_interval {
+            thread::sleep(frame_interval.saturating_sub(started_at.elapsed()));
+        }
+    }
+}
+
+fn send_headless_input(world: &mut World, frame: u64) {
+    let events = world
+        .get_resource_mut::<HeadlessInput>()
+        .unwrap()
+        .take_due(frame);
+    for event in events {
+        match event {
+            HeadlessInputEvent::Keyboard(input) => send(world, input),
+            HeadlessInputEvent::Character { id, char } => {
+                send(world, ReceivedCharacter { id, char })
+            }
+            HeadlessInputEvent::MouseButton(input) => send(world, input),
+            HeadlessInputEvent::MouseWheel(input) => send(world, input),
+            HeadlessInputEvent::CursorMoved { id, position } => {
+                let convention = world
+                    .get_resource::<WindowCoordinateConvention>()
+                    .map_or_else(Default::default, |convention| *convention);
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                let position = windows.get_mut(id).map(|window| {
+                    let position = converters::convert_window_position(
+                        LogicalPosition::new(position.x, position.y),
+                        window.height(),
+                        convention,
+                    );
+                    window.update_cursor_position_from_backend(Some(position));
+                    position
+                });
+                if let Some(position) = position {
+                    send(world, CursorMoved { id, position });
+                }
+            }
+            HeadlessInputEvent::Resized { id, width, height } => {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                let size = windows.get_mut(id).map(|window| {
+                    window.update_actual_size_from_backend(width, height);
+                    (window.width(), window.height())
+                });
+                if let Some((width, height)) = size {
+                    send(world, WindowResized { id, width, height });
+                }
+            }
+            HeadlessInputEvent::CloseRequested { id } => send(world, WindowCloseRequested { id }),
+        }
+    }
+}
+
+fn send<T: bevy_ecs::component::Component>(world: &mut World, event: T) {
+    world.get_resource_mut::<Events<T>>().unwrap().send(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_is_sent_in_its_frame() {
+        let mut input = HeadlessInput::default();
+        let id = WindowId::primary();
+        input.send_at(2, HeadlessInputEvent::CloseRequested { id });
+        input.send(HeadlessInputEvent::Character { id, char: 'a' });
+
+        let due = input.take_due(0);
+        assert!(matches!(
+            due.as_slice(),
+            [HeadlessInputEvent::Character { char: 'a', .. }]
+        ));
+        assert!(input.take_due(1).is_empty());
+        assert_eq!(input.take_due(2).len(), 1);
+    }
+
+    #[test]
+    fn cursor_positions_follow_the_coordinate_convention() {
+        let id = WindowId::primary();
+        let mut world = World::default();
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            id,
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            1.0,
+            None,
+        ));
+        world.insert_resource(windows);
+        world.insert_resource(WindowCoordinateConvention::BottomLeft);
+        world.insert_resource(Events::<CursorMoved>::default());
+        let mut input = HeadlessInput::default();
+        input.send(HeadlessInputEvent::CursorMoved {
+            id,
+            position: Vec2::new(10.0, 20.0),
+        });
+        world.insert_resource(input);
+
+        send_headless_input(&mut world, 0);
+        let expected = Vec2::new(10.0, 700.0);
+        let windows = world.get_resource::<Windows>().unwrap();
+        assert_eq!(windows.get(id).unwrap().cursor_position(), Some(expected));
+        let cursor_moved_events = world.get_resource::<Events<CursorMoved>>().unwrap();
+        let moved = cursor_moved_events
+            .get_reader()
+            .iter(cursor_moved_events)
+            .map(|event| event.position)
+            .collect::<Vec<_>>();
+        assert_eq!(moved, [expected]);
+    }
+}
@@ -0,0 +1,72 @@
+//! Memory warnings on iOS, which winit 0.25 does not forward. UIKit posts them as notifications,
+//! so an observer is registered through the Objective-C runtime.
+
+use crate::objc::{
+    class_addMethod, objc_allocateClassPair, objc_getClass, objc_msgSend, objc_registerClassPair,
+    selector, send_id, Id, Sel,
+};
+use std::{
+    ffi::c_void,
+    mem,
+    os::raw::c_char,
+    ptr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+};
+
+#[link(name = "UIKit", kind = "framework")]
+extern "C" {
+    static UIApplicationDidReceiveMemoryWarningNotification: Id;
+}
+
+static MEMORY_WARNING: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn did_receive_memory_warning(_this: Id, _cmd: Sel, _notification: Id) {
+    MEMORY_WARNING.store(true, Ordering::Relaxed);
+}
+
+/// Starts observing memory warnings. The notifications are posted on the main thread, where the
+/// event loop runs as well.
+pub(crate) fn observe_memory_warnings() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| unsafe {
+        let add_observer: unsafe extern "C" fn(Id, Sel, Id, Sel, Id, Id) =
+            mem::transmute(objc_msgSend as unsafe extern "C" fn());
+
+        let class = objc_allocateClassPair(
+            objc_getClass(b"NSObject\0".as_ptr() as *const c_char),
+            b"BevyMemoryWarningObserver\0".as_ptr() as *const c_char,
+            0,
+        );
+        let callback = selector(b"didReceiveMemoryWarning:\0");
+        class_addMethod(
+            class,
+            callback,
+            did_receive_memory_warning as *const c_void,
+            b"v@:@\0".as_ptr() as *const c_char,
+        );
+        objc_registerClassPair(class);
+
+        // the observer lives as long as the application
+        let observer = send_id(send_id(class, b"alloc\0"), b"init\0");
+        let center = send_id(
+            objc_getClass(b"NSNotificationCenter\0".as_ptr() as *const c_char),
+            b"defaultCenter\0",
+        );
+        add_observer(
+            center,
+            selector(b"addObserver:selector:name:object:\0"),
+            observer,
+            callback,
+            UIApplicationDidReceiveMemoryWarningNotification,
+            ptr::null_mut(),
+        );
+    });
+}
+
+/// Whether a memory warning was received since the previous call.
+pub(crate) fn take_memory_warning() -> bool {
+    MEMORY_WARNING.swap(false, Ordering::Relaxed)
+}
@@ -0,0 +1,125 @@
+use std::{
+    collections::{hash_map::RandomState, VecDeque},
+    hash::{BuildHasher, Hasher},
+};
+
+use crate::{JitterDistribution, SimulatedLatency};
+use bevy_utils::{Duration, Instant};
+
+/// Holds back events according to a [SimulatedLatency] until they are due.
+pub(crate) struct LatencySimulator<T> {
+    config: SimulatedLatency,
+    rng_state: u64,
+    queue: VecDeque<(Instant, T)>,
+}
+
+impl<T> LatencySimulator<T> {
+    pub fn new(config: SimulatedLatency) -> Self {
+        // only needs to differ between runs, not to be of any particular quality
+        let seed = RandomState::new().build_hasher().finish();
+        LatencySimulator {
+            config,
+            rng_state: seed | 1,
+            queue: VecDeque::new(),
+        }
+    }
+
+    pub fn push(&mut self, event: T, now: Instant) {
+        let mut release_at = now + self.config.latency + self.sample_jitter();
+        // never let an event overtake the one before it
+        if let Some((last_release_at, _)) = self.queue.back() {
+            release_at = release_at.max(*last_release_at);
+        }
+        self.queue.push_back((release_at, event));
+    }
+
+    /// The time at which the next held back event is due, if any.
+    pub fn next_release(&self) -> Option<Instant> {
+        self.queue.front().map(|(release_at, _)| *release_at)
+    }
+
+    pub fn pop_due(&mut self, now: Instant) -> Option<T> {
+        if self.next_release()? <= now {
+            self.queue.pop_front().map(|(_, event)| event)
+        } else {
+            None
+        }
+    }
+
+    fn sample_jitter(&mut self) -> Duration {
+        let jitter = self.config.jitter;
+        match self.config.distribution {
+            JitterDistribution::Uniform => jitter.mul_f64(self.next_f64()),
+            JitterDistribution::Spikes { probability } => {
+                if self.next_f64() < probability as f64 {
+                    jitter
+                } else {
+                    Duration::ZERO
+                }
+            }
+        }
+    }
+
+    /// xorshift64, returns a value in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 7;
+        self.rng_state ^= self.rng_state << 17;
+        (self.rng_state >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulator(latency_ms: u64, jitter_ms: u64) -> LatencySimulator<u32> {
+        LatencySimulator::new(SimulatedLatency {
+            latency: Duration::from_millis(latency_ms),
+            jitter: Duration::from_millis(jitter_ms),
+            distribution: JitterDistribution::Uniform,
+        })
+    }
+
+    #[test]
+    fn holds_events_back_until_due() {
+        let mut simulator = simulator(10, 0);
+        let now = Instant::now();
+        assert_eq!(simulator.next_release(), None);
+        simulator.push(1, now);
+        assert_eq!(
+            simulator.next_release(),
+            Some(now + Duration::from_millis(10))
+        );
+        assert_eq!(simulator.pop_due(now), None);
+        assert_eq!(simulator.pop_due(now + Duration::from_millis(10)), Some(1));
+        assert_eq!(simulator.pop_due(now + Duration::from_millis(10)), None);
+    }
+
+    #[test]
+    fn keeps_the_order_of_events() {
+        let mut simulator = simulator(5, 50);
+        let now = Instant::now();
+        for event in 0..100 {
+            simulator.push(event, now + Duration::from_micros(event as u64));
+        }
+        let later = now + Duration::from_secs(1);
+        let released = std::iter::from_fn(|| simulator.pop_due(later)).collect::<Vec<_>>();
+        assert_eq!(released, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let mut uniform = simulator(0, 20);
+        let mut spikes = LatencySimulator::<u32>::new(SimulatedLatency {
+            latency: Duration::ZERO,
+            jitter: Duration::from_millis(20),
+            distribution: JitterDistribution::Spikes { probability: 0.5 },
+        });
+        for _ in 0..1000 {
+            assert!(uniform.sample_jitter() < Duration::from_millis(20));
+            let spike = spikes.sample_jitter();
+            assert!(spike == Duration::ZERO || spike == Duration::from_millis(20));
+        }
+    }
+}
@@ -1,46 +1,139 @@
-mod converters;
+mod backend;
+mod clipboard;
+pub mod converters;
+mod cursor_lock;
+mod device_ids;
+mod diagnostics;
+mod error_dialog;
+mod event_buffers;
+mod event_converter;
+mod event_frames;
+mod event_log;
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+mod exit_signal;
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+mod frame_pacing;
+mod gamepads;
+mod headless;
+#[cfg(target_os = "ios")]
+mod ios;
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+mod latency;
+#[cfg(any(target_arch = "wasm32", target_os = "ios"))]
+mod main_thread;
+mod monitor_watch;
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+mod objc;
+mod raw_input;
+#[cfg(feature = "remote_input")]
+mod remote_input;
+mod safe_area;
+mod size_lock;
+mod startup_timings;
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+mod suspend_signal;
+mod system_settings;
+mod touch_ids;
+mod transport;
+mod unknown_windows;
+mod user_events;
 mod winit_config;
 mod winit_windows;
+mod worker;
 
 use std::{
+    iter, panic,
     path::PathBuf,
-    sync::{mpsc, Mutex},
+    sync::{mpsc, Arc},
+};
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
     thread,
 };
 
+pub use backend::*;
 use bevy_input::{
-    keyboard::KeyboardInput,
-    mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
-    touch::TouchInput,
+    device::{
+        DeviceConnectionEvent, DeviceId, RawKeyboardInput, RawMouseButtonInput, RawMouseWheel,
+    },
+    gamepad::GamepadEventRaw,
+    keyboard::{KeyboardInput, KeyboardModifiers},
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::PenInput,
+    ElementState,
 };
+pub use clipboard::{Clipboard, ClipboardChanged, TextPasted};
+pub use diagnostics::{WinitChannelStats, WinitDiagnosticsPlugin};
+pub use error_dialog::show_error_dialog;
+use event_buffers::{CoalescedWindowEvents, WindowEventBuffers};
+use event_converter::EventConverter;
+pub use event_frames::EventFrames;
+pub use event_log::*;
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+use exit_signal::ExitSignal;
+pub use headless::*;
+pub use raw_input::RawInputChannel;
+#[cfg(feature = "remote_input")]
+pub use remote_input::*;
+use startup_timings::StartupStep;
+pub use startup_timings::{WinitStartupCompleted, WinitStartupTimings};
+pub use transport::{
+    TransportEvent, TransportKeyboardInput, TransportModifiers, TransportMouseButton,
+    TransportMouseButtonInput, TransportMouseWheel, TransportTouchPhase, TransportWindowEvent,
+    WinitTransport, TRANSPORT_SCHEMA_VERSION,
+};
+use unknown_windows::UnknownWindowEvents;
+use user_events::WinitWaker;
+pub use user_events::{WinitUserEventPlugin, WinitUserEventSender};
 pub use winit_config::*;
 pub use winit_windows::*;
 
 use bevy_app::{App, AppBuilder, AppExit, CoreStage, Events, ManualEventReader, Plugin};
-use bevy_ecs::{system::IntoExclusiveSystem, world::World};
+use bevy_ecs::{
+    system::IntoExclusiveSystem,
+    world::{Mut, World},
+};
 use bevy_math::{ivec2, Vec2};
-use bevy_utils::tracing::{error, trace, warn};
+use bevy_utils::{
+    tracing::{info, trace, warn},
+    HashMap, Instant,
+};
 use bevy_window::{
-    CreateWindow, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, ReceivedCharacter,
-    WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCreated, WindowFocused,
-    WindowMoved, WindowResized, WindowScaleFactorChanged, Windows,
+    apply_window_commands, create_windows, ApplicationLifecycle, BackendErrorPolicy, CreateWindow,
+    CursorEntered, CursorGrabMode, CursorLeft, CursorMoved, CursorPredictor, FileDragAndDrop,
+    FocusedWindow, Monitor, MonitorModeChanged, Monitors, RawWindowHandles, ReceivedCharacter,
+    SafeAreaChanged, SafeAreaInsets, SurfaceChangeReason, SurfaceLost, SurfaceRecreated,
+    SystemInfo, SystemPreferences, SystemPreferencesChanged, TouchesCancelled,
+    WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCommandError, WindowCommandFailed,
+    WindowCoordinateConvention, WindowEventMask, WindowFocused, WindowMaximized, WindowMinimized,
+    WindowMoved, WindowRedrawRequested, WindowResized, WindowRestored, WindowScaleFactorChanged,
+    WindowStats, WindowTheme, WindowThemeChanged, Windows,
 };
 use winit::{
     dpi::{PhysicalPosition, PhysicalSize},
-    event::{self, DeviceEvent, Event, Touch, WindowEvent},
-    event_loop::{ControlFlow, EventLoop, EventLoopProxy, EventLoopWindowTarget},
+    event::{self, Touch, TouchPhase},
+    event_loop::{EventLoopProxy, EventLoopWindowTarget},
     window::WindowId,
 };
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+use {
+    bevy_utils::{tracing::error, Duration},
+    bevy_window::WindowClosed,
+    winit::{
+        event::Event,
+        event_loop::{ControlFlow, EventLoop},
+    },
+};
 
-use winit::dpi::LogicalSize;
-#[cfg(any(
-    target_os = "linux",
-    target_os = "dragonfly",
-    target_os = "freebsd",
-    target_os = "netbsd",
-    target_os = "openbsd"
-))]
-use winit::platform::unix::EventLoopExtUnix;
+/// The ratio between the old and the new scale factor of a window from which renderers are
+/// told to recreate its surface with a [SurfaceRecreated] event, rather than only resize it.
+const SURFACE_RECREATION_SCALE_RATIO: f64 = 1.5;
+
+/// How long the winit thread waits for the apps to handle a suspension before it lets the
+/// platform go on, so a stuck app cannot make the system consider the application hung.
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+const SUSPEND_TIMEOUT: Duration = Duration::from_secs(2);
 
 #[derive(Default)]
 pub struct WinitPlugin;
@@ -48,128 +141,109 @@ pub struct WinitPlugin;
 impl Plugin for WinitPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_resource::<WinitWindows>()
-            .set_runner(winit_runner_any_thread)
-            .add_system_to_stage(CoreStage::PostUpdate, change_window.exclusive_system());
+            .init_resource::<WinitChannelStats>()
+            .init_resource::<WinitTransport>()
+            .init_resource::<Arc<WinitWaker>>()
+            .insert_resource(WinitStartupTimings::new())
+            .add_event::<WinitStartupCompleted>()
+            .add_event::<WinitBackendFailed>()
+            .add_event::<RestartBackend>()
+            .add_event::<ClipboardChanged>()
+            .add_event::<TextPasted>()
+            .insert_resource(EventFrames::with_winit_events())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                apply_winit_window_commands.exclusive_system(),
+            );
+
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        app.set_runner(winit_runner_any_thread);
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )))]
+        app.set_runner(winit_runner);
     }
 }
 
-fn change_window(world: &mut World) {
+fn apply_winit_window_commands(world: &mut World) {
+    if let Some(convention) = world.get_resource::<WindowCoordinateConvention>().copied() {
+        world
+            .get_resource_mut::<WinitWindows>()
+            .unwrap()
+            .coordinate_convention = convention;
+    }
+    let despawned = world
+        .get_resource::<WinitWindows>()
+        .unwrap()
+        .windows_of_despawned_entities(world);
+    if !despawned.is_empty() {
+        let mut windows = world.get_resource_mut::<Windows>().unwrap();
+        for id in despawned {
+            if let Some(window) = windows.get_mut(id) {
+                window.close();
+            }
+        }
+    }
+    apply_window_commands::<WinitWindows>(world);
+
+    let despawned_entities = std::mem::take(
+        &mut world
+            .get_resource_mut::<WinitWindows>()
+            .unwrap()
+            .despawned_entities,
+    );
+    for entity in despawned_entities {
+        world.despawn(entity);
+    }
+
+    update_raw_window_handles(world);
+
     let world = world.cell();
-    let winit_windows = world.get_resource::<WinitWindows>().unwrap();
+    let mut winit_windows = world.get_resource_mut::<WinitWindows>().unwrap();
+    if winit_windows.geometry_refreshes.is_empty() {
+        return;
+    }
     let mut windows = world.get_resource_mut::<Windows>().unwrap();
+    for id in std::mem::take(&mut winit_windows.geometry_refreshes) {
+        if let Some(window) = windows.get_mut(id) {
+            winit_windows.update_geometry(window);
+        }
+    }
+}
 
-    for bevy_window in windows.iter_mut() {
-        let id = bevy_window.id();
-        for command in bevy_window.drain_commands() {
-            match command {
-                bevy_window::WindowCommand::SetWindowMode {
-                    mode,
-                    resolution: (width, height),
-                } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    match mode {
-                        bevy_window::WindowMode::BorderlessFullscreen => {
-                            window.set_fullscreen(Some(winit::window::Fullscreen::Borderless(None)))
-                        }
-                        bevy_window::WindowMode::Fullscreen { use_size } => window.set_fullscreen(
-                            Some(winit::window::Fullscreen::Exclusive(match use_size {
-                                true => get_fitting_videomode(
-                                    &window.current_monitor().unwrap(),
-                                    width,
-                                    height,
-                                ),
-                                false => get_best_videomode(&window.current_monitor().unwrap()),
-                            })),
-                        ),
-                        bevy_window::WindowMode::Windowed => window.set_fullscreen(None),
-                    }
-                }
-                bevy_window::WindowCommand::SetTitle { title } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_title(&title);
-                }
-                bevy_window::WindowCommand::SetScaleFactor { scale_factor } => {
-                    let mut window_dpi_changed_events = world
-                        .get_resource_mut::<Events<WindowScaleFactorChanged>>()
-                        .unwrap();
-                    window_dpi_changed_events.send(WindowScaleFactorChanged { id, scale_factor });
-                }
-                bevy_window::WindowCommand::SetResolution {
-                    logical_resolution: (width, height),
-                    scale_factor,
-                } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_inner_size(
-                        winit::dpi::LogicalSize::new(width, height)
-                            .to_physical::<f64>(scale_factor),
-                    );
-                }
-                bevy_window::WindowCommand::SetVsync { .. } => (),
-                bevy_window::WindowCommand::SetResizable { resizable } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_resizable(resizable);
-                }
-                bevy_window::WindowCommand::SetDecorations { decorations } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_decorations(decorations);
-                }
-                bevy_window::WindowCommand::SetCursorLockMode { locked } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window
-                        .set_cursor_grab(locked)
-                        .unwrap_or_else(|e| error!("Unable to un/grab cursor: {}", e));
-                }
-                bevy_window::WindowCommand::SetCursorVisibility { visible } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_cursor_visible(visible);
-                }
-                bevy_window::WindowCommand::SetCursorPosition { position } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    let inner_size = window.inner_size().to_logical::<f32>(window.scale_factor());
-                    window
-                        .set_cursor_position(winit::dpi::LogicalPosition::new(
-                            position.x,
-                            inner_size.height - position.y,
-                        ))
-                        .unwrap_or_else(|e| error!("Unable to set cursor position: {}", e));
-                }
-                bevy_window::WindowCommand::SetMaximized { maximized } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_maximized(maximized)
-                }
-                bevy_window::WindowCommand::SetMinimized { minimized } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_minimized(minimized)
-                }
-                bevy_window::WindowCommand::SetPosition { position } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_outer_position(PhysicalPosition {
-                        x: position[0],
-                        y: position[1],
-                    });
-                }
-                bevy_window::WindowCommand::SetResizeConstraints { resize_constraints } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    let constraints = resize_constraints.check_constraints();
-                    let min_inner_size = LogicalSize {
-                        width: constraints.min_width,
-                        height: constraints.min_height,
-                    };
-                    let max_inner_size = LogicalSize {
-                        width: constraints.max_width,
-                        height: constraints.max_height,
-                    };
+fn update_raw_window_handles(world: &mut World) {
+    let world = world.cell();
+    if let Some(mut handles) = world.get_resource_mut::<RawWindowHandles>() {
+        let winit_windows = world.get_resource::<WinitWindows>().unwrap();
+        winit_windows.update_raw_window_handles(&mut handles);
+    };
+}
 
-                    window.set_min_inner_size(Some(min_inner_size));
-                    if constraints.max_width.is_finite() && constraints.max_height.is_finite() {
-                        window.set_max_inner_size(Some(max_inner_size));
-                    }
-                }
-            }
-        }
+/// Reports the errors of windows that were created with a fallback, e.g. windowed instead of
+/// fullscreen, according to the [BackendErrorPolicy].
+fn report_creation_errors(world: &mut World) {
+    let world = world.cell();
+    let mut winit_windows = world.get_resource_mut::<WinitWindows>().unwrap();
+    let policy = world.get_resource::<BackendErrorPolicy>().unwrap();
+    let mut window_command_failed_events = world
+        .get_resource_mut::<Events<WindowCommandFailed>>()
+        .unwrap();
+    for (id, error) in winit_windows.creation_errors.drain(..) {
+        policy.handle(id, error, &mut window_command_failed_events);
     }
 }
 
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
 fn run<F>(event_loop: EventLoop<()>, event_handler: F) -> !
 where
     F: 'static + FnMut(Event<'_, ()>, &EventLoopWindowTarget<()>, &mut ControlFlow),
@@ -206,6 +280,7 @@ where
     target_os = "netbsd",
     target_os = "openbsd"
 )))]
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
 fn run_return<F>(_event_loop: &mut EventLoop<()>, _event_handler: F)
 where
     F: FnMut(Event<'_, ()>, &EventLoopWindowTarget<()>, &mut ControlFlow),
@@ -228,119 +303,172 @@ pub fn winit_runner_any_thread(app: App) {
     winit_runner_with(app, true);
 }
 
-pub fn winit_runner_with(mut app: App, is_any_thread: bool) {
-    if !is_any_thread {
+pub fn winit_runner_with(app: App, is_any_thread: bool) {
+    // the web and iOS runners do not move the event loop to another thread
+    if !is_any_thread && !cfg!(any(target_arch = "wasm32", target_os = "ios")) {
         panic!("non-any-thread is not supported!");
     }
 
-    let should_return_from_run = app
-        .world
-        .get_resource::<WinitConfig>()
-        .map_or(false, |config| config.return_from_run);
+    winit_runner_multi(vec![app]);
+}
 
-    let (app_exit_event_sender, app_exit_event_receiver) = mpsc::sync_channel::<()>(0);
-    let (winit_event_sender, winit_event_receiver) = mpsc::channel::<WinitEvent>();
+/// Runs several apps, e.g. an editor and a play-mode preview, on a single winit event loop.
+///
+/// Each app owns the windows it creates and only receives the events of those windows, while
+/// device events such as raw mouse motion are sent to every app. The settings that apply to the
+/// event loop itself (`return_from_run`, `update_mode`, `hidden_update_mode` and
+/// `simulated_latency`) are taken from the [WinitConfig] of the first app. An app is dropped
+/// once it sends [AppExit], and this returns when all apps have exited.
+///
+/// On the web and on iOS the event loop runs on the main thread instead, as there is no other
+/// thread or UIKit requires it.
+pub fn winit_runner_multi(apps: Vec<App>) {
+    #[allow(unused_mut)]
+    let mut apps = apps.into_iter().map(RunningApp::new).collect::<Vec<_>>();
+    let config = match apps.first() {
+        Some(running) => running.config.clone(),
+        None => return,
+    };
 
-    let (keyboard_input_sender, keyboard_input_receiver) = mpsc::channel::<KeyboardInput>();
-    app.world
-        .insert_resource(Mutex::new(keyboard_input_receiver));
+    #[cfg(any(target_arch = "wasm32", target_os = "ios"))]
+    main_thread::run_single_threaded(apps, config);
 
-    thread::spawn(move || {
-        let mut event_loop = EventLoop::new_any_thread();
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    loop {
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            run_event_loop(&mut apps, &config)
+        }));
+        match result {
+            Ok(true) => trace!("Restarting winit event loop"),
+            Ok(false) => break,
+            Err(e) => {
+                if config.error_dialog_on_panic {
+                    let message = error_dialog::panic_message(&*e);
+                    show_error_dialog("The application stopped unexpectedly", &message);
+                }
+                panic::resume_unwind(e);
+            }
+        }
+    }
+}
+
+/// Runs a winit event loop on its own thread until all apps have exited, in which case they
+/// are shut down, or until one of them requests a [RestartBackend], in which case their windows
+/// are torn down and `true` is returned.
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+fn run_event_loop(apps: &mut Vec<RunningApp>, config: &WinitConfig) -> bool {
+    let should_return_from_run = config.return_from_run;
+    let update_mode = config.update_mode;
+    let hidden_update_mode = config.hidden_update_mode.unwrap_or(update_mode);
+    // set while every app only has hidden or minimized windows, so the winit thread can wait
+    // according to `hidden_update_mode`
+    let all_hidden = Arc::new(AtomicBool::new(false));
+    let mut latency_simulator = config.simulated_latency.map(latency::LatencySimulator::new);
+    let mut unknown_window_events = UnknownWindowEvents::new(config.unknown_window_events);
+
+    let backend = match select_backend(config.display_backend) {
+        Ok(backend) => {
+            for running in apps.iter_mut() {
+                let capabilities = window_capabilities(backend, running.config.emulate_cursor_lock);
+                running.app.world.insert_resource(backend);
+                running.app.world.insert_resource(capabilities);
+            }
+            backend
+        }
+        Err(error) => {
+            for mut running in apps.drain(..) {
+                report_backend_failure(&mut running.app, error.clone());
+                running.shut_down();
+            }
+            return false;
+        }
+    };
+
+    let exit_signal = ExitSignal::default();
+    let suspend_signal = suspend_signal::SuspendSignal::default();
+    // events are sent with the time they were sent at, for the `WinitChannelStats`
+    let (winit_event_sender, winit_event_receiver) = mpsc::channel::<(WinitEvent, Instant)>();
+    let dropped_events = Arc::new(AtomicUsize::new(0));
+
+    let (keyboard_input_senders, clipboard_request_receiver, event_masks, safe_area_views) =
+        connect_apps(apps);
+    let mut converter_config = config.clone();
+    converter_config.poll_gamepads &=
+        !gamepads::polled_by_apps(apps.iter().map(|running| &running.app.world));
+    let winit_all_hidden = all_hidden.clone();
+    let winit_exit_signal = exit_signal.clone();
+    let winit_suspend_signal = suspend_signal.clone();
+    let winit_dropped_events = dropped_events.clone();
+    let winit_thread = thread::spawn(move || {
+        // winit panics if it cannot connect to the display server, turn that into an error the
+        // app can handle
+        let mut event_loop = match panic::catch_unwind(|| backend::create_event_loop(backend)) {
+            Ok(Ok(event_loop)) => event_loop,
+            Ok(Err(reason)) => {
+                let _ =
+                    winit_event_sender.send((WinitEvent::BackendFailed(reason), Instant::now()));
+                return;
+            }
+            Err(e) => {
+                let reason = error_dialog::panic_message(&*e);
+                let _ =
+                    winit_event_sender.send((WinitEvent::BackendFailed(reason), Instant::now()));
+                return;
+            }
+        };
+        let now = Instant::now();
         winit_event_sender
-            .send(WinitEvent::CreatedProxy(event_loop.create_proxy()))
+            .send((
+                WinitEvent::CreatedProxy(event_loop.create_proxy(), now),
+                now,
+            ))
             .unwrap();
 
         trace!("Entering winit event loop");
 
+        let mut converter = EventConverter::new(
+            &converter_config,
+            keyboard_input_senders,
+            clipboard_request_receiver,
+            event_masks,
+            safe_area_views,
+            event_loop.create_proxy(),
+        );
+        converter.dropped_events = winit_dropped_events;
+        let mut converted = Vec::new();
+
         let event_handler = move |event: Event<()>,
                                   event_loop: &EventLoopWindowTarget<()>,
                                   control_flow: &mut ControlFlow| {
-            *control_flow = ControlFlow::Poll;
+            let update_mode = if winit_all_hidden.load(Ordering::Relaxed) {
+                hidden_update_mode
+            } else {
+                update_mode
+            };
+            *control_flow = converter.control_flow(update_mode.max_wait());
 
-            if let Ok(_) = app_exit_event_receiver.try_recv() {
+            if winit_exit_signal.is_requested() {
                 *control_flow = ControlFlow::Exit;
+                return;
             }
 
-            let e = match event {
-                event::Event::WindowEvent {
-                    event,
-                    window_id: winit_window_id,
-                    ..
-                } => {
-                    let e = match event {
-                        WindowEvent::Resized(size) => WinitWindowEvent::Resized(size),
-                        WindowEvent::CloseRequested => WinitWindowEvent::CloseRequested,
-                        WindowEvent::KeyboardInput { ref input, .. } => {
-                            let input = converters::convert_keyboard_input(input);
-
-                            keyboard_input_sender.send(input.clone()).unwrap();
-
-                            WinitWindowEvent::KeyboardInput(input)
-                        }
-                        WindowEvent::CursorMoved { position, .. } => {
-                            WinitWindowEvent::CursorMoved(position)
-                        }
-                        WindowEvent::CursorEntered { .. } => WinitWindowEvent::CursorEntered,
-                        WindowEvent::CursorLeft { .. } => WinitWindowEvent::CursorLeft,
-                        WindowEvent::MouseInput { state, button, .. } => {
-                            WinitWindowEvent::MouseInput(MouseButtonInput {
-                                button: converters::convert_mouse_button(button),
-                                state: converters::convert_element_state(state),
-                            })
-                        }
-                        WindowEvent::MouseWheel { delta, .. } => match delta {
-                            event::MouseScrollDelta::LineDelta(x, y) => {
-                                WinitWindowEvent::MouseWheel(MouseWheel {
-                                    unit: MouseScrollUnit::Line,
-                                    x,
-                                    y,
-                                })
-                            }
-                            event::MouseScrollDelta::PixelDelta(p) => {
-                                WinitWindowEvent::MouseWheel(MouseWheel {
-                                    unit: MouseScrollUnit::Pixel,
-                                    x: p.x as f32,
-                                    y: p.y as f32,
-                                })
-                            }
-                        },
-                        WindowEvent::Touch(touch) => WinitWindowEvent::Touch(touch),
-                        WindowEvent::ReceivedCharacter(c) => WinitWindowEvent::ReceivedCharacter(c),
-                        WindowEvent::ScaleFactorChanged {
-                            scale_factor,
-                            new_inner_size,
-                        } => WinitWindowEvent::ScaleFactorChanged(
-                            scale_factor,
-                            new_inner_size.clone(),
-                        ),
-                        WindowEvent::Focused(focused) => WinitWindowEvent::Focused(focused),
-                        WindowEvent::DroppedFile(path_buf) => {
-                            WinitWindowEvent::DroppedFile(path_buf)
-                        }
-                        WindowEvent::HoveredFile(path_buf) => {
-                            WinitWindowEvent::HoveredFile(path_buf)
-                        }
-                        WindowEvent::HoveredFileCancelled => WinitWindowEvent::HoveredFileCancelled,
-                        WindowEvent::Moved(position) => WinitWindowEvent::Moved(position),
-                        _ => WinitWindowEvent::None,
-                    };
-
-                    WinitEvent::WindowEvent(e, winit_window_id)
+            let suspend_ticket =
+                matches!(event, Event::Suspended).then(|| winit_suspend_signal.request());
+            converter.convert(event, event_loop, &mut converted);
+            let now = Instant::now();
+            for e in converted.drain(..) {
+                if winit_event_sender.send((e, now)).is_err() {
+                    // the app loop has already exited and is waiting for this thread to finish
+                    *control_flow = ControlFlow::Exit;
                 }
-                event::Event::DeviceEvent {
-                    event: DeviceEvent::MouseMotion { delta },
-                    ..
-                } => WinitEvent::MouseMotion(MouseMotion {
-                    delta: Vec2::new(delta.0 as f32, delta.1 as f32),
-                }),
-                event::Event::MainEventsCleared => WinitEvent::MainEventsCleared(
-                    event_loop as *const EventLoopWindowTarget<()> as usize,
-                ),
-                _ => WinitEvent::None,
-            };
-
-            winit_event_sender.send(e).unwrap();
+            }
+            if let Some(ticket) = suspend_ticket {
+                // the native windows may be destroyed once this returns, so the apps have to
+                // drop their surfaces first
+                if !winit_suspend_signal.wait(ticket, SUSPEND_TIMEOUT) {
+                    warn!("The apps did not handle the suspension in time");
+                }
+            }
         };
 
         if should_return_from_run {
@@ -350,272 +478,1331 @@ pub fn winit_runner_with(mut app: App, is_any_thread: bool) {
         }
     });
 
-    let mut create_window_event_reader = ManualEventReader::<CreateWindow>::default();
-    let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
-
     let mut current_elwt = None;
+    let mut event_loop_proxy = None;
+    let mut restart = false;
+    // reused between iterations, so draining the channel does not allocate once they have grown
+    // FIXME: channelをsyncにして容量の制限
+    let mut received = Vec::new();
+    let mut drainer = Vec::new();
+    let mut passed = Vec::new();
 
     trace!("Entering bevy (from winit) event loop");
 
-    loop {
-        if let Some(app_exit_events) = app.world.get_resource_mut::<Events<AppExit>>() {
-            if app_exit_event_reader
-                .iter(&app_exit_events)
-                .next_back()
-                .is_some()
-            {
-                app_exit_event_sender.send(()).unwrap();
+    'app: loop {
+        shut_down_exited_apps(apps);
+        if apps.is_empty() {
+            break;
+        }
+
+        if apps.iter_mut().any(|running| running.restart_requested()) {
+            if should_return_from_run {
+                restart = true;
+                break;
             }
+            warn!("Restarting the windowing backend requires `WinitConfig::return_from_run`");
         }
 
-        let mut drainer = vec![]; // FIXME: Smallvec化 + channelをsyncにして容量の制限
+        let mut timeout = apps
+            .iter()
+            .filter_map(|running| {
+                let update_mode = running.update_mode(update_mode, hidden_update_mode);
+                let until_frame = running.time_until_frame(update_mode);
+                until_frame.or_else(|| running.time_until_due(update_mode))
+            })
+            .min();
+        if let Some(release_at) = latency_simulator.as_ref().and_then(|s| s.next_release()) {
+            let until_release = release_at.saturating_duration_since(Instant::now());
+            timeout = Some(timeout.map_or(until_release, |timeout| timeout.min(until_release)));
+        }
+        if let Some(timeout) = timeout {
+            // sleep until the winit thread has something for us, the next update is due or a
+            // held back event is released
+            match winit_event_receiver.recv_timeout(timeout) {
+                Ok(e) => received.push(e),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        } else {
+            // sleep until the winit thread has something for us, frames are paced by
+            // `MainEventsCleared`
+            match winit_event_receiver.recv() {
+                Ok(e) => received.push(e),
+                Err(mpsc::RecvError) => break,
+            }
+        }
         winit_event_receiver
             .try_iter()
-            .for_each(|e| drainer.push(e));
+            .for_each(|e| received.push(e));
 
-        for e in drainer.drain(..) {
-            match e {
-                WinitEvent::WindowEvent(e, winit_window_id) => {
-                    let world = app.world.cell();
-                    let winit_windows = world.get_resource_mut::<WinitWindows>().unwrap();
-                    let mut windows = world.get_resource_mut::<Windows>().unwrap();
-                    let window_id =
-                        if let Some(window_id) = winit_windows.get_window_id(winit_window_id) {
-                            window_id
-                        } else {
-                            warn!(
-                                "Skipped event for unknown winit Window Id {:?}",
-                                winit_window_id
-                            );
-                            return;
-                        };
-
-                    let window = if let Some(window) = windows.get_mut(window_id) {
-                        window
-                    } else {
-                        warn!("Skipped event for unknown Window Id {:?}", winit_window_id);
-                        return;
-                    };
+        let now = Instant::now();
+        let dropped = dropped_events.swap(0, Ordering::Relaxed);
+        for running in apps.iter_mut() {
+            if let Some(mut stats) = running.app.world.get_resource_mut::<WinitChannelStats>() {
+                stats.record_batch(received.iter().map(|(_, sent_at)| *sent_at), now);
+                stats.dropped_events += dropped;
+            }
+        }
+        drainer.extend(received.drain(..).map(|(e, _)| e));
+        take_injected_events(apps, &mut drainer);
 
-                    match e {
-                        WinitWindowEvent::Resized(size) => {
-                            window.update_actual_size_from_backend(size.width, size.height);
-                            let mut resize_events =
-                                world.get_resource_mut::<Events<WindowResized>>().unwrap();
-                            resize_events.send(WindowResized {
-                                id: window_id,
-                                width: window.width(),
-                                height: window.height(),
-                            });
-                        }
-                        WinitWindowEvent::CloseRequested => world
-                            .get_resource_mut::<Events<WindowCloseRequested>>()
-                            .unwrap()
-                            .send(WindowCloseRequested { id: window_id }),
-                        WinitWindowEvent::KeyboardInput(input) => world
-                            .get_resource_mut::<Events<KeyboardInput>>()
-                            .unwrap()
-                            .send(input),
-                        WinitWindowEvent::CursorMoved(position) => {
-                            let mut cursor_moved_events =
-                                world.get_resource_mut::<Events<CursorMoved>>().unwrap();
-                            let winit_window = winit_windows.get_window(window_id).unwrap();
-                            let position = position.to_logical(winit_window.scale_factor());
-                            let inner_size = winit_window
-                                .inner_size()
-                                .to_logical::<f32>(winit_window.scale_factor());
-
-                            // move origin to bottom left
-                            let y_position = inner_size.height - position.y;
-
-                            let position = Vec2::new(position.x, y_position);
-                            window.update_cursor_position_from_backend(Some(position));
-
-                            cursor_moved_events.send(CursorMoved {
-                                id: window_id,
-                                position,
-                            });
-                        }
-                        WinitWindowEvent::CursorEntered => world
-                            .get_resource_mut::<Events<CursorEntered>>()
-                            .unwrap()
-                            .send(CursorEntered { id: window_id }),
-                        WinitWindowEvent::CursorLeft => world
-                            .get_resource_mut::<Events<CursorLeft>>()
-                            .unwrap()
-                            .send(CursorLeft { id: window_id }),
-                        WinitWindowEvent::MouseInput(input) => world
-                            .get_resource_mut::<Events<MouseButtonInput>>()
-                            .unwrap()
-                            .send(input),
-                        WinitWindowEvent::MouseWheel(input) => world
-                            .get_resource_mut::<Events<MouseWheel>>()
-                            .unwrap()
-                            .send(input),
-                        WinitWindowEvent::Touch(touch) => {
-                            let mut touch_input_events =
-                                world.get_resource_mut::<Events<TouchInput>>().unwrap();
-
-                            let winit_window = winit_windows.get_window(window_id).unwrap();
-                            let mut location =
-                                touch.location.to_logical(winit_window.scale_factor());
-
-                            // On a mobile window, the start is from the top while on PC/Linux/OSX from
-                            // bottom
-                            if cfg!(target_os = "android") || cfg!(target_os = "ios") {
-                                let window_height = windows.get_primary().unwrap().height();
-                                location.y = window_height - location.y;
-                            }
-                            touch_input_events
-                                .send(converters::convert_touch_input(touch, location));
-                        }
-                        WinitWindowEvent::ReceivedCharacter(c) => {
-                            let mut char_input_events = world
-                                .get_resource_mut::<Events<ReceivedCharacter>>()
-                                .unwrap();
-
-                            char_input_events.send(ReceivedCharacter {
-                                id: window_id,
-                                char: c,
-                            });
-                        }
-                        WinitWindowEvent::ScaleFactorChanged(scale_factor, new_inner_size) => {
-                            let mut backend_scale_factor_change_events = world
-                                .get_resource_mut::<Events<WindowBackendScaleFactorChanged>>()
-                                .unwrap();
-                            backend_scale_factor_change_events.send(
-                                WindowBackendScaleFactorChanged {
-                                    id: window_id,
-                                    scale_factor,
-                                },
-                            );
-
-                            #[allow(clippy::float_cmp)]
-                            if window.scale_factor() != scale_factor {
-                                let mut scale_factor_change_events = world
-                                    .get_resource_mut::<Events<WindowScaleFactorChanged>>()
-                                    .unwrap();
-
-                                scale_factor_change_events.send(WindowScaleFactorChanged {
-                                    id: window_id,
-                                    scale_factor,
-                                });
-                            }
-
-                            window.update_scale_factor_from_backend(scale_factor);
-
-                            if window.physical_width() != new_inner_size.width
-                                || window.physical_height() != new_inner_size.height
-                            {
-                                let mut resize_events =
-                                    world.get_resource_mut::<Events<WindowResized>>().unwrap();
-                                resize_events.send(WindowResized {
-                                    id: window_id,
-                                    width: window.width(),
-                                    height: window.height(),
-                                });
-                            }
-                            window.update_actual_size_from_backend(
-                                new_inner_size.width,
-                                new_inner_size.height,
-                            );
-                        }
-                        WinitWindowEvent::Focused(focused) => {
-                            window.update_focused_status_from_backend(focused);
-                            let mut focused_events =
-                                world.get_resource_mut::<Events<WindowFocused>>().unwrap();
-                            focused_events.send(WindowFocused {
-                                id: window_id,
-                                focused,
-                            });
-                        }
-                        WinitWindowEvent::DroppedFile(path_buf) => {
-                            let mut events =
-                                world.get_resource_mut::<Events<FileDragAndDrop>>().unwrap();
-                            events.send(FileDragAndDrop::DroppedFile {
-                                id: window_id,
-                                path_buf,
-                            });
-                        }
-                        WinitWindowEvent::HoveredFile(path_buf) => {
-                            let mut events =
-                                world.get_resource_mut::<Events<FileDragAndDrop>>().unwrap();
-                            events.send(FileDragAndDrop::HoveredFile {
-                                id: window_id,
-                                path_buf,
-                            });
-                        }
-                        WinitWindowEvent::HoveredFileCancelled => {
-                            let mut events =
-                                world.get_resource_mut::<Events<FileDragAndDrop>>().unwrap();
-                            events.send(FileDragAndDrop::HoveredFileCancelled { id: window_id });
-                        }
-                        WinitWindowEvent::Moved(position) => {
-                            let position = ivec2(position.x, position.y);
-                            window.update_actual_position_from_backend(position);
-                            let mut events =
-                                world.get_resource_mut::<Events<WindowMoved>>().unwrap();
-                            events.send(WindowMoved {
-                                id: window_id,
-                                position,
-                            });
-                        }
-                        WinitWindowEvent::None => (),
-                    }
-                }
-                WinitEvent::MouseMotion(input) => {
-                    let mut mouse_motion_events =
-                        app.world.get_resource_mut::<Events<MouseMotion>>().unwrap();
-                    mouse_motion_events.send(input);
+        if let Some(latency_simulator) = latency_simulator.as_mut() {
+            let now = Instant::now();
+            for e in drainer.drain(..) {
+                match e {
+                    WinitEvent::WindowEvent(..)
+                    | WinitEvent::MouseMotion(_)
+                    | WinitEvent::DeviceEvent(_) => latency_simulator.push(e, now),
+                    _ => passed.push(e),
                 }
-                WinitEvent::CreatedProxy(proxy) => app.world.insert_non_send(proxy),
+            }
+            while let Some(e) = latency_simulator.pop_due(now) {
+                drainer.push(e);
+            }
+            drainer.append(&mut passed);
+        }
+        // events of windows that were unknown so far go first, they arrived earlier
+        unknown_window_events.take_known(|id| is_known_window(apps, id), &mut passed);
+        drainer.splice(0..0, passed.drain(..));
 
-                WinitEvent::MainEventsCleared(raw_elwt_ptr) => {
+        let mut suspending = false;
+        let mut events = drainer.drain(..).peekable();
+        while let Some(e) = events.next() {
+            suspending |= matches!(e, WinitEvent::Lifecycle(ApplicationLifecycle::Suspended));
+            let dispatched = match e {
+                WinitEvent::WindowEvent(e, winit_window_id) => {
+                    // consecutive events of the same window are dispatched together
+                    let is_same_window =
+                        |next: &WinitEvent| next.window_id() == Some(winit_window_id);
+                    let same_window = iter::from_fn(|| match events.next_if(is_same_window) {
+                        Some(WinitEvent::WindowEvent(e, _)) => Some(e),
+                        _ => None,
+                    });
+                    dispatch_window_events(
+                        apps,
+                        winit_window_id,
+                        iter::once(e).chain(same_window),
+                        &mut unknown_window_events,
+                    )
+                }
+                e => dispatch_event(
+                    apps,
+                    e,
+                    update_mode,
+                    hidden_update_mode,
+                    &mut event_loop_proxy,
+                    &mut unknown_window_events,
+                ),
+            };
+            match dispatched {
+                Dispatched::Continue => {}
+                Dispatched::MainEventsCleared(raw_elwt_ptr) => {
                     current_elwt = Some(unsafe {
                         (raw_elwt_ptr as *const EventLoopWindowTarget<()>)
                             .as_ref()
                             .unwrap()
                     });
                 }
-                WinitEvent::None => (),
+                Dispatched::BackendFailed => break 'app,
             }
         }
 
         if let Some(elwt) = current_elwt {
-            handle_create_window_events(&mut app.world, elwt, &mut create_window_event_reader);
-            app.update();
+            for running in apps.iter_mut() {
+                if running.should_update(running.update_mode(update_mode, hidden_update_mode)) {
+                    // held back by the frame rate limit, the wait for events above wakes up
+                    // in time for it
+                    if let Some(next_frame_at) = running.next_frame_at() {
+                        if !frame_pacing::spin_until(next_frame_at) {
+                            continue;
+                        }
+                    }
+                    running.update(elwt);
+                }
+            }
+        }
+        if suspending {
+            // the winit thread waits for this, regardless of the update mode and frame rate limit
+            if let Some(elwt) = current_elwt {
+                for running in apps.iter_mut().filter(|running| running.update_requested) {
+                    running.update(elwt);
+                }
+            }
+            suspend_signal.acknowledge();
+        }
+
+        let now_all_hidden = !apps.is_empty() && apps.iter().all(|running| running.hidden);
+        if all_hidden.swap(now_all_hidden, Ordering::Relaxed) && !now_all_hidden {
+            // the winit thread may be waiting for a long time, let it pick up `update_mode`
+            if let Some(event_loop_proxy) = event_loop_proxy.as_ref() {
+                let _ = event_loop_proxy.send_event(());
+            }
         }
     }
+
+    trace!("Exiting bevy (from winit) event loop");
+
+    // The windows have to be destroyed while their event loop is still running.
+    if restart {
+        for running in apps.iter_mut() {
+            running.tear_down_windows();
+            update_raw_window_handles(&mut running.app.world);
+        }
+    } else {
+        for running in apps.drain(..) {
+            running.shut_down();
+        }
+    }
+
+    // a suspension that is still pending must not hold up the exit of the winit thread
+    suspend_signal.acknowledge();
+    request_event_loop_exit(&exit_signal, event_loop_proxy, &winit_event_receiver);
+
+    // Without `return_from_run` winit exits the process from its own thread, so this only
+    // returns when the event loop was run with `run_return`.
+    if let Err(e) = winit_thread.join() {
+        panic::resume_unwind(e);
+    }
+
+    restart
 }
 
-fn handle_create_window_events(
-    world: &mut World,
-    event_loop: &EventLoopWindowTarget<()>,
-    create_window_event_reader: &mut ManualEventReader<CreateWindow>,
+/// Shuts down the apps that sent [AppExit].
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+fn shut_down_exited_apps(apps: &mut Vec<RunningApp>) {
+    let mut index = 0;
+    while index < apps.len() {
+        if apps[index].exit_requested() {
+            apps.remove(index).shut_down();
+        } else {
+            index += 1;
+        }
+    }
+}
+
+/// Tells the winit thread to leave its event loop, once the app loop is done.
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+fn request_event_loop_exit(
+    exit_signal: &ExitSignal,
+    event_loop_proxy: Option<EventLoopProxy<()>>,
+    winit_event_receiver: &mpsc::Receiver<(WinitEvent, Instant)>,
 ) {
-    let world = world.cell();
+    if exit_signal.request() {
+        // The event loop may be waiting for events, so wake it up to notice the exit request.
+        // If the proxy has not been received yet, the winit thread has either not entered its
+        // event loop and will see the request on its first event, or the proxy is still queued.
+        let event_loop_proxy = event_loop_proxy.or_else(|| {
+            winit_event_receiver.try_iter().find_map(|(e, _)| match e {
+                WinitEvent::CreatedProxy(proxy, _) => Some(proxy),
+                _ => None,
+            })
+        });
+        // Fails only if the winit thread has already stopped on its own.
+        if let Some(event_loop_proxy) = event_loop_proxy {
+            let _ = event_loop_proxy.send_event(());
+        }
+    }
+}
+
+/// What the app side of a runner has to do after [dispatch_event].
+enum Dispatched {
+    Continue,
+    /// The event loop has handled all events, the apps may be updated with its target.
+    MainEventsCleared(
+        #[cfg_attr(any(target_arch = "wasm32", target_os = "ios"), allow(dead_code))] usize,
+    ),
+    /// The event loop could not be started, the apps have to be shut down.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    BackendFailed,
+}
+
+/// Dispatches an event of the [EventConverter] to the apps it concerns.
+fn dispatch_event(
+    apps: &mut [RunningApp],
+    e: WinitEvent,
+    update_mode: UpdateMode,
+    hidden_update_mode: UpdateMode,
+    event_loop_proxy: &mut Option<EventLoopProxy<()>>,
+    unknown_window_events: &mut UnknownWindowEvents,
+) -> Dispatched {
+    match e {
+        WinitEvent::WindowEvent(e, winit_window_id) => {
+            return dispatch_window_events(
+                apps,
+                winit_window_id,
+                iter::once(e),
+                unknown_window_events,
+            );
+        }
+        WinitEvent::MouseMotion(input) => {
+            for running in apps.iter_mut() {
+                if !matches!(
+                    running.update_mode(update_mode, hidden_update_mode),
+                    UpdateMode::ReactiveLowPower { .. }
+                ) {
+                    running.update_requested = true;
+                }
+
+                // an emulated cursor lock reports the motion of the cursor instead
+                if running
+                    .app
+                    .world
+                    .get_resource::<WinitWindows>()
+                    .map_or(false, |windows| windows.has_emulated_cursor_lock())
+                {
+                    continue;
+                }
+
+                if let Some(mut transport) = running.app.world.get_resource_mut::<WinitTransport>()
+                {
+                    transport.record(|| Some(TransportEvent::from_mouse_motion(&input)));
+                }
+                let mut mouse_motion_events = running
+                    .app
+                    .world
+                    .get_resource_mut::<Events<MouseMotion>>()
+                    .unwrap();
+                mouse_motion_events.send(input.clone());
+            }
+        }
+        WinitEvent::DeviceEvent(e) => {
+            for running in apps.iter_mut() {
+                // like mouse motion, raw input should not wake apps in low power mode,
+                // but devices being added or removed is rare enough to always do so
+                if matches!(e, WinitDeviceEvent::Added(_) | WinitDeviceEvent::Removed(_))
+                    || !matches!(
+                        running.update_mode(update_mode, hidden_update_mode),
+                        UpdateMode::ReactiveLowPower { .. }
+                    )
+                {
+                    running.update_requested = true;
+                }
+
+                let world = running.app.world.cell();
+                match &e {
+                    WinitDeviceEvent::Added(device) | WinitDeviceEvent::Removed(device) => {
+                        world
+                            .get_resource_mut::<Events<DeviceConnectionEvent>>()
+                            .unwrap()
+                            .send(DeviceConnectionEvent {
+                                device: *device,
+                                connected: matches!(e, WinitDeviceEvent::Added(_)),
+                            });
+                    }
+                    WinitDeviceEvent::Button(input) => world
+                        .get_resource_mut::<Events<RawMouseButtonInput>>()
+                        .unwrap()
+                        .send(input.clone()),
+                    WinitDeviceEvent::Key(input) => world
+                        .get_resource_mut::<Events<RawKeyboardInput>>()
+                        .unwrap()
+                        .send(input.clone()),
+                    WinitDeviceEvent::MouseWheel(input) => world
+                        .get_resource_mut::<Events<RawMouseWheel>>()
+                        .unwrap()
+                        .send(input.clone()),
+                    WinitDeviceEvent::None => (),
+                }
+            }
+        }
+        WinitEvent::Monitors(monitors) => {
+            for running in apps.iter_mut() {
+                let changes =
+                    if let Some(mut resource) = running.app.world.get_resource_mut::<Monitors>() {
+                        resource.set_from_backend(monitors.clone())
+                    } else {
+                        continue;
+                    };
+                if !changes.is_empty() {
+                    let mut events = running
+                        .app
+                        .world
+                        .get_resource_mut::<Events<MonitorModeChanged>>()
+                        .unwrap();
+                    for change in changes {
+                        events.send(change);
+                    }
+                    running.update_requested = true;
+                }
+            }
+        }
+        WinitEvent::ClipboardChanged => {
+            for running in apps.iter_mut() {
+                running
+                    .app
+                    .world
+                    .get_resource_mut::<Events<ClipboardChanged>>()
+                    .unwrap()
+                    .send(ClipboardChanged);
+                running.update_requested = true;
+            }
+        }
+        WinitEvent::Gamepad(gamepad_event) => {
+            for running in apps.iter_mut() {
+                running
+                    .app
+                    .world
+                    .get_resource_mut::<Events<GamepadEventRaw>>()
+                    .unwrap()
+                    .send(gamepad_event.clone());
+                running.update_requested = true;
+            }
+        }
+        WinitEvent::SystemInfo(info) => {
+            for running in apps.iter_mut() {
+                running.app.world.insert_resource(info.clone());
+            }
+        }
+        WinitEvent::SystemPreferences(preferences) => {
+            for running in apps.iter_mut() {
+                let world = running.app.world.cell();
+                let mut resource = world.get_resource_mut::<SystemPreferences>().unwrap();
+                if *resource != preferences {
+                    *resource = preferences.clone();
+                    world
+                        .get_resource_mut::<Events<SystemPreferencesChanged>>()
+                        .unwrap()
+                        .send(SystemPreferencesChanged {
+                            preferences: preferences.clone(),
+                        });
+                    running.update_requested = true;
+                }
+            }
+        }
+        WinitEvent::Lifecycle(lifecycle) => {
+            for running in apps.iter_mut() {
+                running.update_surfaces_for_lifecycle(lifecycle);
+                running
+                    .app
+                    .world
+                    .get_resource_mut::<Events<ApplicationLifecycle>>()
+                    .unwrap()
+                    .send(lifecycle);
+                running.update_requested = true;
+            }
+        }
+        WinitEvent::CreatedProxy(proxy, created_at) => {
+            for running in apps.iter_mut() {
+                running.record_startup_step(StartupStep::ProxyCreated, created_at);
+                running.app.world.insert_non_send(proxy.clone());
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(clipboard) = running.app.world.get_resource::<Clipboard>() {
+                    clipboard.set_event_loop_proxy(proxy.clone());
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(waker) = running.app.world.get_resource::<Arc<WinitWaker>>() {
+                    waker.set_event_loop_proxy(proxy.clone());
+                }
+            }
+            *event_loop_proxy = Some(proxy);
+        }
+
+        WinitEvent::MainEventsCleared(raw_elwt_ptr) => {
+            let now = Instant::now();
+            for running in apps.iter_mut() {
+                running.record_startup_step(StartupStep::FirstMainEventsCleared, now);
+                running.main_events_cleared = true;
+                // user events wake the event loop to be handled right away
+                if running
+                    .app
+                    .world
+                    .get_resource::<Arc<WinitWaker>>()
+                    .map_or(false, |waker| waker.take_pending())
+                {
+                    running.update_requested = true;
+                }
+            }
+            return Dispatched::MainEventsCleared(raw_elwt_ptr);
+        }
+        #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+        WinitEvent::BackendFailed(reason) => {
+            let error = WinitBackendError::EventLoopCreation(reason);
+            for running in apps.iter_mut() {
+                report_backend_failure(&mut running.app, error.clone());
+            }
+            return Dispatched::BackendFailed;
+        }
+        WinitEvent::None => (),
+    }
+    Dispatched::Continue
+}
+
+/// Dispatches window events of the same winit window together, so that the window and the
+/// resources of its app are only looked up once per batch.
+fn dispatch_window_events(
+    apps: &mut [RunningApp],
+    winit_window_id: winit::window::WindowId,
+    events: impl IntoIterator<Item = WinitWindowEvent>,
+    unknown_window_events: &mut UnknownWindowEvents,
+) -> Dispatched {
+    let owner = apps.iter_mut().find_map(|running| {
+        let window_id = running
+            .app
+            .world
+            .get_resource::<WinitWindows>()?
+            .get_window_id(winit_window_id)?;
+        Some((running, window_id))
+    });
+    let (running, window_id) = if let Some(owner) = owner {
+        owner
+    } else {
+        unknown_window_events.push(winit_window_id, events);
+        return Dispatched::Continue;
+    };
+
+    let world = running.app.world.cell();
     let mut winit_windows = world.get_resource_mut::<WinitWindows>().unwrap();
     let mut windows = world.get_resource_mut::<Windows>().unwrap();
-    let create_window_events = world.get_resource::<Events<CreateWindow>>().unwrap();
-    let mut window_created_events = world.get_resource_mut::<Events<WindowCreated>>().unwrap();
-    for create_window_event in create_window_event_reader.iter(&create_window_events) {
-        let window = winit_windows.create_window(
-            event_loop,
-            create_window_event.id,
-            &create_window_event.descriptor,
+
+    let window = if let Some(window) = windows.get_mut(window_id) {
+        window
+    } else {
+        world.get_resource::<BackendErrorPolicy>().unwrap().handle(
+            window_id,
+            WindowCommandError::UnknownWindow,
+            &mut world
+                .get_resource_mut::<Events<WindowCommandFailed>>()
+                .unwrap(),
         );
-        windows.add(window);
-        window_created_events.send(WindowCreated {
-            id: create_window_event.id,
+        return Dispatched::Continue;
+    };
+
+    let mut window_stats = world.get_resource_mut::<WindowStats>().unwrap();
+    let mut transport = world.get_resource_mut::<WinitTransport>();
+    let coordinate_convention = world
+        .get_resource::<WindowCoordinateConvention>()
+        .map_or_else(Default::default, |convention| *convention);
+    let buffers = &mut running.event_buffers;
+
+    for e in events {
+        if !window.is_enabled() && e.is_input() {
+            continue;
+        }
+
+        // events of low priority windows wait for the next regular update
+        if !matches!(e, WinitWindowEvent::None) && !window.is_low_priority() {
+            running.update_requested = true;
+        }
+
+        let mut stats = window_stats.get_mut(window_id);
+        if let Some(stats) = stats.as_mut() {
+            stats.record_event_from_backend();
+        }
+        if let Some(transport) = transport.as_mut() {
+            transport.record(|| TransportEvent::from_window_event(window_id, &e));
+        }
+
+        match e {
+            WinitWindowEvent::Resized(size) => {
+                // the corrected size is reported right away, so the app never sees the one that
+                // breaks the lock
+                let size = winit_windows
+                    .enforce_size_lock(
+                        window_id,
+                        PhysicalSize::new(window.physical_width(), window.physical_height()),
+                    )
+                    .unwrap_or(size);
+                if let Some(stats) = stats {
+                    stats.record_resize_from_backend();
+                }
+                // minimizing is only reported as a zero size on some platforms
+                let minimized = size.width == 0 || size.height == 0;
+                let suppressed =
+                    minimized && running.config.zero_size_policy == ZeroSizePolicy::Suppress;
+                let was_zero_sized = window.physical_width() == 0 || window.physical_height() == 0;
+                if !suppressed {
+                    window.update_actual_size_from_backend(size.width, size.height);
+                }
+                if suppressed {
+                    trace!("Suppressed zero-sized resize of {}", window_id);
+                } else if minimized && !was_zero_sized {
+                    buffers.surface_lost.push(SurfaceLost {
+                        id: window_id,
+                        reason: SurfaceChangeReason::ZeroSize,
+                    });
+                } else if !minimized && was_zero_sized {
+                    buffers.surface_recreated.push(SurfaceRecreated {
+                        id: window_id,
+                        reason: SurfaceChangeReason::ZeroSize,
+                    });
+                }
+                // a window stays maximized while it is minimized
+                let maximized = if minimized {
+                    window.is_maximized()
+                } else {
+                    winit_windows
+                        .get_window(window_id)
+                        .map_or(false, |winit_window| winit_window.is_maximized())
+                };
+                let change = window_state_change(
+                    (window.is_minimized(), window.is_maximized()),
+                    (minimized, maximized),
+                );
+                match change {
+                    Some(WindowStateChange::Minimized) => {
+                        buffers.minimized.push(WindowMinimized { id: window_id })
+                    }
+                    Some(WindowStateChange::Maximized) => {
+                        buffers.maximized.push(WindowMaximized { id: window_id })
+                    }
+                    Some(WindowStateChange::Restored) => {
+                        buffers.restored.push(WindowRestored { id: window_id })
+                    }
+                    None => {}
+                }
+                window.update_minimized_from_backend(minimized);
+                window.update_maximized_from_backend(maximized);
+                winit_windows.update_geometry(window);
+                if !suppressed {
+                    buffers.resized.push(WindowResized {
+                        id: window_id,
+                        width: window.width(),
+                        height: window.height(),
+                    });
+                }
+            }
+            WinitWindowEvent::CloseRequested => {
+                let now = Instant::now();
+                if let Some(last) = running.last_close_requests.insert(window_id, now) {
+                    if now.duration_since(last) < running.config.close_request_debounce {
+                        trace!("Dropped duplicate close request for {}", window_id);
+                        if let Some(mut stats) = world.get_resource_mut::<WinitChannelStats>() {
+                            stats.dropped_events += 1;
+                        }
+                        continue;
+                    }
+                }
+
+                if running.config.close_when_requested {
+                    window.close();
+                }
+                buffers
+                    .close_requested
+                    .push(WindowCloseRequested { id: window_id })
+            }
+            WinitWindowEvent::KeyboardInput(input) => buffers.keyboard_input.push(input),
+            WinitWindowEvent::TextPasted(text) => buffers.text_pasted.push(TextPasted {
+                id: window_id,
+                text,
+            }),
+            WinitWindowEvent::ModifiersChanged(modifiers) => {
+                buffers.keyboard_modifiers.push(KeyboardModifiers {
+                    state: converters::convert_modifiers_state(modifiers),
+                })
+            }
+            WinitWindowEvent::CursorMoved(position, moved_at) => {
+                if let Some(delta) = winit_windows.locked_cursor_motion(window_id, position) {
+                    if delta != Vec2::ZERO {
+                        buffers.mouse_motion.push(MouseMotion { delta });
+                    }
+                    continue;
+                }
+
+                let winit_window = winit_windows.get_window(window_id).unwrap();
+                let inner_size = winit_window
+                    .inner_size()
+                    .to_logical::<f32>(winit_window.scale_factor());
+                let position = converters::convert_window_position(
+                    position.to_logical(winit_window.scale_factor()),
+                    inner_size.height,
+                    coordinate_convention,
+                );
+                window.update_cursor_position_from_backend(Some(position));
+                if running.config.cursor_prediction.is_some() {
+                    running
+                        .cursor_predictors
+                        .entry(window_id)
+                        .or_default()
+                        .push(moved_at, position);
+                }
+
+                buffers.cursor_moved.push(CursorMoved {
+                    id: window_id,
+                    position,
+                });
+            }
+            WinitWindowEvent::CursorEntered => {
+                buffers.cursor_entered.push(CursorEntered { id: window_id })
+            }
+            WinitWindowEvent::CursorLeft => {
+                running.cursor_predictors.remove(&window_id);
+                window.update_predicted_cursor_position_from_backend(None);
+                buffers.cursor_left.push(CursorLeft { id: window_id });
+            }
+            WinitWindowEvent::MouseInput(input) => buffers.mouse_button_input.push(input),
+            WinitWindowEvent::MouseWheel(input) => buffers.mouse_wheel.push(input),
+            WinitWindowEvent::Touch(touch, received_at) => {
+                let winit_window = winit_windows.get_window(window_id).unwrap();
+                // winit reports touches with the origin at the top left on every platform
+                let position = converters::convert_window_position(
+                    touch.location.to_logical(winit_window.scale_factor()),
+                    window.height(),
+                    coordinate_convention,
+                );
+                let touch_input =
+                    converters::convert_touch_input(touch, window_id, position, received_at);
+                buffers.touch_input.push(touch_input);
+                buffers.pen_input.extend(PenInput::from_touch(&touch_input));
+            }
+            WinitWindowEvent::TouchesCancelled => buffers
+                .touches_cancelled
+                .push(TouchesCancelled { window: window_id }),
+            WinitWindowEvent::ReceivedCharacter(c) => {
+                buffers.received_character.push(ReceivedCharacter {
+                    id: window_id,
+                    char: c,
+                });
+            }
+            WinitWindowEvent::ScaleFactorChanged(scale_factor, new_inner_size) => {
+                buffers
+                    .backend_scale_factor_changed
+                    .push(WindowBackendScaleFactorChanged {
+                        id: window_id,
+                        scale_factor,
+                    });
+
+                let old_logical_size = (window.width(), window.height());
+
+                #[allow(clippy::float_cmp)]
+                if window.scale_factor() != scale_factor {
+                    buffers.scale_factor_changed.push(WindowScaleFactorChanged {
+                        id: window_id,
+                        scale_factor,
+                    });
+                }
+                let ratio = scale_factor.max(window.scale_factor())
+                    / scale_factor.min(window.scale_factor());
+                if ratio >= SURFACE_RECREATION_SCALE_RATIO
+                    && new_inner_size.width != 0
+                    && new_inner_size.height != 0
+                {
+                    buffers.surface_recreated.push(SurfaceRecreated {
+                        id: window_id,
+                        reason: SurfaceChangeReason::ScaleFactorChanged,
+                    });
+                }
+
+                window.update_scale_factor_from_backend(scale_factor);
+
+                let physical_size_changed = window.physical_width() != new_inner_size.width
+                    || window.physical_height() != new_inner_size.height;
+                window.update_actual_size_from_backend(new_inner_size.width, new_inner_size.height);
+                #[allow(clippy::float_cmp)]
+                let logical_size_changed = old_logical_size != (window.width(), window.height());
+
+                if physical_size_changed {
+                    if let Some(stats) = stats {
+                        stats.record_resize_from_backend();
+                    }
+                }
+                if physical_size_changed
+                    || (running.config.resize_on_scale_change && logical_size_changed)
+                {
+                    buffers.resized.push(WindowResized {
+                        id: window_id,
+                        width: window.width(),
+                        height: window.height(),
+                    });
+                }
+            }
+            WinitWindowEvent::Focused(focused) => {
+                if let Some(stats) = stats {
+                    stats.record_focus_from_backend(focused);
+                }
+                window.update_focused_status_from_backend(focused);
+                let cursor_captured =
+                    !window.cursor_visible() || window.cursor_grab_mode() != CursorGrabMode::None;
+                match running.config.cursor_focus_policy {
+                    CursorFocusPolicy::Keep => {}
+                    _ if !cursor_captured => {}
+                    CursorFocusPolicy::Release if !focused => {
+                        winit_windows.release_cursor(window_id);
+                        window.update_cursor_visible_from_backend(true);
+                        window.update_cursor_grab_mode_from_backend(CursorGrabMode::None);
+                    }
+                    CursorFocusPolicy::Release => {}
+                    CursorFocusPolicy::ReleaseAndRestore => {
+                        if focused {
+                            winit_windows.restore_cursor(window);
+                        } else {
+                            winit_windows.release_cursor(window_id);
+                        }
+                    }
+                }
+                world
+                    .get_resource_mut::<FocusedWindow>()
+                    .unwrap()
+                    .update_from_backend(window_id, focused);
+                buffers.focused.push(WindowFocused {
+                    id: window_id,
+                    focused,
+                });
+            }
+            WinitWindowEvent::DroppedFile(path_buf) => {
+                buffers
+                    .file_drag_and_drop
+                    .push(FileDragAndDrop::DroppedFile {
+                        id: window_id,
+                        path_buf,
+                    });
+            }
+            WinitWindowEvent::HoveredFile(path_buf) => {
+                buffers
+                    .file_drag_and_drop
+                    .push(FileDragAndDrop::HoveredFile {
+                        id: window_id,
+                        path_buf,
+                    });
+            }
+            WinitWindowEvent::HoveredFileCancelled => {
+                buffers
+                    .file_drag_and_drop
+                    .push(FileDragAndDrop::HoveredFileCancelled { id: window_id });
+            }
+            WinitWindowEvent::Moved(position) => {
+                let position = ivec2(position.x, position.y);
+                window.update_actual_position_from_backend(position);
+                winit_windows.update_geometry(window);
+                buffers.moved.push(WindowMoved {
+                    id: window_id,
+                    position,
+                });
+            }
+            WinitWindowEvent::ThemeChanged(theme) => {
+                window.update_theme_from_backend(Some(theme));
+                buffers.theme_changed.push(WindowThemeChanged {
+                    id: window_id,
+                    theme,
+                });
+            }
+            WinitWindowEvent::RedrawRequested => buffers
+                .redraw_requested
+                .push(WindowRedrawRequested { id: window_id }),
+            WinitWindowEvent::SafeAreaChanged(insets) => {
+                if insets != window.safe_area_insets() {
+                    window.update_safe_area_insets_from_backend(insets);
+                    buffers.safe_area_changed.push(SafeAreaChanged {
+                        id: window_id,
+                        insets,
+                    });
+                }
+            }
+            WinitWindowEvent::None => (),
+        }
+    }
+    if window.is_low_priority() {
+        running.coalesced_events.coalesce(window_id, buffers);
+    } else {
+        running.coalesced_events.flush_window(&world, window_id);
+    }
+    buffers.flush(&world);
+    Dispatched::Continue
+}
+
+/// Whether an app knows the winit window `winit_window_id`.
+fn is_known_window(apps: &[RunningApp], winit_window_id: WindowId) -> bool {
+    apps.iter().any(|running| {
+        running
+            .app
+            .world
+            .get_resource::<WinitWindows>()
+            .map_or(false, |windows| {
+                windows.get_window_id(winit_window_id).is_some()
+            })
+    })
+}
+
+/// An event that the backend sends for a change of the minimized or maximized state of a
+/// window.
+#[derive(Debug, PartialEq, Eq)]
+enum WindowStateChange {
+    Minimized,
+    Maximized,
+    Restored,
+}
+
+/// The change between the `(minimized, maximized)` state a window was in and the one it is in
+/// now. Leaving the minimized state restores the window, even when it is still maximized.
+fn window_state_change(
+    (was_minimized, was_maximized): (bool, bool),
+    (minimized, maximized): (bool, bool),
+) -> Option<WindowStateChange> {
+    if minimized {
+        (!was_minimized).then_some(WindowStateChange::Minimized)
+    } else if was_minimized || (was_maximized && !maximized) {
+        Some(WindowStateChange::Restored)
+    } else {
+        (maximized && !was_maximized).then_some(WindowStateChange::Maximized)
+    }
+}
+
+/// Appends the events injected through the [WinitTransport] of each app to `events`.
+fn take_injected_events(apps: &mut [RunningApp], events: &mut Vec<WinitEvent>) {
+    for running in apps.iter_mut() {
+        let world = running.app.world.cell();
+        let (mut transport, winit_windows) = match (
+            world.get_resource_mut::<WinitTransport>(),
+            world.get_resource::<WinitWindows>(),
+        ) {
+            (Some(transport), Some(winit_windows)) => (transport, winit_windows),
+            _ => continue,
+        };
+        for injected in transport.take_injected() {
+            match injected.clone().into_winit_event(&winit_windows) {
+                Some(e) => events.push(e),
+                None => warn!("Dropped injected event {:?}", injected),
+            }
+        }
+    }
+}
+
+/// Inserts the resources through which the apps talk to the thread of the event loop, and
+/// returns the ends that the [EventConverter] of that thread needs.
+fn connect_apps(
+    apps: &mut [RunningApp],
+) -> (
+    Vec<raw_input::RawInputSender<KeyboardInput>>,
+    mpsc::Receiver<clipboard::ClipboardRequest>,
+    EventMasks,
+    safe_area::SafeAreaViews,
+) {
+    let (clipboard_request_sender, clipboard_request_receiver) = mpsc::channel();
+    let event_masks = EventMasks::default();
+    let safe_area_views = safe_area::SafeAreaViews::default();
+
+    let mut keyboard_input_senders = Vec::new();
+    for running in apps.iter_mut() {
+        running
+            .app
+            .world
+            .insert_resource(Clipboard::new(clipboard_request_sender.clone()));
+        let raw_input = running.config.raw_input_capacity.map(|capacity| {
+            let (keyboard_input_sender, keyboard_input_channel) =
+                RawInputChannel::<KeyboardInput>::with_capacity(capacity);
+            let windows = keyboard_input_sender.windows();
+            keyboard_input_senders.push(keyboard_input_sender);
+            running.app.world.insert_resource(keyboard_input_channel);
+            windows
         });
+        if let Some(mut winit_windows) = running.app.world.get_resource_mut::<WinitWindows>() {
+            winit_windows.event_masks = event_masks.clone();
+            winit_windows.safe_area_views = safe_area_views.clone();
+            if let Some(raw_input_windows) = raw_input {
+                winit_windows.raw_input_windows = raw_input_windows;
+            }
+        }
+    }
+    (
+        keyboard_input_senders,
+        clipboard_request_receiver,
+        event_masks,
+        safe_area_views,
+    )
+}
+
+fn get_monitors(event_loop: &EventLoopWindowTarget<()>) -> Vec<Monitor> {
+    let primary_monitor = event_loop.primary_monitor();
+    event_loop
+        .available_monitors()
+        .map(|monitor| {
+            let is_primary = primary_monitor.as_ref() == Some(&monitor);
+            converters::convert_monitor(&monitor, is_primary)
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+fn report_backend_failure(app: &mut App, error: WinitBackendError) {
+    error!("Could not start a windowing backend: {}", error);
+    app.world
+        .get_resource_mut::<Events<WinitBackendFailed>>()
+        .unwrap()
+        .send(WinitBackendFailed { error });
+    app.update();
+}
+
+/// The runner state of an app driven by [winit_runner_multi].
+struct RunningApp {
+    app: App,
+    config: WinitConfig,
+    create_window_event_reader: ManualEventReader<CreateWindow>,
+    app_exit_event_reader: ManualEventReader<AppExit>,
+    restart_backend_event_reader: ManualEventReader<RestartBackend>,
+    frame_count: u64,
+    last_update: Option<Instant>,
+    update_requested: bool,
+    main_events_cleared: bool,
+    /// Whether the app has windows but all of them are hidden or minimized.
+    hidden: bool,
+    /// Whether [SurfaceLost] events were sent because the app was suspended.
+    surfaces_suspended: bool,
+    cursor_predictors: HashMap<bevy_window::WindowId, CursorPredictor>,
+    last_close_requests: HashMap<bevy_window::WindowId, Instant>,
+    event_buffers: WindowEventBuffers,
+    coalesced_events: CoalescedWindowEvents,
+}
+
+impl RunningApp {
+    fn new(mut app: App) -> Self {
+        let config = app
+            .world
+            .get_resource::<WinitConfig>()
+            .cloned()
+            .unwrap_or_default();
+        if let Some(mut winit_windows) = app.world.get_resource_mut::<WinitWindows>() {
+            winit_windows.emulate_cursor_lock = config.emulate_cursor_lock;
+        }
+        RunningApp {
+            app,
+            config,
+            create_window_event_reader: Default::default(),
+            app_exit_event_reader: Default::default(),
+            restart_backend_event_reader: Default::default(),
+            frame_count: 0,
+            last_update: None,
+            update_requested: false,
+            main_events_cleared: false,
+            hidden: false,
+            surfaces_suspended: false,
+            cursor_predictors: Default::default(),
+            last_close_requests: Default::default(),
+            event_buffers: Default::default(),
+            coalesced_events: Default::default(),
+        }
+    }
+
+    fn exit_requested(&mut self) -> bool {
+        match self.app.world.get_resource_mut::<Events<AppExit>>() {
+            Some(app_exit_events) => self
+                .app_exit_event_reader
+                .iter(&app_exit_events)
+                .next_back()
+                .is_some(),
+            None => false,
+        }
+    }
+
+    /// Sends [SurfaceLost] events for all windows when the app is suspended, and
+    /// [SurfaceRecreated] events once it is resumed. Resuming without a prior suspension, like
+    /// at startup, does not invalidate any surface.
+    fn update_surfaces_for_lifecycle(&mut self, lifecycle: ApplicationLifecycle) {
+        let suspended = match lifecycle {
+            ApplicationLifecycle::Suspended => true,
+            ApplicationLifecycle::Resumed => false,
+            ApplicationLifecycle::MemoryWarning => return,
+        };
+        if suspended == self.surfaces_suspended {
+            return;
+        }
+        self.surfaces_suspended = suspended;
+        let world = self.app.world.cell();
+        let windows = world.get_resource::<Windows>().unwrap();
+        let reason = SurfaceChangeReason::Suspended;
+        if suspended {
+            let mut surface_lost_events = world.get_resource_mut::<Events<SurfaceLost>>().unwrap();
+            for window in windows.iter() {
+                surface_lost_events.send(SurfaceLost {
+                    id: window.id(),
+                    reason,
+                });
+            }
+        } else {
+            let mut surface_recreated_events = world
+                .get_resource_mut::<Events<SurfaceRecreated>>()
+                .unwrap();
+            for window in windows.iter() {
+                surface_recreated_events.send(SurfaceRecreated {
+                    id: window.id(),
+                    reason,
+                });
+            }
+        }
+    }
+
+    fn restart_requested(&mut self) -> bool {
+        match self.app.world.get_resource_mut::<Events<RestartBackend>>() {
+            Some(restart_backend_events) => self
+                .restart_backend_event_reader
+                .iter(&restart_backend_events)
+                .next_back()
+                .is_some(),
+            None => false,
+        }
+    }
+
+    /// Destroys all backend windows and queues [CreateWindow] events that recreate them in their
+    /// current state once the next event loop is running.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    fn tear_down_windows(&mut self) {
+        let world = self.app.world.cell();
+        let mut winit_windows = world.get_resource_mut::<WinitWindows>().unwrap();
+        let mut windows = world.get_resource_mut::<Windows>().unwrap();
+        let mut window_stats = world.get_resource_mut::<WindowStats>().unwrap();
+        let mut window_closed_events = world.get_resource_mut::<Events<WindowClosed>>().unwrap();
+        let mut create_window_events = world.get_resource_mut::<Events<CreateWindow>>().unwrap();
+
+        let ids = windows.iter().map(|window| window.id()).collect::<Vec<_>>();
+        for id in ids {
+            let descriptor = windows.remove(id).unwrap().descriptor();
+            // keeps the entity of the window for its recreation
+            winit_windows.remove_window(id);
+            window_stats.remove(id);
+            window_closed_events.send(WindowClosed { id });
+            create_window_events.send(CreateWindow { id, descriptor });
+        }
+    }
+
+    /// The update mode that applies to this app in its current state.
+    fn update_mode(&self, update_mode: UpdateMode, hidden_update_mode: UpdateMode) -> UpdateMode {
+        if self.hidden {
+            hidden_update_mode
+        } else {
+            update_mode
+        }
+    }
+
+    /// How long until the app has to be updated even without new events, if ever.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    fn time_until_due(&self, update_mode: UpdateMode) -> Option<Duration> {
+        let until_max_wait = match (update_mode.max_wait(), self.last_update) {
+            (Some(max_wait), Some(last_update)) => Some(
+                max_wait
+                    .checked_sub(last_update.elapsed())
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        };
+        let until_title_restore = self
+            .next_title_restore()
+            .map(|restore_at| restore_at.saturating_duration_since(Instant::now()));
+        until_max_wait.into_iter().chain(until_title_restore).min()
+    }
+
+    /// When the title of a window that shows an announcement has to be restored, which happens
+    /// with the next update.
+    fn next_title_restore(&self) -> Option<Instant> {
+        self.app
+            .world
+            .get_resource::<WinitWindows>()?
+            .next_title_restore()
+    }
+
+    fn should_update(&self, update_mode: UpdateMode) -> bool {
+        if self
+            .next_title_restore()
+            .map_or(false, |restore_at| restore_at <= Instant::now())
+        {
+            return true;
+        }
+        match (update_mode.max_wait(), self.last_update) {
+            (Some(max_wait), Some(last_update)) => {
+                self.update_requested || last_update.elapsed() >= max_wait
+            }
+            _ => self.main_events_cleared,
+        }
+    }
+
+    /// When the [frame rate limit](WinitConfig::frame_rate_limit) allows the next update.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    fn next_frame_at(&self) -> Option<Instant> {
+        frame_pacing::next_frame_at(self.config.frame_rate_limit, self.last_update?)
+    }
+
+    /// How long until an update that the frame rate limit holds back is close enough to be
+    /// waited for by spinning, if the app has to be updated.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    fn time_until_frame(&self, update_mode: UpdateMode) -> Option<Duration> {
+        if !self.should_update(update_mode) {
+            return None;
+        }
+        self.next_frame_at().map(frame_pacing::time_until_spin)
+    }
+
+    fn update(&mut self, elwt: &EventLoopWindowTarget<()>) {
+        self.update_requested = false;
+        self.main_events_cleared = false;
+        self.last_update = Some(Instant::now());
+        {
+            let world = self.app.world.cell();
+            let mut winit_windows = world.get_resource_mut::<WinitWindows>().unwrap();
+            let windows = world.get_resource::<Windows>().unwrap();
+            winit_windows.restore_announced_titles(&windows, Instant::now());
+        }
+        create_windows::<WinitWindows>(
+            &mut self.app.world,
+            elwt,
+            &mut self.create_window_event_reader,
+            self.frame_count,
+        );
+        report_creation_errors(&mut self.app.world);
+        self.app
+            .world
+            .resource_scope(|world, mut winit_windows: Mut<WinitWindows>| {
+                winit_windows.spawn_window_entities(world)
+            });
+        update_raw_window_handles(&mut self.app.world);
+        if self
+            .app
+            .world
+            .get_resource::<Windows>()
+            .unwrap()
+            .iter()
+            .next()
+            .is_some()
+        {
+            self.record_startup_step(StartupStep::FirstWindowCreated, Instant::now());
+        }
+        if let Some(lookahead) = self.config.cursor_prediction {
+            let at = Instant::now() + lookahead;
+            let mut windows = self.app.world.get_resource_mut::<Windows>().unwrap();
+            for (id, predictor) in self.cursor_predictors.iter() {
+                if let Some(window) = windows.get_mut(*id) {
+                    let size = Vec2::new(window.width(), window.height());
+                    window
+                        .update_predicted_cursor_position_from_backend(predictor.predict(at, size));
+                }
+            }
+        }
+        self.coalesced_events.flush(&self.app.world.cell());
+        EventFrames::record(&mut self.app.world, self.frame_count);
+        self.app.update();
+        // events sent by the app itself belong to the frame they were sent in
+        EventFrames::record(&mut self.app.world, self.frame_count);
+        self.frame_count += 1;
+        if let Some(mut stats) = self.app.world.get_resource_mut::<WinitChannelStats>() {
+            *stats = WinitChannelStats::default();
+        }
+
+        let windows = self.app.world.get_resource::<Windows>().unwrap();
+        self.hidden = windows.iter().next().is_some()
+            && windows
+                .iter()
+                .all(|window| !window.is_visible() || window.is_minimized());
+    }
+
+    fn record_startup_step(&mut self, step: StartupStep, at: Instant) {
+        let world = self.app.world.cell();
+        let mut timings = match world.get_resource_mut::<WinitStartupTimings>() {
+            Some(timings) => timings,
+            None => return,
+        };
+        timings.record(step, at);
+        if timings.take_completed() {
+            info!(
+                "Backend startup: event loop proxy after {:?}, first MainEventsCleared after {:?}, \
+                 first window after {:?}",
+                timings.proxy_created.unwrap_or_default(),
+                timings.first_main_events_cleared.unwrap_or_default(),
+                timings.first_window_created.unwrap_or_default(),
+            );
+            world
+                .get_resource_mut::<Events<WinitStartupCompleted>>()
+                .unwrap()
+                .send(WinitStartupCompleted {
+                    timings: timings.clone(),
+                });
+        }
+    }
+
+    /// Drops everything that may still refer to the backend windows (e.g. render surfaces)
+    /// before the windows themselves, and both before the event loop is torn down.
+    fn shut_down(mut self) {
+        let winit_windows = self.app.world.remove_resource::<WinitWindows>();
+        drop(self.app);
+        drop(winit_windows);
     }
 }
 
 enum WinitEvent {
     WindowEvent(WinitWindowEvent, WindowId),
     MouseMotion(MouseMotion),
+    DeviceEvent(WinitDeviceEvent),
     MainEventsCleared(usize),
-    CreatedProxy(EventLoopProxy<()>),
+    CreatedProxy(EventLoopProxy<()>, Instant),
+    Monitors(Vec<Monitor>),
+    ClipboardChanged,
+    Lifecycle(ApplicationLifecycle),
+    Gamepad(GamepadEventRaw),
+    SystemInfo(SystemInfo),
+    SystemPreferences(SystemPreferences),
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    BackendFailed(String),
+    None,
+}
+
+impl WinitEvent {
+    /// The winit window of a window event.
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    fn window_id(&self) -> Option<WindowId> {
+        match self {
+            WinitEvent::WindowEvent(_, winit_window_id) => Some(*winit_window_id),
+            _ => None,
+        }
+    }
+}
+
+impl WinitWindowEvent {
+    /// Whether this is user input, which a disabled window does not receive. Releases are not,
+    /// a disabled window still has to learn about them.
+    fn is_input(&self) -> bool {
+        !self.is_release()
+            && matches!(
+                self,
+                WinitWindowEvent::KeyboardInput(_)
+                    | WinitWindowEvent::TextPasted(_)
+                    | WinitWindowEvent::CursorMoved(..)
+                    | WinitWindowEvent::MouseInput(_)
+                    | WinitWindowEvent::MouseWheel(_)
+                    | WinitWindowEvent::Touch(..)
+                    | WinitWindowEvent::ReceivedCharacter(_)
+                    | WinitWindowEvent::DroppedFile(_)
+                    | WinitWindowEvent::HoveredFile(_)
+            )
+    }
+
+    /// Whether this ends input that may have started before the window was masked or disabled,
+    /// so the app does not consider keys, buttons or touches held forever.
+    fn is_release(&self) -> bool {
+        match self {
+            WinitWindowEvent::KeyboardInput(input) => input.state == ElementState::Released,
+            WinitWindowEvent::MouseInput(input) => input.state == ElementState::Released,
+            WinitWindowEvent::Touch(touch, _) => {
+                matches!(touch.phase, TouchPhase::Ended | TouchPhase::Cancelled)
+            }
+            WinitWindowEvent::TouchesCancelled => true,
+            _ => false,
+        }
+    }
+
+    /// Whether a window with `mask` forwards this event to its app. Releases and modifier
+    /// changes are always forwarded, they keep the input state of the app up to date.
+    fn is_forwarded(&self, mask: &WindowEventMask) -> bool {
+        if self.is_release() {
+            return true;
+        }
+        match self {
+            WinitWindowEvent::CursorMoved(..)
+            | WinitWindowEvent::CursorEntered
+            | WinitWindowEvent::CursorLeft => mask.cursor,
+            WinitWindowEvent::MouseInput(_) | WinitWindowEvent::MouseWheel(_) => mask.mouse_buttons,
+            WinitWindowEvent::KeyboardInput(_)
+            | WinitWindowEvent::TextPasted(_)
+            | WinitWindowEvent::ReceivedCharacter(_) => mask.keyboard,
+            WinitWindowEvent::Touch(..) | WinitWindowEvent::TouchesCancelled => mask.touch,
+            WinitWindowEvent::DroppedFile(_)
+            | WinitWindowEvent::HoveredFile(_)
+            | WinitWindowEvent::HoveredFileCancelled => mask.file_drop,
+            _ => true,
+        }
+    }
+}
+
+enum WinitDeviceEvent {
+    Added(DeviceId),
+    Removed(DeviceId),
+    Button(RawMouseButtonInput),
+    Key(RawKeyboardInput),
+    MouseWheel(RawMouseWheel),
     None,
 }
 
@@ -623,12 +1810,15 @@ enum WinitWindowEvent {
     Resized(PhysicalSize<u32>),
     CloseRequested,
     KeyboardInput(KeyboardInput),
-    CursorMoved(PhysicalPosition<f64>),
+    ModifiersChanged(event::ModifiersState),
+    TextPasted(String),
+    CursorMoved(PhysicalPosition<f64>, Instant),
     CursorEntered,
     CursorLeft,
     MouseInput(MouseButtonInput),
     MouseWheel(MouseWheel),
-    Touch(Touch),
+    Touch(Touch, Instant),
+    TouchesCancelled,
     ReceivedCharacter(char),
     ScaleFactorChanged(f64, PhysicalSize<u32>),
     Focused(bool),
@@ -636,5 +1826,77 @@ enum WinitWindowEvent {
     HoveredFile(PathBuf),
     HoveredFileCancelled,
     Moved(PhysicalPosition<i32>),
+    ThemeChanged(WindowTheme),
+    RedrawRequested,
+    SafeAreaChanged(SafeAreaInsets),
     None,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaving_minimized_restores_maximized_windows() {
+        let normal = (false, false);
+        let maximized = (false, true);
+        // minimized windows keep their maximized state
+        let minimized_from_maximized = (true, true);
+
+        assert_eq!(
+            window_state_change(normal, maximized),
+            Some(WindowStateChange::Maximized)
+        );
+        assert_eq!(
+            window_state_change(maximized, minimized_from_maximized),
+            Some(WindowStateChange::Minimized)
+        );
+        assert_eq!(
+            window_state_change(minimized_from_maximized, maximized),
+            Some(WindowStateChange::Restored)
+        );
+        assert_eq!(
+            window_state_change(maximized, normal),
+            Some(WindowStateChange::Restored)
+        );
+        assert_eq!(window_state_change(maximized, maximized), None);
+        assert_eq!(
+            window_state_change(minimized_from_maximized, minimized_from_maximized),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+    fn app_exit_leaves_the_event_loop() {
+        let mut apps = vec![
+            RunningApp::new(App::build().app),
+            RunningApp::new(App::build().app),
+        ];
+        let send_app_exit = |running: &mut RunningApp| {
+            running
+                .app
+                .world
+                .get_resource_mut::<Events<AppExit>>()
+                .unwrap()
+                .send(AppExit);
+        };
+
+        shut_down_exited_apps(&mut apps);
+        assert_eq!(apps.len(), 2);
+        send_app_exit(&mut apps[0]);
+        shut_down_exited_apps(&mut apps);
+        assert_eq!(apps.len(), 1);
+        send_app_exit(&mut apps[0]);
+        shut_down_exited_apps(&mut apps);
+        assert!(apps.is_empty());
+
+        // the winit thread leaves its event loop on the first event that sees the request
+        let exit_signal = ExitSignal::default();
+        let (_winit_event_sender, winit_event_receiver) = mpsc::channel();
+        request_event_loop_exit(&exit_signal, None, &winit_event_receiver);
+        assert!(exit_signal.is_requested());
+        // only the first request wakes up the event loop
+        assert!(!exit_signal.request());
+    }
+}
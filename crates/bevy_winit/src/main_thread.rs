@@ -0,0 +1,122 @@
+//! The runner for the web, where the event loop has to run on the only thread there is, and for
+//! iOS, where UIKit requires it to run on the main thread.
+
+use crate::{
+    connect_apps, dispatch_event, gamepads, is_known_window, take_injected_events,
+    unknown_windows::UnknownWindowEvents, window_capabilities, Dispatched, EventConverter,
+    RunningApp, WinitBackend, WinitConfig, WinitEvent,
+};
+use bevy_utils::{tracing::warn, Instant};
+use winit::event_loop::{ControlFlow, EventLoop};
+
+/// Runs the apps on an event loop on the current thread. Events are converted by the same
+/// [EventConverter] as in the threaded runner, but are dispatched to the apps right away, and
+/// the apps are updated once the event loop has handled all pending events.
+///
+/// This never returns, winit hands the event loop over to the browser or to UIKit.
+pub(crate) fn run_single_threaded(mut apps: Vec<RunningApp>, mut config: WinitConfig) -> ! {
+    let update_mode = config.update_mode;
+    let hidden_update_mode = config.hidden_update_mode.unwrap_or(update_mode);
+
+    for running in apps.iter_mut() {
+        let capabilities =
+            window_capabilities(WinitBackend::Native, running.config.emulate_cursor_lock);
+        running.app.world.insert_resource(WinitBackend::Native);
+        running.app.world.insert_resource(capabilities);
+    }
+    let event_loop = EventLoop::new();
+    let (keyboard_input_senders, clipboard_request_receiver, event_masks, safe_area_views) =
+        connect_apps(&mut apps);
+    config.poll_gamepads &=
+        !gamepads::polled_by_apps(apps.iter().map(|running| &running.app.world));
+    let mut converter = EventConverter::new(
+        &config,
+        keyboard_input_senders,
+        clipboard_request_receiver,
+        event_masks,
+        safe_area_views,
+        event_loop.create_proxy(),
+    );
+    #[cfg(target_os = "ios")]
+    {
+        use winit::platform::ios::EventLoopExtIOS;
+
+        converter.device_idiom = crate::converters::convert_idiom(event_loop.idiom());
+        crate::ios::observe_memory_warnings();
+    }
+
+    let mut event_loop_proxy = None;
+    let mut unknown_window_events = UnknownWindowEvents::new(config.unknown_window_events);
+    dispatch_event(
+        &mut apps,
+        WinitEvent::CreatedProxy(event_loop.create_proxy(), Instant::now()),
+        update_mode,
+        hidden_update_mode,
+        &mut event_loop_proxy,
+        &mut unknown_window_events,
+    );
+
+    let mut converted = Vec::new();
+    event_loop.run(move |event, event_loop, control_flow| {
+        let all_hidden = !apps.is_empty() && apps.iter().all(|running| running.hidden);
+        let max_wait = if all_hidden {
+            hidden_update_mode.max_wait()
+        } else {
+            update_mode.max_wait()
+        };
+        *control_flow = converter.control_flow(max_wait);
+
+        unknown_window_events.take_known(|id| is_known_window(&apps, id), &mut converted);
+        take_injected_events(&mut apps, &mut converted);
+        converter.convert(event, event_loop, &mut converted);
+        let mut main_events_cleared = false;
+        for e in converted.drain(..) {
+            match dispatch_event(
+                &mut apps,
+                e,
+                update_mode,
+                hidden_update_mode,
+                &mut event_loop_proxy,
+                &mut unknown_window_events,
+            ) {
+                Dispatched::Continue => {}
+                Dispatched::MainEventsCleared(_) => main_events_cleared = true,
+            }
+        }
+        if !main_events_cleared {
+            return;
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        for running in apps.iter_mut() {
+            let world = running.app.world.cell();
+            if let (Some(winit_windows), Some(windows)) = (
+                world.get_resource::<crate::WinitWindows>(),
+                world.get_resource::<bevy_window::Windows>(),
+            ) {
+                winit_windows.fit_canvases_to_parents(&windows);
+            };
+        }
+
+        for running in apps.iter_mut() {
+            if running.should_update(running.update_mode(update_mode, hidden_update_mode)) {
+                running.update(event_loop);
+            }
+        }
+
+        let mut index = 0;
+        while index < apps.len() {
+            if apps[index].exit_requested() {
+                apps.remove(index).shut_down();
+            } else {
+                if apps[index].restart_requested() {
+                    warn!("Restarting the windowing backend is not supported on this platform");
+                }
+                index += 1;
+            }
+        }
+        if apps.is_empty() {
+            *control_flow = ControlFlow::Exit;
+        }
+    })
+}
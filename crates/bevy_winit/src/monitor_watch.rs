@@ -0,0 +1,69 @@
+use bevy_utils::{Duration, Instant};
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event_loop::EventLoopWindowTarget,
+};
+
+/// The properties of a monitor that are compared to notice changes. Cheap to query, unlike the
+/// full [Monitor](bevy_window::Monitor) with its video modes.
+#[derive(Debug, Clone, PartialEq)]
+struct MonitorSnapshot {
+    name: Option<String>,
+    size: PhysicalSize<u32>,
+    position: PhysicalPosition<i32>,
+    scale_factor: f64,
+}
+
+/// Polls the monitor setup on the winit thread, since winit 0.25 does not report resolution
+/// changes or connected monitors that no window is on.
+pub(crate) struct MonitorWatcher {
+    interval: Option<Duration>,
+    next_poll: Instant,
+    snapshot: Option<Vec<MonitorSnapshot>>,
+}
+
+impl MonitorWatcher {
+    pub fn new(interval: Option<Duration>) -> Self {
+        MonitorWatcher {
+            interval,
+            next_poll: Instant::now(),
+            snapshot: None,
+        }
+    }
+
+    /// When the event loop has to wake up for the next poll, if polling is enabled.
+    pub fn next_poll(&self) -> Option<Instant> {
+        self.interval.map(|_| self.next_poll)
+    }
+
+    /// Takes a new snapshot if the interval has elapsed. Returns `true` if the monitors changed
+    /// since the previous snapshot, the first snapshot is not reported.
+    pub fn poll(&mut self, event_loop: &EventLoopWindowTarget<()>, now: Instant) -> bool {
+        let interval = match self.interval {
+            Some(interval) if now >= self.next_poll => interval,
+            _ => return false,
+        };
+        self.next_poll = now + interval;
+        self.update(event_loop)
+    }
+
+    /// Takes a new snapshot right away, e.g. after the full monitors were sent for another
+    /// reason. Returns `true` if the monitors changed since the previous snapshot.
+    pub fn update(&mut self, event_loop: &EventLoopWindowTarget<()>) -> bool {
+        let snapshot = event_loop
+            .available_monitors()
+            .map(|monitor| MonitorSnapshot {
+                name: monitor.name(),
+                size: monitor.size(),
+                position: monitor.position(),
+                scale_factor: monitor.scale_factor(),
+            })
+            .collect::<Vec<_>>();
+        let changed = self
+            .snapshot
+            .as_ref()
+            .map_or(false, |previous| *previous != snapshot);
+        self.snapshot = Some(snapshot);
+        changed
+    }
+}
@@ -0,0 +1,80 @@
+//! The few parts of the Objective-C runtime that the backend needs where winit 0.25 does not
+//! expose a platform feature, shared by the iOS and macOS code.
+
+use std::{ffi::c_void, mem, os::raw::c_char};
+
+pub(crate) type Id = *mut c_void;
+pub(crate) type Sel = *const c_void;
+
+#[cfg(target_pointer_width = "64")]
+pub(crate) type CGFloat = f64;
+#[cfg(not(target_pointer_width = "64"))]
+pub(crate) type CGFloat = f32;
+
+#[link(name = "objc", kind = "dylib")]
+extern "C" {
+    pub(crate) fn objc_getClass(name: *const c_char) -> Id;
+    pub(crate) fn objc_allocateClassPair(
+        superclass: Id,
+        name: *const c_char,
+        extra_bytes: usize,
+    ) -> Id;
+    pub(crate) fn objc_registerClassPair(class: Id);
+    pub(crate) fn class_addMethod(
+        class: Id,
+        name: Sel,
+        imp: *const c_void,
+        types: *const c_char,
+    ) -> bool;
+    fn sel_registerName(name: *const c_char) -> Sel;
+    pub(crate) fn objc_msgSend();
+    #[cfg(target_arch = "x86_64")]
+    fn objc_msgSend_stret();
+}
+
+/// The selector named `name`, which has to end with a nul byte.
+pub(crate) fn selector(name: &[u8]) -> Sel {
+    unsafe { sel_registerName(name.as_ptr() as *const c_char) }
+}
+
+/// Four `CGFloat`s, the layout of both `UIEdgeInsets` and `CGRect`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Quad(pub CGFloat, pub CGFloat, pub CGFloat, pub CGFloat);
+
+/// Sends `selector` to `receiver`, which returns an object.
+///
+/// # Safety
+///
+/// `receiver` has to be an object that responds to `selector` with an object, and `selector`
+/// has to end with a nul byte.
+pub(crate) unsafe fn send_id(receiver: Id, selector: &[u8]) -> Id {
+    let send: unsafe extern "C" fn(Id, Sel) -> Id =
+        mem::transmute(objc_msgSend as unsafe extern "C" fn());
+    send(receiver, self::selector(selector))
+}
+
+/// Sends `selector` to `receiver`, which returns a [Quad]. Such structs are returned in
+/// registers on arm64, but through memory on x86_64.
+///
+/// # Safety
+///
+/// `receiver` has to be an object that responds to `selector` with a [Quad], and `selector`
+/// has to end with a nul byte.
+pub(crate) unsafe fn send_quad(receiver: Id, selector: &[u8]) -> Quad {
+    let selector = self::selector(selector);
+    #[cfg(target_arch = "x86_64")]
+    {
+        let send: unsafe extern "C" fn(*mut Quad, Id, Sel) =
+            mem::transmute(objc_msgSend_stret as unsafe extern "C" fn());
+        let mut quad = Quad::default();
+        send(&mut quad, receiver, selector);
+        quad
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let send: unsafe extern "C" fn(Id, Sel) -> Quad =
+            mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        send(receiver, selector)
+    }
+}
@@ -0,0 +1,154 @@
+use bevy_utils::{Duration, HashSet, Instant};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Condvar, Mutex, RwLock},
+};
+use winit::window::WindowId;
+
+/// The windows of an app that has a [RawInputChannel], registered by its
+/// [WinitWindows](crate::WinitWindows) so the winit thread only sends it the input of its own
+/// windows.
+pub(crate) type RawInputWindows = Arc<RwLock<HashSet<WindowId>>>;
+
+/// Input sent straight from the winit thread as soon as the OS reports it, for consumers that
+/// need it sooner than the next update, like rhythm games that poll it from their own thread.
+/// Inserted as a resource by the runner of the [WinitPlugin](crate::WinitPlugin) for
+/// [KeyboardInput](bevy_input::keyboard::KeyboardInput) if
+/// [WinitConfig::raw_input_capacity](crate::WinitConfig::raw_input_capacity) is set.
+///
+/// Only the input of the windows of the app is sent to its channel. Every input is also sent as
+/// an event with the next update. The channel does not replace
+/// those events: it receives input before window event masks, disabled windows and simulated
+/// latency apply. It keeps at most the configured number of inputs, and drops the oldest one
+/// when a new input arrives while it is full.
+pub struct RawInputChannel<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The winit thread's end of a [RawInputChannel].
+pub(crate) struct RawInputSender<T> {
+    shared: Arc<Shared<T>>,
+    windows: RawInputWindows,
+}
+
+struct Shared<T> {
+    queue: Mutex<VecDeque<T>>,
+    available: Condvar,
+    capacity: usize,
+}
+
+impl<T> RawInputChannel<T> {
+    /// A channel that keeps at most `capacity` inputs, at least one.
+    pub(crate) fn with_capacity(capacity: usize) -> (RawInputSender<T>, Self) {
+        let capacity = capacity.max(1);
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(VecDeque::with_capacity(capacity)),
+            available: Condvar::new(),
+            capacity,
+        });
+        (
+            RawInputSender {
+                shared: shared.clone(),
+                windows: RawInputWindows::default(),
+            },
+            RawInputChannel { shared },
+        )
+    }
+
+    /// The oldest input that was not taken yet, without waiting.
+    pub fn try_recv(&self) -> Option<T> {
+        self.shared.queue.lock().unwrap().pop_front()
+    }
+
+    /// Waits up to `timeout` for the next input.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        let mut queue = self.shared.queue.lock().unwrap();
+        loop {
+            if let Some(input) = queue.pop_front() {
+                return Some(input);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            queue = self
+                .shared
+                .available
+                .wait_timeout(queue, deadline - now)
+                .unwrap()
+                .0;
+        }
+    }
+
+    /// Takes all input that was not taken yet, in the order it was reported, without waiting.
+    pub fn drain(&self) -> Vec<T> {
+        self.shared.queue.lock().unwrap().drain(..).collect()
+    }
+}
+
+impl<T> RawInputSender<T> {
+    /// The windows whose input is sent to this channel.
+    pub(crate) fn windows(&self) -> RawInputWindows {
+        self.windows.clone()
+    }
+
+    pub(crate) fn is_for(&self, window_id: WindowId) -> bool {
+        self.windows.read().unwrap().contains(&window_id)
+    }
+
+    pub(crate) fn send(&self, input: T) {
+        let mut queue = self.shared.queue.lock().unwrap();
+        if queue.len() >= self.shared.capacity {
+            queue.pop_front();
+        }
+        queue.push_back(input);
+        self.shared.available.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drains_in_order() {
+        let (sender, channel) = RawInputChannel::with_capacity(8);
+        assert_eq!(channel.try_recv(), None);
+        sender.send(1);
+        sender.send(2);
+        sender.send(3);
+        assert_eq!(channel.try_recv(), Some(1));
+        assert_eq!(channel.drain(), [2, 3]);
+        assert_eq!(channel.recv_timeout(Duration::from_millis(1)), None);
+    }
+
+    #[test]
+    fn drops_the_oldest_input_when_full() {
+        let (sender, channel) = RawInputChannel::with_capacity(2);
+        sender.send(1);
+        sender.send(2);
+        sender.send(3);
+        assert_eq!(channel.drain(), [2, 3]);
+    }
+
+    #[test]
+    fn only_takes_input_of_its_windows() {
+        let (sender, _channel) = RawInputChannel::<u32>::with_capacity(1);
+        let window_id = unsafe { WindowId::dummy() };
+        assert!(!sender.is_for(window_id));
+        sender.windows().write().unwrap().insert(window_id);
+        assert!(sender.is_for(window_id));
+    }
+
+    #[test]
+    fn wakes_waiting_receivers() {
+        let (sender, channel) = RawInputChannel::with_capacity(1);
+        let thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(10));
+            sender.send(1);
+        });
+        assert_eq!(channel.recv_timeout(Duration::from_secs(5)), Some(1));
+        thread.join().unwrap();
+    }
+}
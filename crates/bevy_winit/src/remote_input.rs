@@ -0,0 +1,320 @@
+//! Remote control of an app over TCP, for automated UI tests and tools that drive the app from
+//! another process. Requires the `remote_input` feature.
+//!
+//! Clients send [RemoteMessage]s as JSON, one per line. Their events are injected through the
+//! [WinitTransport] as if the OS had sent them. With [RemoteInputConfig::mirror_events], the
+//! events dispatched to the app are sent back to every client in the same format.
+
+use crate::{TransportEvent, WinitTransport, TRANSPORT_SCHEMA_VERSION};
+use bevy_app::{AppBuilder, CoreStage, Plugin};
+use bevy_ecs::system::{IntoSystem, Res, ResMut};
+use bevy_utils::{
+    tracing::{error, info, warn},
+    Duration,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+
+/// How long the writer thread of a client waits for it to read, before it drops the client.
+const MIRROR_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How many updates worth of mirrored events are queued for a client that does not keep up,
+/// before it is dropped.
+const MIRROR_QUEUE_CAPACITY: usize = 64;
+
+/// Accepts [RemoteMessage]s on a TCP socket and injects their events into the app. Add it
+/// after the [WinitPlugin](crate::WinitPlugin), and configure it by inserting a
+/// [RemoteInputConfig] before.
+///
+/// Injected events are dispatched with the next batch of events from the winit thread, so in a
+/// reactive [UpdateMode](crate::UpdateMode) they wait until the app wakes up for another reason.
+#[derive(Default)]
+pub struct RemoteInputPlugin;
+
+impl Plugin for RemoteInputPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let config = app
+            .world()
+            .get_resource::<RemoteInputConfig>()
+            .cloned()
+            .unwrap_or_default();
+        let remote_input = match RemoteInput::listen(&config.address) {
+            Ok(remote_input) => remote_input,
+            Err(e) => {
+                error!(
+                    "Failed to listen for remote input on {}: {}",
+                    config.address, e
+                );
+                return;
+            }
+        };
+        info!("Listening for remote input on {}", remote_input.local_addr);
+
+        if config.mirror_events {
+            app.world_mut()
+                .get_resource_mut::<WinitTransport>()
+                .expect("the RemoteInputPlugin requires the WinitPlugin")
+                .set_recording(true);
+            app.add_system_to_stage(CoreStage::Last, mirror_events_system.system());
+        }
+        app.insert_resource(remote_input)
+            .add_system_to_stage(CoreStage::First, inject_remote_events_system.system());
+    }
+}
+
+/// Settings of the [RemoteInputPlugin].
+#[derive(Debug, Clone)]
+pub struct RemoteInputConfig {
+    /// The address to listen on. Only the local machine can connect by default; anyone who can
+    /// connect can control the app.
+    pub address: String,
+    /// Sends the events dispatched to the app to the clients. This takes the recorded events
+    /// of the [WinitTransport], so [WinitTransport::take_recorded] returns nothing.
+    pub mirror_events: bool,
+}
+
+impl Default for RemoteInputConfig {
+    fn default() -> Self {
+        RemoteInputConfig {
+            address: "127.0.0.1:7190".to_string(),
+            mirror_events: false,
+        }
+    }
+}
+
+/// A line of the remote input protocol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteMessage {
+    /// The [TRANSPORT_SCHEMA_VERSION] of the event. Messages of other versions are dropped.
+    pub version: u32,
+    pub event: TransportEvent,
+}
+
+/// The connections of the [RemoteInputPlugin].
+pub struct RemoteInput {
+    local_addr: SocketAddr,
+    received: Mutex<mpsc::Receiver<TransportEvent>>,
+    /// The queues of the writer threads of the clients.
+    clients: Arc<Mutex<Vec<mpsc::SyncSender<Arc<str>>>>>,
+}
+
+impl RemoteInput {
+    /// The address the plugin listens on, e.g. to find the port when listening on port 0.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    fn listen(address: &str) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let local_addr = listener.local_addr()?;
+        let (sender, received) = mpsc::channel();
+        let clients = Arc::new(Mutex::new(Vec::new()));
+
+        let accepted_clients = clients.clone();
+        thread::Builder::new()
+            .name("remote input".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    let stream = match stream {
+                        Ok(stream) => stream,
+                        Err(e) => {
+                            warn!("Failed to accept a remote input client: {}", e);
+                            continue;
+                        }
+                    };
+                    if let Ok(writer) = stream.try_clone() {
+                        let _ = writer.set_write_timeout(Some(MIRROR_WRITE_TIMEOUT));
+                        let (lines_sender, lines) = mpsc::sync_channel(MIRROR_QUEUE_CAPACITY);
+                        let spawned = thread::Builder::new()
+                            .name("remote input writer".to_string())
+                            .spawn(move || write_messages(writer, lines));
+                        if spawned.is_ok() {
+                            accepted_clients.lock().unwrap().push(lines_sender);
+                        }
+                    }
+                    let sender = sender.clone();
+                    let _ = thread::Builder::new()
+                        .name("remote input client".to_string())
+                        .spawn(move || read_messages(stream, sender));
+                }
+            })?;
+
+        Ok(RemoteInput {
+            local_addr,
+            received: Mutex::new(received),
+            clients,
+        })
+    }
+}
+
+/// Forwards the events of a client until it disconnects or the app is gone.
+fn read_messages(stream: TcpStream, sender: mpsc::Sender<TransportEvent>) {
+    for line in BufReader::new(stream).lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => return,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<RemoteMessage>(&line) {
+            Ok(message) if message.version == TRANSPORT_SCHEMA_VERSION => {
+                if sender.send(message.event).is_err() {
+                    return;
+                }
+            }
+            Ok(message) => warn!(
+                "Dropped remote input of schema version {}, expected {}",
+                message.version, TRANSPORT_SCHEMA_VERSION
+            ),
+            Err(e) => warn!("Dropped malformed remote input: {}", e),
+        }
+    }
+}
+
+/// Writes the mirrored events to a client until it stops reading or is dropped.
+fn write_messages(mut stream: TcpStream, lines: mpsc::Receiver<Arc<str>>) {
+    for lines in lines {
+        if stream.write_all(lines.as_bytes()).is_err() {
+            return;
+        }
+    }
+}
+
+fn inject_remote_events_system(
+    remote_input: Res<RemoteInput>,
+    mut transport: ResMut<WinitTransport>,
+) {
+    for event in remote_input.received.lock().unwrap().try_iter() {
+        transport.inject(event);
+    }
+}
+
+fn mirror_events_system(remote_input: Res<RemoteInput>, mut transport: ResMut<WinitTransport>) {
+    let events = transport.take_recorded();
+    let mut clients = remote_input.clients.lock().unwrap();
+    if events.is_empty() || clients.is_empty() {
+        return;
+    }
+
+    let mut lines = String::new();
+    for event in events {
+        let message = RemoteMessage {
+            version: TRANSPORT_SCHEMA_VERSION,
+            event,
+        };
+        match serde_json::to_string(&message) {
+            Ok(line) => {
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+            Err(e) => warn!("Failed to serialize {:?}: {}", message.event, e),
+        }
+    }
+    let lines = Arc::<str>::from(lines);
+    clients.retain(|client| match client.try_send(lines.clone()) {
+        Ok(()) => true,
+        Err(mpsc::TrySendError::Full(_)) => {
+            warn!("Dropped a remote input client that does not read the mirrored events");
+            false
+        }
+        Err(mpsc::TrySendError::Disconnected(_)) => false,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_utils::Instant;
+
+    #[test]
+    fn injects_received_events() {
+        let mut app = App::build();
+        app.init_resource::<WinitTransport>()
+            .insert_resource(RemoteInputConfig {
+                address: "127.0.0.1:0".to_string(),
+                mirror_events: false,
+            })
+            .add_plugin(RemoteInputPlugin);
+        let mut app = app.app;
+        let local_addr = app
+            .world
+            .get_resource::<RemoteInput>()
+            .unwrap()
+            .local_addr();
+
+        let mut client = TcpStream::connect(local_addr).unwrap();
+        let message = RemoteMessage {
+            version: TRANSPORT_SCHEMA_VERSION,
+            event: TransportEvent::MouseMotion {
+                delta_x: 1.0,
+                delta_y: -1.0,
+            },
+        };
+        writeln!(client, "{}", serde_json::to_string(&message).unwrap()).unwrap();
+        writeln!(client, "not a message").unwrap();
+
+        let started_at = Instant::now();
+        let injected = loop {
+            app.update();
+            let injected = app
+                .world
+                .get_resource_mut::<WinitTransport>()
+                .unwrap()
+                .take_injected();
+            if !injected.is_empty() || started_at.elapsed() > Duration::from_secs(5) {
+                break injected;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(injected, [message.event]);
+    }
+
+    #[test]
+    fn mirrors_events_to_clients() {
+        let mut app = App::build();
+        app.init_resource::<WinitTransport>()
+            .insert_resource(RemoteInputConfig {
+                address: "127.0.0.1:0".to_string(),
+                mirror_events: true,
+            })
+            .add_plugin(RemoteInputPlugin);
+        let mut app = app.app;
+        let local_addr = app
+            .world
+            .get_resource::<RemoteInput>()
+            .unwrap()
+            .local_addr();
+
+        let client = TcpStream::connect(local_addr).unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_millis(10)))
+            .unwrap();
+        let mut client = BufReader::new(client);
+        let event = TransportEvent::MouseMotion {
+            delta_x: 1.0,
+            delta_y: -1.0,
+        };
+
+        // the client is registered by the listener thread at some point after connecting
+        let started_at = Instant::now();
+        let mut line = String::new();
+        while client.read_line(&mut line).is_err() || line.is_empty() {
+            assert!(started_at.elapsed() < Duration::from_secs(5));
+            app.world
+                .get_resource_mut::<WinitTransport>()
+                .unwrap()
+                .record(|| Some(event.clone()));
+            app.update();
+        }
+        let message = serde_json::from_str::<RemoteMessage>(&line).unwrap();
+        assert_eq!(message.version, TRANSPORT_SCHEMA_VERSION);
+        assert_eq!(message.event, event);
+    }
+}
@@ -0,0 +1,117 @@
+//! Safe area insets, which winit 0.25 does not report. Where the platform has them, they are
+//! queried through the Objective-C runtime, on the thread of the event loop as UIKit and AppKit
+//! require.
+
+use bevy_utils::HashMap;
+use bevy_window::SafeAreaInsets;
+use std::sync::{Arc, RwLock};
+use winit::{event::WindowEvent, window::WindowId};
+
+/// The native views of the windows that have safe area insets, registered by the
+/// [WinitWindows](crate::WinitWindows) of the apps and queried by the thread of the event loop.
+/// A view is removed before its window is destroyed.
+pub(crate) type SafeAreaViews = Arc<RwLock<HashMap<WindowId, usize>>>;
+
+/// The view whose insets are queried, the `UIView` of a window on iOS.
+#[cfg(target_os = "ios")]
+pub(crate) fn native_view(window: &winit::window::Window) -> Option<usize> {
+    use winit::platform::ios::WindowExtIOS;
+
+    Some(window.ui_view() as usize)
+}
+
+/// The view whose insets are queried, the `NSWindow` of a window on macOS.
+#[cfg(target_os = "macos")]
+pub(crate) fn native_view(window: &winit::window::Window) -> Option<usize> {
+    use winit::platform::macos::WindowExtMacOS;
+
+    Some(window.ns_window() as usize)
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+pub(crate) fn native_view(_window: &winit::window::Window) -> Option<usize> {
+    None
+}
+
+/// Reports the safe area insets of the windows when they change. Lives on the thread of the
+/// event loop.
+pub(crate) struct SafeAreaWatcher {
+    views: SafeAreaViews,
+    reported: HashMap<WindowId, SafeAreaInsets>,
+}
+
+impl SafeAreaWatcher {
+    pub fn new(views: SafeAreaViews) -> Self {
+        SafeAreaWatcher {
+            views,
+            reported: HashMap::default(),
+        }
+    }
+
+    /// The insets of the window of `event` if they changed, checked for the first event of each
+    /// window and whenever it is resized.
+    pub fn poll(&mut self, window_id: WindowId, event: &WindowEvent) -> Option<SafeAreaInsets> {
+        match event {
+            WindowEvent::Destroyed => {
+                self.reported.remove(&window_id);
+                return None;
+            }
+            WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {}
+            _ if self.reported.contains_key(&window_id) => return None,
+            _ => {}
+        }
+        // the lock keeps the window from being destroyed while it is queried
+        let views = self.views.read().unwrap();
+        let insets = unsafe { query_insets(*views.get(&window_id)?) };
+        let previous = self.reported.insert(window_id, insets);
+        (previous != Some(insets)).then_some(insets)
+    }
+}
+
+/// The insets of the notch, the rounded corners and the home indicator.
+///
+/// # Safety
+///
+/// `view` has to be the `UIView` of an existing window, and this has to be called on the main
+/// thread.
+#[cfg(target_os = "ios")]
+unsafe fn query_insets(view: usize) -> SafeAreaInsets {
+    use crate::objc;
+
+    // top, left, bottom, right in points, which are logical pixels
+    let objc::Quad(top, left, bottom, right) =
+        objc::send_quad(view as objc::Id, b"safeAreaInsets\0");
+    SafeAreaInsets {
+        top: top as f32,
+        left: left as f32,
+        bottom: bottom as f32,
+        right: right as f32,
+    }
+}
+
+/// The inset of the titlebar, if the content extends below it with
+/// [MacOsWindowConfig::fullsize_content_view](bevy_window::MacOsWindowConfig::fullsize_content_view).
+///
+/// # Safety
+///
+/// `view` has to be the `NSWindow` of an existing window, and this has to be called on the main
+/// thread.
+#[cfg(target_os = "macos")]
+unsafe fn query_insets(view: usize) -> SafeAreaInsets {
+    use crate::objc;
+
+    let ns_window = view as objc::Id;
+    // x, y, width, height in points, with the origin at the bottom left of the content view
+    let objc::Quad(_, y, _, height) = objc::send_quad(ns_window, b"contentLayoutRect\0");
+    let objc::Quad(_, _, _, content_height) =
+        objc::send_quad(objc::send_id(ns_window, b"contentView\0"), b"frame\0");
+    SafeAreaInsets {
+        top: (content_height - (y + height)).max(0.0) as f32,
+        ..Default::default()
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+unsafe fn query_insets(_view: usize) -> SafeAreaInsets {
+    SafeAreaInsets::default()
+}
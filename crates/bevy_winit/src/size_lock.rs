@@ -0,0 +1,107 @@
+use bevy_math::Vec2;
+
+/// Resize increments and an aspect ratio that the runner enforces by resizing a window back
+/// whenever it is resized to a size that breaks them. winit 0.25 can only set increments when
+/// a window is created, on X11 and macOS, and cannot lock the aspect ratio at all.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct SizeLock {
+    /// In logical pixels, `0` leaves an axis free.
+    pub increments: Vec2,
+    /// Width divided by height.
+    pub aspect_ratio: Option<f32>,
+}
+
+impl SizeLock {
+    pub fn is_unlocked(&self) -> bool {
+        self.increments == Vec2::ZERO && self.aspect_ratio.is_none()
+    }
+
+    /// The size closest to `size` that keeps the lock and stays within `min` and `max`, all in
+    /// logical pixels. The aspect ratio wins over the increments, and the limits win over both.
+    /// It follows the side that changed from `previous`, so dragging either edge of the window
+    /// resizes it.
+    pub fn apply(&self, size: Vec2, previous: Vec2, min: Vec2, max: Vec2) -> Vec2 {
+        let mut size = size;
+        for axis in 0..2 {
+            let increment = self.increments[axis];
+            if increment > 0.0 {
+                size[axis] = ((size[axis] / increment).round() * increment).max(increment);
+            }
+        }
+        if let Some(ratio) = self.aspect_ratio.filter(|ratio| *ratio > 0.0) {
+            if size.x == previous.x && size.y != previous.y {
+                size.x = size.y * ratio;
+            } else {
+                size.y = size.x / ratio;
+            }
+            // scales both sides, so the aspect ratio is kept if the limits allow it
+            let grow = (min / size).max_element().max(1.0);
+            let shrink = (max / size).min_element().min(1.0);
+            size *= if grow > 1.0 { grow } else { shrink };
+        }
+        size.max(min).min(max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unbounded() -> Vec2 {
+        Vec2::splat(f32::INFINITY)
+    }
+
+    #[test]
+    fn keeps_increments_and_aspect_ratio() {
+        let increments = SizeLock {
+            increments: Vec2::new(16.0, 0.0),
+            aspect_ratio: None,
+        };
+        assert_eq!(
+            increments.apply(Vec2::new(100.0, 75.0), Vec2::ZERO, Vec2::ONE, unbounded()),
+            Vec2::new(96.0, 75.0)
+        );
+
+        let aspect_ratio = SizeLock {
+            increments: Vec2::ZERO,
+            aspect_ratio: Some(2.0),
+        };
+        let previous = Vec2::new(200.0, 100.0);
+        assert_eq!(
+            aspect_ratio.apply(Vec2::new(300.0, 100.0), previous, Vec2::ONE, unbounded()),
+            Vec2::new(300.0, 150.0)
+        );
+        assert_eq!(
+            aspect_ratio.apply(Vec2::new(200.0, 150.0), previous, Vec2::ONE, unbounded()),
+            Vec2::new(300.0, 150.0)
+        );
+    }
+
+    #[test]
+    fn stays_within_the_limits() {
+        let aspect_ratio = SizeLock {
+            increments: Vec2::ZERO,
+            aspect_ratio: Some(2.0),
+        };
+        let previous = Vec2::new(200.0, 100.0);
+        let min = Vec2::new(100.0, 100.0);
+        let max = Vec2::new(400.0, 400.0);
+        assert_eq!(
+            aspect_ratio.apply(Vec2::new(500.0, 100.0), previous, min, max),
+            Vec2::new(400.0, 200.0)
+        );
+        assert_eq!(
+            aspect_ratio.apply(Vec2::new(150.0, 100.0), previous, min, max),
+            Vec2::new(200.0, 100.0)
+        );
+
+        let increments = SizeLock {
+            increments: Vec2::new(64.0, 64.0),
+            aspect_ratio: None,
+        };
+        assert_eq!(
+            increments.apply(Vec2::new(390.0, 90.0), previous, min, max),
+            Vec2::new(384.0, 100.0)
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use bevy_utils::{Duration, Instant};
+
+/// How long the winit backend took to start, measured from when the
+/// [WinitPlugin](crate::WinitPlugin) was built. Helps to tell whether a slow startup is spent
+/// on the winit thread or in the app.
+///
+/// A step that has not happened yet is `None`. The timings are logged and sent with a
+/// [WinitStartupCompleted] event once all steps are done.
+#[derive(Debug, Clone)]
+pub struct WinitStartupTimings {
+    plugin_built: Instant,
+    /// Until the winit thread created its event loop and the proxy to wake it.
+    pub proxy_created: Option<Duration>,
+    /// Until the first `MainEventsCleared` arrived from the winit thread.
+    pub first_main_events_cleared: Option<Duration>,
+    /// Until the first window was created.
+    pub first_window_created: Option<Duration>,
+    reported: bool,
+}
+
+/// An event that is sent once all [WinitStartupTimings] have been measured.
+#[derive(Debug, Clone)]
+pub struct WinitStartupCompleted {
+    pub timings: WinitStartupTimings,
+}
+
+pub(crate) enum StartupStep {
+    ProxyCreated,
+    FirstMainEventsCleared,
+    FirstWindowCreated,
+}
+
+impl WinitStartupTimings {
+    pub(crate) fn new() -> Self {
+        WinitStartupTimings {
+            plugin_built: Instant::now(),
+            proxy_created: None,
+            first_main_events_cleared: None,
+            first_window_created: None,
+            reported: false,
+        }
+    }
+
+    /// Records when `step` happened, unless it was already recorded.
+    pub(crate) fn record(&mut self, step: StartupStep, at: Instant) {
+        let elapsed = at.saturating_duration_since(self.plugin_built);
+        let slot = match step {
+            StartupStep::ProxyCreated => &mut self.proxy_created,
+            StartupStep::FirstMainEventsCleared => &mut self.first_main_events_cleared,
+            StartupStep::FirstWindowCreated => &mut self.first_window_created,
+        };
+        slot.get_or_insert(elapsed);
+    }
+
+    /// Returns true the first time all steps are measured.
+    pub(crate) fn take_completed(&mut self) -> bool {
+        let completed = self.proxy_created.is_some()
+            && self.first_main_events_cleared.is_some()
+            && self.first_window_created.is_some();
+        if completed && !self.reported {
+            self.reported = true;
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,75 @@
+use bevy_utils::{Duration, Instant};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Lets the winit thread wait in its handler of `Event::Suspended` until the apps were updated
+/// with the suspension. On Android the native windows are destroyed once the handler returns,
+/// so renderers have to drop their surfaces before that.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SuspendSignal {
+    state: Arc<(Mutex<SuspendState>, Condvar)>,
+}
+
+#[derive(Debug, Default)]
+struct SuspendState {
+    requested: u64,
+    acknowledged: u64,
+}
+
+impl SuspendSignal {
+    /// Called by the winit thread before it sends the suspension to the app loop. Returns the
+    /// ticket to [wait](Self::wait) for.
+    pub fn request(&self) -> u64 {
+        let mut state = self.state.0.lock().unwrap();
+        state.requested += 1;
+        state.requested
+    }
+
+    /// Waits until the app loop has acknowledged the suspension of `ticket`, at most for
+    /// `timeout`. Returns whether it was acknowledged.
+    pub fn wait(&self, ticket: u64, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let (state, acknowledged) = &*self.state;
+        let mut state = state.lock().unwrap();
+        while state.acknowledged < ticket {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            state = acknowledged.wait_timeout(state, deadline - now).unwrap().0;
+        }
+        true
+    }
+
+    /// Called by the app loop once the apps were updated with all suspensions it received, or
+    /// when it exits.
+    pub fn acknowledge(&self) {
+        let (state, acknowledged) = &*self.state;
+        let mut state = state.lock().unwrap();
+        state.acknowledged = state.requested;
+        acknowledged.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SuspendSignal;
+    use bevy_utils::Duration;
+    use std::thread;
+
+    #[test]
+    fn waits_for_the_acknowledgement() {
+        let signal = SuspendSignal::default();
+        let ticket = signal.request();
+        assert!(!signal.wait(ticket, Duration::from_millis(1)));
+
+        let app_loop = {
+            let signal = signal.clone();
+            thread::spawn(move || signal.acknowledge())
+        };
+        assert!(signal.wait(ticket, Duration::from_secs(5)));
+        app_loop.join().unwrap();
+
+        let ticket = signal.request();
+        assert!(!signal.wait(ticket, Duration::from_millis(1)));
+    }
+}
@@ -0,0 +1,310 @@
+use bevy_window::{SystemInfo, SystemPreferences};
+
+/// Queries the accessibility preferences of the operating system. Preferences that cannot be
+/// queried on this platform keep their default.
+pub(crate) fn query_system_preferences() -> SystemPreferences {
+    let mut preferences = SystemPreferences::default();
+    platform::query_preferences(&mut preferences);
+    preferences
+}
+
+/// Queries the locale and user interface settings of the operating system. Settings that
+/// cannot be queried on this platform keep their default.
+pub(crate) fn query_system_info() -> SystemInfo {
+    let mut info = SystemInfo::default();
+    platform::query_info(&mut info);
+    info
+}
+
+/// Turns a POSIX locale like `en_US.UTF-8` into a language tag like `en-US`.
+#[cfg(any(
+    test,
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn posix_locale_to_tag(locale: &str) -> Option<String> {
+    let locale = locale.split(['.', '@']).next()?;
+    if locale.is_empty() || locale == "C" || locale == "POSIX" {
+        return None;
+    }
+    Some(locale.replace('_', "-"))
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::*;
+    use bevy_utils::Duration;
+    use bevy_window::{ColorScheme, ContrastPreference};
+    use std::{ffi::OsStr, iter, mem, os::windows::ffi::OsStrExt, ptr};
+    use winapi::{
+        shared::minwindef::{BOOL, DWORD, FALSE, UINT},
+        um::{
+            winnls::GetUserDefaultLocaleName,
+            winnt::LOCALE_NAME_MAX_LENGTH,
+            winreg::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD},
+            winuser::{
+                GetDoubleClickTime, SystemParametersInfoW, HCF_HIGHCONTRASTON, HIGHCONTRASTW,
+                SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST, SPI_GETWHEELSCROLLLINES,
+            },
+        },
+    };
+
+    fn to_wide(text: &str) -> Vec<u16> {
+        OsStr::new(text)
+            .encode_wide()
+            .chain(iter::once(0))
+            .collect()
+    }
+
+    fn read_registry_dword(key: &str, value: &str) -> Option<DWORD> {
+        let key = to_wide(key);
+        let value = to_wide(value);
+        let mut data: DWORD = 0;
+        let mut size = mem::size_of::<DWORD>() as DWORD;
+        let result = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                value.as_ptr(),
+                RRF_RT_REG_DWORD,
+                ptr::null_mut(),
+                &mut data as *mut DWORD as *mut _,
+                &mut size,
+            )
+        };
+        if result == 0 {
+            Some(data)
+        } else {
+            None
+        }
+    }
+
+    pub fn query_preferences(preferences: &mut SystemPreferences) {
+        unsafe {
+            let mut animations: BOOL = FALSE;
+            if SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                &mut animations as *mut BOOL as *mut _,
+                0,
+            ) != 0
+            {
+                preferences.reduced_motion = animations == FALSE;
+            }
+
+            let mut high_contrast: HIGHCONTRASTW = mem::zeroed();
+            high_contrast.cbSize = mem::size_of::<HIGHCONTRASTW>() as u32;
+            if SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                high_contrast.cbSize,
+                &mut high_contrast as *mut HIGHCONTRASTW as *mut _,
+                0,
+            ) != 0
+                && high_contrast.dwFlags & HCF_HIGHCONTRASTON != 0
+            {
+                preferences.contrast = ContrastPreference::More;
+            }
+        }
+
+        // set by "Make text bigger" in the accessibility settings, in percent
+        if let Some(text_scale) =
+            read_registry_dword("Software\\Microsoft\\Accessibility", "TextScaleFactor")
+        {
+            preferences.text_scale = text_scale as f32 / 100.0;
+        }
+    }
+
+    pub fn query_info(info: &mut SystemInfo) {
+        unsafe {
+            let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+            let len = GetUserDefaultLocaleName(name.as_mut_ptr(), name.len() as i32);
+            if len > 1 {
+                info.locale = Some(String::from_utf16_lossy(&name[..len as usize - 1]));
+            }
+
+            info.double_click_interval = Duration::from_millis(GetDoubleClickTime() as u64);
+
+            let mut scroll_lines: UINT = 0;
+            if SystemParametersInfoW(
+                SPI_GETWHEELSCROLLLINES,
+                0,
+                &mut scroll_lines as *mut UINT as *mut _,
+                0,
+            ) != 0
+            {
+                info.scroll_lines = scroll_lines;
+            }
+        }
+
+        if let Some(light) = read_registry_dword(
+            "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+            "AppsUseLightTheme",
+        ) {
+            info.color_scheme = match light {
+                0 => ColorScheme::Dark,
+                _ => ColorScheme::Light,
+            };
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+    use bevy_utils::Duration;
+    use bevy_window::{ColorScheme, ContrastPreference};
+
+    fn defaults_read(domain: &str, key: &str) -> Option<String> {
+        let output = std::process::Command::new("defaults")
+            .args(["read", domain, key])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn read_flag(key: &str) -> Option<bool> {
+        match defaults_read("com.apple.universalaccess", key)?.as_str() {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn query_preferences(preferences: &mut SystemPreferences) {
+        if let Some(reduce_motion) = read_flag("reduceMotion") {
+            preferences.reduced_motion = reduce_motion;
+        }
+        if read_flag("increaseContrast") == Some(true) {
+            preferences.contrast = ContrastPreference::More;
+        }
+    }
+
+    pub fn query_info(info: &mut SystemInfo) {
+        info.locale =
+            defaults_read("-g", "AppleLocale").and_then(|locale| posix_locale_to_tag(&locale));
+        // the key only exists while the dark appearance is active
+        info.color_scheme = match defaults_read("-g", "AppleInterfaceStyle").as_deref() {
+            Some("Dark") => ColorScheme::Dark,
+            _ => ColorScheme::Light,
+        };
+        if let Some(seconds) = defaults_read("-g", "com.apple.mouse.doubleClickThreshold")
+            .and_then(|value| value.parse::<f64>().ok())
+        {
+            info.double_click_interval = Duration::from_secs_f64(seconds);
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod platform {
+    use super::*;
+    use bevy_utils::Duration;
+    use bevy_window::{ColorScheme, ContrastPreference};
+
+    // there is no common API for these settings, but most desktops mirror them into the GNOME
+    // schemas
+    const INTERFACE: &str = "org.gnome.desktop.interface";
+
+    fn gsettings(schema: &str, key: &str) -> Option<String> {
+        let output = std::process::Command::new("gsettings")
+            .args(["get", schema, key])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub fn query_preferences(preferences: &mut SystemPreferences) {
+        if let Some(animations) = gsettings(INTERFACE, "enable-animations") {
+            preferences.reduced_motion = animations == "false";
+        }
+        let high_contrast = gsettings("org.gnome.desktop.a11y.interface", "high-contrast")
+            .map_or(false, |value| value == "true")
+            || gsettings(INTERFACE, "gtk-theme")
+                .map_or(false, |theme| theme.contains("HighContrast"));
+        if high_contrast {
+            preferences.contrast = ContrastPreference::More;
+        }
+        if let Some(text_scale) =
+            gsettings(INTERFACE, "text-scaling-factor").and_then(|value| value.parse().ok())
+        {
+            preferences.text_scale = text_scale;
+        }
+    }
+
+    pub fn query_info(info: &mut SystemInfo) {
+        info.locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| posix_locale_to_tag(&locale));
+
+        info.color_scheme = match gsettings(INTERFACE, "color-scheme").as_deref() {
+            Some("'prefer-dark'") => ColorScheme::Dark,
+            Some("'prefer-light'") => ColorScheme::Light,
+            // older desktops only have dark variants of themes
+            _ => match gsettings(INTERFACE, "gtk-theme") {
+                Some(theme) if theme.to_lowercase().contains("dark") => ColorScheme::Dark,
+                _ => ColorScheme::NoPreference,
+            },
+        };
+
+        // printed with the type, e.g. `int32 400`
+        if let Some(millis) = gsettings("org.gnome.desktop.peripherals.mouse", "double-click")
+            .and_then(|value| value.rsplit(' ').next()?.parse::<u64>().ok())
+        {
+            info.double_click_interval = Duration::from_millis(millis);
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+mod platform {
+    use super::*;
+
+    pub fn query_preferences(_preferences: &mut SystemPreferences) {}
+
+    pub fn query_info(_info: &mut SystemInfo) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posix_locales_become_language_tags() {
+        assert_eq!(posix_locale_to_tag("en_US.UTF-8").as_deref(), Some("en-US"));
+        assert_eq!(posix_locale_to_tag("de_DE@euro").as_deref(), Some("de-DE"));
+        assert_eq!(
+            posix_locale_to_tag("sr_RS.UTF-8@latin").as_deref(),
+            Some("sr-RS")
+        );
+        assert_eq!(posix_locale_to_tag("ja").as_deref(), Some("ja"));
+        assert_eq!(posix_locale_to_tag("C.UTF-8"), None);
+        assert_eq!(posix_locale_to_tag("POSIX"), None);
+        assert_eq!(posix_locale_to_tag(""), None);
+    }
+}
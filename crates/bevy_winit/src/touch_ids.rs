@@ -0,0 +1,141 @@
+use bevy_utils::{HashMap, HashSet};
+use std::{cmp::Reverse, collections::BinaryHeap};
+use winit::{
+    event::{DeviceId, TouchPhase},
+    window::WindowId,
+};
+
+/// Maps the touch ids reported by winit, which are backend-specific and may be reused by the
+/// system at any time, to small ids that stay the same for the whole lifetime of a touch.
+///
+/// An id is freed when its touch ends or is cancelled, and the lowest free id is handed out
+/// to the next touch, so ids stay compact and can be used as map keys by gesture code.
+#[derive(Default)]
+pub(crate) struct TouchIdAllocator {
+    active: HashMap<(DeviceId, u64), u64>,
+    free: BinaryHeap<Reverse<u64>>,
+    next: u64,
+}
+
+impl TouchIdAllocator {
+    /// Returns the stable id for the touch `id` of `device` in the given phase.
+    pub fn map(&mut self, device: DeviceId, id: u64, phase: TouchPhase) -> u64 {
+        let key = (device, id);
+        let stable_id = match self.active.get(&key) {
+            // a `Started` for a touch that never ended is treated as the same touch
+            Some(stable_id) => *stable_id,
+            None => {
+                let stable_id = self.allocate();
+                self.active.insert(key, stable_id);
+                stable_id
+            }
+        };
+
+        if matches!(phase, TouchPhase::Ended | TouchPhase::Cancelled) {
+            self.active.remove(&key);
+            self.free.push(Reverse(stable_id));
+        }
+        stable_id
+    }
+
+    fn allocate(&mut self) -> u64 {
+        match self.free.pop() {
+            Some(Reverse(id)) => id,
+            None => {
+                self.next += 1;
+                self.next - 1
+            }
+        }
+    }
+}
+
+/// Tracks the touches that are down on each window to notice when the system cancels all of
+/// them, rather than just one.
+#[derive(Default)]
+pub(crate) struct TouchCancellation {
+    windows: HashMap<WindowId, WindowTouches>,
+}
+
+#[derive(Default)]
+struct WindowTouches {
+    active: HashSet<u64>,
+    /// Whether any touch ended normally since the window last had no touches down.
+    any_ended: bool,
+}
+
+impl TouchCancellation {
+    /// Records the touch `id` on `window` in the given phase. Returns true if this cancelled
+    /// the last touch of the window and none of its touches ended normally.
+    pub fn process(&mut self, window: WindowId, id: u64, phase: TouchPhase) -> bool {
+        let touches = self.windows.entry(window).or_default();
+        match phase {
+            TouchPhase::Started | TouchPhase::Moved => {
+                touches.active.insert(id);
+                false
+            }
+            TouchPhase::Ended => {
+                touches.active.remove(&id);
+                if touches.active.is_empty() {
+                    self.windows.remove(&window);
+                } else {
+                    touches.any_ended = true;
+                }
+                false
+            }
+            TouchPhase::Cancelled => {
+                touches.active.remove(&id);
+                if !touches.active.is_empty() {
+                    return false;
+                }
+                let all_cancelled = !touches.any_ended;
+                self.windows.remove(&window);
+                all_cancelled
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_stable_and_recycled() {
+        let mut ids = TouchIdAllocator::default();
+        let device = unsafe { DeviceId::dummy() };
+
+        assert_eq!(ids.map(device, 100, TouchPhase::Started), 0);
+        assert_eq!(ids.map(device, 7, TouchPhase::Started), 1);
+        assert_eq!(ids.map(device, 100, TouchPhase::Moved), 0);
+        // a repeated start of a touch that did not end keeps its id
+        assert_eq!(ids.map(device, 7, TouchPhase::Started), 1);
+
+        assert_eq!(ids.map(device, 100, TouchPhase::Ended), 0);
+        assert_eq!(ids.map(device, 7, TouchPhase::Cancelled), 1);
+        // the system may reuse its ids, which are then new touches with the lowest free id
+        assert_eq!(ids.map(device, 7, TouchPhase::Started), 0);
+        assert_eq!(ids.map(device, 100, TouchPhase::Started), 1);
+        assert_eq!(ids.map(device, 3, TouchPhase::Started), 2);
+    }
+
+    #[test]
+    fn notices_when_all_touches_are_cancelled() {
+        let mut cancellation = TouchCancellation::default();
+        let window = unsafe { WindowId::dummy() };
+
+        assert!(!cancellation.process(window, 0, TouchPhase::Started));
+        assert!(!cancellation.process(window, 1, TouchPhase::Started));
+        assert!(!cancellation.process(window, 0, TouchPhase::Cancelled));
+        assert!(cancellation.process(window, 1, TouchPhase::Cancelled));
+
+        // a single cancelled touch among ones that ended is not a cancellation of all touches
+        assert!(!cancellation.process(window, 0, TouchPhase::Started));
+        assert!(!cancellation.process(window, 1, TouchPhase::Started));
+        assert!(!cancellation.process(window, 0, TouchPhase::Ended));
+        assert!(!cancellation.process(window, 1, TouchPhase::Cancelled));
+
+        // the window starts over once it has no touches down
+        assert!(!cancellation.process(window, 0, TouchPhase::Started));
+        assert!(cancellation.process(window, 0, TouchPhase::Cancelled));
+    }
+}
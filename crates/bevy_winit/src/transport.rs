@@ -0,0 +1,471 @@
+//! The public form of the events the winit thread sends to the apps, for tools that work with
+//! them outside of the app: recorders, replayers, remote input injectors and bridges to other
+//! processes. With the `serialize` feature, all types implement serde's `Serialize` and
+//! `Deserialize`.
+//!
+//! The schema is stable within a [TRANSPORT_SCHEMA_VERSION]: existing variants and fields are
+//! never changed or removed, a change that would do so bumps the version. It consists of the
+//! types of this module only, so changes to the input events of `bevy_input` do not change it,
+//! apart from the names of [KeyCode]. Events that only concern the runner itself, like the event
+//! loop proxy or frame boundaries, are not part of it.
+
+use crate::{WinitEvent, WinitWindowEvent, WinitWindows};
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    ElementState,
+};
+use bevy_math::Vec2;
+use bevy_utils::Instant;
+use bevy_window::{WindowId, WindowTheme};
+use std::path::PathBuf;
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{ModifiersState, TouchPhase},
+};
+
+/// The version of the schema of [TransportEvent]. Tools should refuse events of a version they
+/// do not know.
+pub const TRANSPORT_SCHEMA_VERSION: u32 = 2;
+
+/// An event as sent from the winit thread to an app.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransportEvent {
+    /// An event of the window with the id returned by [WindowId::to_u128].
+    Window {
+        window: u128,
+        event: TransportWindowEvent,
+    },
+    /// Raw mouse motion, not bound to a window.
+    MouseMotion { delta_x: f32, delta_y: f32 },
+}
+
+/// An event of a window. Positions and sizes are in physical pixels, with the origin at the top
+/// left, as reported by the OS.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransportWindowEvent {
+    Resized {
+        width: u32,
+        height: u32,
+    },
+    CloseRequested,
+    KeyboardInput(TransportKeyboardInput),
+    ModifiersChanged(TransportModifiers),
+    TextPasted(String),
+    CursorMoved {
+        x: f64,
+        y: f64,
+    },
+    CursorEntered,
+    CursorLeft,
+    MouseInput(TransportMouseButtonInput),
+    MouseWheel(TransportMouseWheel),
+    /// A touch, only recorded: injecting it is not supported, as winit has no way to create the
+    /// device of a touch.
+    Touch {
+        id: u64,
+        phase: TransportTouchPhase,
+        x: f64,
+        y: f64,
+    },
+    TouchesCancelled,
+    ReceivedCharacter(char),
+    ScaleFactorChanged {
+        scale_factor: f64,
+        width: u32,
+        height: u32,
+    },
+    Focused(bool),
+    DroppedFile(PathBuf),
+    HoveredFile(PathBuf),
+    HoveredFileCancelled,
+    Moved {
+        x: i32,
+        y: i32,
+    },
+    ThemeChanged {
+        dark: bool,
+    },
+    RedrawRequested,
+}
+
+/// A key press or release.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransportKeyboardInput {
+    pub scan_code: u32,
+    pub key_code: Option<KeyCode>,
+    pub pressed: bool,
+    /// Whether this press was generated by the key being held down.
+    pub repeat: bool,
+    /// The text the key press produced with the layout and modifiers at the time.
+    pub text: Option<String>,
+}
+
+/// Which modifier keys are held down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransportModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+/// A mouse button press or release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransportMouseButtonInput {
+    pub button: TransportMouseButton,
+    pub pressed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransportMouseButton {
+    Left,
+    Right,
+    Middle,
+    Other(u16),
+}
+
+/// A scroll of the mouse wheel, in lines or in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransportMouseWheel {
+    pub pixels: bool,
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransportTouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+/// Records and injects the [TransportEvent]s of an app. Inserted as a resource by the
+/// [WinitPlugin](crate::WinitPlugin).
+///
+/// Injected events are dispatched before the next update as if the OS had sent them, events of
+/// windows that do not exist (anymore) are dropped.
+#[derive(Debug, Default)]
+pub struct WinitTransport {
+    recording: bool,
+    recorded: Vec<TransportEvent>,
+    injected: Vec<TransportEvent>,
+}
+
+impl WinitTransport {
+    /// Starts or stops recording the events dispatched to the app.
+    pub fn set_recording(&mut self, recording: bool) {
+        self.recording = recording;
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Takes the events recorded since the last call, in the order they were dispatched.
+    pub fn take_recorded(&mut self) -> Vec<TransportEvent> {
+        std::mem::take(&mut self.recorded)
+    }
+
+    /// Dispatches `event` before the next update.
+    pub fn inject(&mut self, event: TransportEvent) {
+        self.injected.push(event);
+    }
+
+    pub(crate) fn record(&mut self, event: impl FnOnce() -> Option<TransportEvent>) {
+        if self.recording {
+            self.recorded.extend(event());
+        }
+    }
+
+    pub(crate) fn take_injected(&mut self) -> Vec<TransportEvent> {
+        std::mem::take(&mut self.injected)
+    }
+}
+
+impl TransportEvent {
+    pub(crate) fn from_mouse_motion(input: &MouseMotion) -> Self {
+        TransportEvent::MouseMotion {
+            delta_x: input.delta.x,
+            delta_y: input.delta.y,
+        }
+    }
+
+    pub(crate) fn from_window_event(window: WindowId, event: &WinitWindowEvent) -> Option<Self> {
+        let event = match event {
+            WinitWindowEvent::Resized(size) => TransportWindowEvent::Resized {
+                width: size.width,
+                height: size.height,
+            },
+            WinitWindowEvent::CloseRequested => TransportWindowEvent::CloseRequested,
+            WinitWindowEvent::KeyboardInput(input) => {
+                TransportWindowEvent::KeyboardInput(TransportKeyboardInput {
+                    scan_code: input.scan_code,
+                    key_code: input.key_code,
+                    pressed: input.state.is_pressed(),
+                    repeat: input.repeat,
+                    text: input.text.clone(),
+                })
+            }
+            WinitWindowEvent::ModifiersChanged(modifiers) => {
+                TransportWindowEvent::ModifiersChanged(TransportModifiers {
+                    shift: modifiers.shift(),
+                    ctrl: modifiers.ctrl(),
+                    alt: modifiers.alt(),
+                    logo: modifiers.logo(),
+                })
+            }
+            WinitWindowEvent::TextPasted(text) => TransportWindowEvent::TextPasted(text.clone()),
+            WinitWindowEvent::CursorMoved(position, _) => TransportWindowEvent::CursorMoved {
+                x: position.x,
+                y: position.y,
+            },
+            WinitWindowEvent::CursorEntered => TransportWindowEvent::CursorEntered,
+            WinitWindowEvent::CursorLeft => TransportWindowEvent::CursorLeft,
+            WinitWindowEvent::MouseInput(input) => {
+                TransportWindowEvent::MouseInput(TransportMouseButtonInput {
+                    button: match input.button {
+                        MouseButton::Left => TransportMouseButton::Left,
+                        MouseButton::Right => TransportMouseButton::Right,
+                        MouseButton::Middle => TransportMouseButton::Middle,
+                        MouseButton::Other(button) => TransportMouseButton::Other(button),
+                    },
+                    pressed: input.state.is_pressed(),
+                })
+            }
+            WinitWindowEvent::MouseWheel(input) => {
+                TransportWindowEvent::MouseWheel(TransportMouseWheel {
+                    pixels: input.unit == MouseScrollUnit::Pixel,
+                    x: input.x,
+                    y: input.y,
+                })
+            }
+            WinitWindowEvent::Touch(touch, _) => TransportWindowEvent::Touch {
+                id: touch.id,
+                phase: match touch.phase {
+                    TouchPhase::Started => TransportTouchPhase::Started,
+                    TouchPhase::Moved => TransportTouchPhase::Moved,
+                    TouchPhase::Ended => TransportTouchPhase::Ended,
+                    TouchPhase::Cancelled => TransportTouchPhase::Cancelled,
+                },
+                x: touch.location.x,
+                y: touch.location.y,
+            },
+            WinitWindowEvent::TouchesCancelled => TransportWindowEvent::TouchesCancelled,
+            WinitWindowEvent::ReceivedCharacter(c) => TransportWindowEvent::ReceivedCharacter(*c),
+            WinitWindowEvent::ScaleFactorChanged(scale_factor, size) => {
+                TransportWindowEvent::ScaleFactorChanged {
+                    scale_factor: *scale_factor,
+                    width: size.width,
+                    height: size.height,
+                }
+            }
+            WinitWindowEvent::Focused(focused) => TransportWindowEvent::Focused(*focused),
+            WinitWindowEvent::DroppedFile(path) => TransportWindowEvent::DroppedFile(path.clone()),
+            WinitWindowEvent::HoveredFile(path) => TransportWindowEvent::HoveredFile(path.clone()),
+            WinitWindowEvent::HoveredFileCancelled => TransportWindowEvent::HoveredFileCancelled,
+            WinitWindowEvent::Moved(position) => TransportWindowEvent::Moved {
+                x: position.x,
+                y: position.y,
+            },
+            WinitWindowEvent::ThemeChanged(theme) => TransportWindowEvent::ThemeChanged {
+                dark: *theme == WindowTheme::Dark,
+            },
+            WinitWindowEvent::RedrawRequested => TransportWindowEvent::RedrawRequested,
+            // queried from the platform rather than reported by it
+            WinitWindowEvent::SafeAreaChanged(_) | WinitWindowEvent::None => return None,
+        };
+        Some(TransportEvent::Window {
+            window: window.to_u128(),
+            event,
+        })
+    }
+
+    /// The event as the winit thread would have sent it, `None` if it cannot be injected.
+    pub(crate) fn into_winit_event(self, winit_windows: &WinitWindows) -> Option<WinitEvent> {
+        match self {
+            TransportEvent::Window { window, event } => {
+                let winit_window_id = winit_windows
+                    .window_id_to_winit
+                    .get(&WindowId::from_u128(window))?;
+                Some(WinitEvent::WindowEvent(
+                    event.into_window_event()?,
+                    *winit_window_id,
+                ))
+            }
+            TransportEvent::MouseMotion { delta_x, delta_y } => {
+                Some(WinitEvent::MouseMotion(MouseMotion {
+                    delta: Vec2::new(delta_x, delta_y),
+                }))
+            }
+        }
+    }
+}
+
+impl TransportWindowEvent {
+    fn into_window_event(self) -> Option<WinitWindowEvent> {
+        Some(match self {
+            TransportWindowEvent::Resized { width, height } => {
+                WinitWindowEvent::Resized(PhysicalSize::new(width, height))
+            }
+            TransportWindowEvent::CloseRequested => WinitWindowEvent::CloseRequested,
+            TransportWindowEvent::KeyboardInput(input) => {
+                WinitWindowEvent::KeyboardInput(KeyboardInput {
+                    scan_code: input.scan_code,
+                    key_code: input.key_code,
+                    state: element_state(input.pressed),
+                    repeat: input.repeat,
+                    text: input.text,
+                    received_at: None,
+                })
+            }
+            TransportWindowEvent::ModifiersChanged(state) => {
+                let mut modifiers = ModifiersState::empty();
+                modifiers.set(ModifiersState::SHIFT, state.shift);
+                modifiers.set(ModifiersState::CTRL, state.ctrl);
+                modifiers.set(ModifiersState::ALT, state.alt);
+                modifiers.set(ModifiersState::LOGO, state.logo);
+                WinitWindowEvent::ModifiersChanged(modifiers)
+            }
+            TransportWindowEvent::TextPasted(text) => WinitWindowEvent::TextPasted(text),
+            TransportWindowEvent::CursorMoved { x, y } => {
+                WinitWindowEvent::CursorMoved(PhysicalPosition::new(x, y), Instant::now())
+            }
+            TransportWindowEvent::CursorEntered => WinitWindowEvent::CursorEntered,
+            TransportWindowEvent::CursorLeft => WinitWindowEvent::CursorLeft,
+            TransportWindowEvent::MouseInput(input) => {
+                WinitWindowEvent::MouseInput(MouseButtonInput {
+                    button: match input.button {
+                        TransportMouseButton::Left => MouseButton::Left,
+                        TransportMouseButton::Right => MouseButton::Right,
+                        TransportMouseButton::Middle => MouseButton::Middle,
+                        TransportMouseButton::Other(button) => MouseButton::Other(button),
+                    },
+                    state: element_state(input.pressed),
+                    received_at: None,
+                })
+            }
+            TransportWindowEvent::MouseWheel(input) => WinitWindowEvent::MouseWheel(MouseWheel {
+                unit: if input.pixels {
+                    MouseScrollUnit::Pixel
+                } else {
+                    MouseScrollUnit::Line
+                },
+                x: input.x,
+                y: input.y,
+                received_at: None,
+            }),
+            TransportWindowEvent::Touch { .. } => return None,
+            TransportWindowEvent::TouchesCancelled => WinitWindowEvent::TouchesCancelled,
+            TransportWindowEvent::ReceivedCharacter(c) => WinitWindowEvent::ReceivedCharacter(c),
+            TransportWindowEvent::ScaleFactorChanged {
+                scale_factor,
+                width,
+                height,
+            } => {
+                WinitWindowEvent::ScaleFactorChanged(scale_factor, PhysicalSize::new(width, height))
+            }
+            TransportWindowEvent::Focused(focused) => WinitWindowEvent::Focused(focused),
+            TransportWindowEvent::DroppedFile(path) => WinitWindowEvent::DroppedFile(path),
+            TransportWindowEvent::HoveredFile(path) => WinitWindowEvent::HoveredFile(path),
+            TransportWindowEvent::HoveredFileCancelled => WinitWindowEvent::HoveredFileCancelled,
+            TransportWindowEvent::Moved { x, y } => {
+                WinitWindowEvent::Moved(PhysicalPosition::new(x, y))
+            }
+            TransportWindowEvent::ThemeChanged { dark } => {
+                WinitWindowEvent::ThemeChanged(if dark {
+                    WindowTheme::Dark
+                } else {
+                    WindowTheme::Light
+                })
+            }
+            TransportWindowEvent::RedrawRequested => WinitWindowEvent::RedrawRequested,
+        })
+    }
+}
+
+fn element_state(pressed: bool) -> ElementState {
+    if pressed {
+        ElementState::Pressed
+    } else {
+        ElementState::Released
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_events_round_trip() {
+        let window = WindowId::new();
+        let events = vec![
+            WinitWindowEvent::Resized(PhysicalSize::new(640, 480)),
+            WinitWindowEvent::ModifiersChanged(ModifiersState::SHIFT | ModifiersState::LOGO),
+            WinitWindowEvent::KeyboardInput(KeyboardInput {
+                scan_code: 30,
+                key_code: Some(KeyCode::A),
+                state: ElementState::Pressed,
+                repeat: true,
+                text: Some("a".to_string()),
+                received_at: None,
+            }),
+            WinitWindowEvent::MouseWheel(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: -1.0,
+                received_at: None,
+            }),
+            WinitWindowEvent::MouseInput(MouseButtonInput {
+                button: MouseButton::Left,
+                state: ElementState::Pressed,
+                received_at: None,
+            }),
+            WinitWindowEvent::ThemeChanged(WindowTheme::Dark),
+        ];
+        for e in events {
+            let transported = TransportEvent::from_window_event(window, &e).unwrap();
+            let event = match transported.clone() {
+                TransportEvent::Window { window: id, event } => {
+                    assert_eq!(WindowId::from_u128(id), window);
+                    event
+                }
+                TransportEvent::MouseMotion { .. } => panic!("not a window event"),
+            };
+            let back = event.into_window_event().unwrap();
+            assert_eq!(
+                TransportEvent::from_window_event(window, &back),
+                Some(transported)
+            );
+        }
+    }
+
+    #[test]
+    fn records_only_while_recording() {
+        let mut transport = WinitTransport::default();
+        let motion = MouseMotion {
+            delta: Vec2::new(1.0, 2.0),
+        };
+        transport.record(|| Some(TransportEvent::from_mouse_motion(&motion)));
+        assert!(transport.take_recorded().is_empty());
+
+        transport.set_recording(true);
+        transport.record(|| Some(TransportEvent::from_mouse_motion(&motion)));
+        assert_eq!(
+            transport.take_recorded(),
+            [TransportEvent::MouseMotion {
+                delta_x: 1.0,
+                delta_y: 2.0
+            }]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{UnknownWindowEventPolicy, WinitEvent, WinitWindowEvent};
+use bevy_utils::{
+    tracing::{trace, warn},
+    HashSet,
+};
+use std::collections::VecDeque;
+use winit::window::WindowId;
+
+/// Handles the events of winit windows that no app knows according to an
+/// [UnknownWindowEventPolicy].
+pub(crate) struct UnknownWindowEvents {
+    policy: UnknownWindowEventPolicy,
+    warned: HashSet<WindowId>,
+    buffered: VecDeque<(WinitWindowEvent, WindowId)>,
+}
+
+impl UnknownWindowEvents {
+    pub fn new(policy: UnknownWindowEventPolicy) -> Self {
+        UnknownWindowEvents {
+            policy,
+            warned: HashSet::default(),
+            buffered: VecDeque::new(),
+        }
+    }
+
+    /// Takes events of `winit_window_id`, which no app knows.
+    pub fn push(
+        &mut self,
+        winit_window_id: WindowId,
+        events: impl IntoIterator<Item = WinitWindowEvent>,
+    ) {
+        match self.policy {
+            UnknownWindowEventPolicy::WarnOnce => {
+                if self.warned.insert(winit_window_id) {
+                    warn!(
+                        "Skipped events for unknown winit Window Id {:?}",
+                        winit_window_id
+                    );
+                }
+            }
+            UnknownWindowEventPolicy::Buffer { max_events } => {
+                for e in events {
+                    if self.buffered.len() >= max_events {
+                        if let Some((_, dropped_from)) = self.buffered.pop_front() {
+                            trace!(
+                                "Dropped buffered event for unknown winit Window Id {:?}",
+                                dropped_from
+                            );
+                        }
+                    }
+                    if max_events > 0 {
+                        self.buffered.push_back((e, winit_window_id));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves the buffered events of the windows that are now known into `events`, in the order
+    /// they arrived.
+    pub fn take_known(
+        &mut self,
+        is_known: impl Fn(WindowId) -> bool,
+        events: &mut Vec<WinitEvent>,
+    ) {
+        if self.buffered.is_empty() {
+            return;
+        }
+        let mut index = 0;
+        while index < self.buffered.len() {
+            if is_known(self.buffered[index].1) {
+                let (e, winit_window_id) = self.buffered.remove(index).unwrap();
+                events.push(WinitEvent::WindowEvent(e, winit_window_id));
+            } else {
+                index += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffers_events_until_their_window_is_known() {
+        let winit_window_id = unsafe { WindowId::dummy() };
+        let mut unknown =
+            UnknownWindowEvents::new(UnknownWindowEventPolicy::Buffer { max_events: 2 });
+        unknown.push(
+            winit_window_id,
+            vec![
+                WinitWindowEvent::CursorEntered,
+                WinitWindowEvent::Focused(true),
+                WinitWindowEvent::ReceivedCharacter('a'),
+            ],
+        );
+
+        let mut events = Vec::new();
+        unknown.take_known(|_| false, &mut events);
+        assert!(events.is_empty());
+
+        unknown.take_known(|_| true, &mut events);
+        assert!(matches!(
+            events.as_slice(),
+            [
+                WinitEvent::WindowEvent(WinitWindowEvent::Focused(true), _),
+                WinitEvent::WindowEvent(WinitWindowEvent::ReceivedCharacter('a'), _)
+            ]
+        ));
+        unknown.take_known(|_| true, &mut events);
+        assert_eq!(events.len(), 2);
+    }
+}
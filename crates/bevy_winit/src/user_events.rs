@@ -0,0 +1,146 @@
+use bevy_app::{AppBuilder, CoreStage, Events, Plugin};
+use bevy_ecs::{
+    component::Component,
+    system::{IntoSystem, Res, ResMut},
+};
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+};
+#[cfg(not(target_arch = "wasm32"))]
+use winit::event_loop::EventLoopProxy;
+
+/// Adds `Events<T>` that can be sent from any thread with a [WinitUserEventSender<T>], e.g. by
+/// async tasks or network threads. Sending wakes the event loop, so the app updates for the
+/// event even in a reactive [UpdateMode](crate::UpdateMode), and it arrives as `Events<T>` in
+/// that update.
+pub struct WinitUserEventPlugin<T>(PhantomData<T>);
+
+impl<T> Default for WinitUserEventPlugin<T> {
+    fn default() -> Self {
+        WinitUserEventPlugin(PhantomData)
+    }
+}
+
+impl<T: Component> Plugin for WinitUserEventPlugin<T> {
+    fn build(&self, app: &mut AppBuilder) {
+        let waker = app
+            .world_mut()
+            .get_resource_or_insert_with(Arc::<WinitWaker>::default)
+            .clone();
+        let (sender, receiver) = mpsc::channel::<T>();
+        app.add_event::<T>()
+            .insert_resource(WinitUserEventSender {
+                sender: Arc::new(Mutex::new(sender)),
+                waker,
+            })
+            .insert_resource(WinitUserEventReceiver(Mutex::new(receiver)))
+            .add_system_to_stage(CoreStage::PreUpdate, forward_user_events::<T>.system());
+    }
+}
+
+/// Sends `T` to the app from any thread and wakes its event loop. Inserted as a resource by the
+/// [WinitUserEventPlugin<T>], clone it to hand it to other threads.
+pub struct WinitUserEventSender<T> {
+    sender: Arc<Mutex<mpsc::Sender<T>>>,
+    waker: Arc<WinitWaker>,
+}
+
+impl<T> Clone for WinitUserEventSender<T> {
+    fn clone(&self) -> Self {
+        WinitUserEventSender {
+            sender: self.sender.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+impl<T> WinitUserEventSender<T> {
+    /// Sends `event` as `Events<T>` with the next update. Returns the event if the app has
+    /// exited.
+    pub fn send(&self, event: T) -> Result<(), T> {
+        self.sender
+            .lock()
+            .unwrap()
+            .send(event)
+            .map_err(|mpsc::SendError(event)| event)?;
+        self.waker.wake();
+        Ok(())
+    }
+}
+
+struct WinitUserEventReceiver<T>(Mutex<mpsc::Receiver<T>>);
+
+fn forward_user_events<T: Component>(
+    receiver: Res<WinitUserEventReceiver<T>>,
+    mut events: ResMut<Events<T>>,
+) {
+    events.extend(receiver.0.lock().unwrap().try_iter());
+}
+
+/// Wakes the event loop for the [WinitUserEventSender]s of an app, and tells the runner that the
+/// app has to update for their events.
+#[derive(Default)]
+pub(crate) struct WinitWaker {
+    // the proxy of the web cannot be shared, and there is no other thread to wake it from
+    #[cfg(not(target_arch = "wasm32"))]
+    event_loop_proxy: Mutex<Option<EventLoopProxy<()>>>,
+    pending: AtomicBool,
+}
+
+impl WinitWaker {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_event_loop_proxy(&self, proxy: EventLoopProxy<()>) {
+        *self.event_loop_proxy.lock().unwrap() = Some(proxy);
+    }
+
+    /// Whether user events were sent since the last call.
+    pub fn take_pending(&self) -> bool {
+        self.pending.swap(false, Ordering::AcqRel)
+    }
+
+    fn wake(&self) {
+        self.pending.store(true, Ordering::Release);
+        // fails only after the event loop has stopped
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(proxy) = self.event_loop_proxy.lock().unwrap().as_ref() {
+            let _ = proxy.send_event(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use std::thread;
+
+    #[test]
+    fn events_sent_from_other_threads_arrive() {
+        let mut app = App::build();
+        app.add_plugin(WinitUserEventPlugin::<u32>::default());
+        let mut app = app.app;
+
+        let sender = app
+            .world
+            .get_resource::<WinitUserEventSender<u32>>()
+            .unwrap()
+            .clone();
+        thread::spawn(move || sender.send(7).unwrap())
+            .join()
+            .unwrap();
+        assert!(app
+            .world
+            .get_resource::<Arc<WinitWaker>>()
+            .unwrap()
+            .take_pending());
+
+        app.update();
+        let events = app.world.get_resource::<Events<u32>>().unwrap();
+        let mut reader = events.get_reader();
+        assert_eq!(reader.iter(events).copied().collect::<Vec<_>>(), [7]);
+    }
+}
@@ -1,5 +1,7 @@
+use bevy_utils::Duration;
+
 /// A resource for configuring usage of the `rust_winit` library.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone)]
 pub struct WinitConfig {
     /// Configures the winit library to return control to the main thread after
     /// the [run](bevy_app::App::run) loop is exited. Winit strongly recommends
@@ -12,4 +14,211 @@ pub struct WinitConfig {
     /// `openbsd`. If set to true on an unsupported platform
     /// [run](bevy_app::App::run) will panic.
     pub return_from_run: bool,
+    /// Which display server protocol the event loop uses on Linux and the BSDs. The backend
+    /// that was started is available as the [WinitBackend](crate::WinitBackend) resource,
+    /// since cursor grabs and fullscreen behave differently between them.
+    pub display_backend: DisplayBackend,
+    /// Close requests for a window that arrive within this duration of the previous one are
+    /// dropped, since some desktops deliver `CloseRequested` more than once per click.
+    /// `Duration::ZERO` disables the debounce.
+    pub close_request_debounce: Duration,
+    /// Closes a window as soon as a close is requested for it, instead of only sending a
+    /// [WindowCloseRequested](bevy_window::WindowCloseRequested) event. Unlike
+    /// [WindowPlugin::close_when_requested](bevy_window::WindowPlugin::close_when_requested),
+    /// this skips any [CloseConfirmation](bevy_window::CloseConfirmation).
+    pub close_when_requested: bool,
+    /// Also sends a [WindowResized](bevy_window::WindowResized) event when only the scale
+    /// factor of a window changes, since its logical size changes with it even though its
+    /// physical size stays the same. Useful for layouts that work in logical pixels.
+    pub resize_on_scale_change: bool,
+    /// Emulates [CursorGrabMode::Locked](bevy_window::CursorGrabMode::Locked) on platforms
+    /// that can only confine the cursor, such as X11 and Windows, by moving the cursor back to
+    /// the center of the window whenever it moves. [MouseMotion](bevy_input::mouse::MouseMotion)
+    /// is then derived from those cursor movements instead of raw device motion. Without it,
+    /// a locked cursor is only confined on these platforms.
+    pub emulate_cursor_lock: bool,
+    /// Extrapolates the cursor position from its recent movement to this far beyond the start
+    /// of each update, e.g. the expected time until the frame is shown. The result is available
+    /// as [Window::predicted_cursor_position](bevy_window::Window::predicted_cursor_position).
+    pub cursor_prediction: Option<Duration>,
+    /// What happens to a hidden or grabbed cursor when its window loses focus, so that users
+    /// are not left with an invisible or trapped cursor after switching to another application.
+    pub cursor_focus_policy: CursorFocusPolicy,
+    /// How often the winit thread checks the monitors for changes that are not caused by the
+    /// app, like a changed resolution or a connected TV, which winit does not report. Changes
+    /// are sent as [MonitorModeChanged](bevy_window::MonitorModeChanged) events and update
+    /// [Monitors](bevy_window::Monitors). `None`, the default, only updates the monitors when a
+    /// window's scale factor changes, so the winit thread does not wake up while idle.
+    pub monitor_poll_interval: Option<Duration>,
+    /// Determines how often the app is updated. Use one of the reactive modes for editors and
+    /// tools that should not use any CPU while idle.
+    pub update_mode: UpdateMode,
+    /// Replaces `update_mode` while an app has windows but all of them are hidden or
+    /// minimized, e.g. for apps that keep running in the system tray. This applies only to
+    /// that state, unfocused windows still use `update_mode`.
+    pub hidden_update_mode: Option<UpdateMode>,
+    /// Artificially delays input and window events on their way to the app. Meant for testing
+    /// how an app feels under worst-case latency, leave it unset otherwise.
+    pub simulated_latency: Option<SimulatedLatency>,
+    /// Shows a native message box with the panic message when the app or the event loop
+    /// panics, using [show_error_dialog](crate::show_error_dialog), before the panic is
+    /// propagated.
+    pub error_dialog_on_panic: bool,
+    /// Polls gamepads on the winit thread and sends their input to the app like window input,
+    /// so that it also wakes apps using a reactive [UpdateMode]. The winit thread only wakes up
+    /// regularly for this while gamepads are connected. Requires the `gilrs` feature and
+    /// replaces `GilrsPlugin`, this is ignored if an app adds it anyway.
+    pub poll_gamepads: bool,
+    /// Reads the clipboard when the user presses the paste shortcut of the platform and sends
+    /// its text as a [TextPasted](crate::TextPasted) event, so text fields do not have to
+    /// detect the shortcut and query the [Clipboard](crate::Clipboard) themselves.
+    pub paste_events: bool,
+    /// Caps how often each app is updated, in updates per second, without relying on vsync.
+    /// The app loop keeps waiting for events for most of the remaining frame time and spins for
+    /// the last moments, which is more precise than sleeping alone. `None` does not limit the
+    /// frame rate.
+    ///
+    /// Not available on the web and iOS, where the event loop runs on the main thread and
+    /// frames are paced by the platform.
+    pub frame_rate_limit: Option<f64>,
+    /// What happens to events of a window that no app knows, e.g. events that overtake the
+    /// creation of their window, or that arrive after it was closed.
+    pub unknown_window_events: UnknownWindowEventPolicy,
+    /// What happens when a window is resized to zero width or height, like minimized windows
+    /// on Windows.
+    pub zero_size_policy: ZeroSizePolicy,
+    /// Inserts a [RawInputChannel](crate::RawInputChannel) for keyboard input that keeps at
+    /// most this many inputs, dropping the oldest ones first. `None` does not insert it.
+    pub raw_input_capacity: Option<usize>,
+}
+
+impl Default for WinitConfig {
+    fn default() -> Self {
+        WinitConfig {
+            return_from_run: false,
+            display_backend: DisplayBackend::default(),
+            close_request_debounce: Duration::from_millis(100),
+            close_when_requested: false,
+            resize_on_scale_change: false,
+            emulate_cursor_lock: false,
+            cursor_prediction: None,
+            cursor_focus_policy: CursorFocusPolicy::default(),
+            monitor_poll_interval: None,
+            update_mode: UpdateMode::default(),
+            hidden_update_mode: None,
+            simulated_latency: None,
+            error_dialog_on_panic: false,
+            poll_gamepads: false,
+            paste_events: false,
+            frame_rate_limit: None,
+            unknown_window_events: UnknownWindowEventPolicy::default(),
+            zero_size_policy: ZeroSizePolicy::default(),
+            raw_input_capacity: None,
+        }
+    }
+}
+
+/// The display server protocol requested with [WinitConfig::display_backend].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayBackend {
+    /// `WINIT_UNIX_BACKEND` if set, otherwise Wayland if a compositor is running, otherwise
+    /// X11.
+    #[default]
+    Auto,
+    X11,
+    Wayland,
+}
+
+/// Determines when the app is updated and how the winit event loop waits for new events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateMode {
+    /// The app is updated as fast as possible and the event loop polls continuously.
+    #[default]
+    Continuous,
+    /// The app is only updated when a window or device event is received, or when `max_wait`
+    /// has elapsed since the last update. Both threads sleep in the meantime.
+    Reactive { max_wait: Duration },
+    /// Like [UpdateMode::Reactive], but device events such as raw mouse motion do not wake the
+    /// app, so that moving the mouse over other applications does not cause updates.
+    ReactiveLowPower { max_wait: Duration },
+}
+
+impl UpdateMode {
+    /// The longest time the app may go without an update, if any.
+    pub fn max_wait(&self) -> Option<Duration> {
+        match self {
+            UpdateMode::Continuous => None,
+            UpdateMode::Reactive { max_wait } | UpdateMode::ReactiveLowPower { max_wait } => {
+                Some(*max_wait)
+            }
+        }
+    }
+}
+
+/// Determines how the cursor of a window is treated when the window loses and regains focus,
+/// see [WinitConfig::cursor_focus_policy].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorFocusPolicy {
+    /// The cursor stays hidden and grabbed, as requested by the app.
+    Keep,
+    /// The cursor is shown and released when the window loses focus. The window's
+    /// [cursor_visible](bevy_window::Window::cursor_visible) and
+    /// [cursor_grab_mode](bevy_window::Window::cursor_grab_mode) are updated to match, so the
+    /// app has to hide or grab it again.
+    #[default]
+    Release,
+    /// Like [CursorFocusPolicy::Release], but the cursor is hidden and grabbed again when the
+    /// window regains focus. The window keeps reporting the state requested by the app.
+    ReleaseAndRestore,
+}
+
+/// Determines what happens to events of a window that no app knows, see
+/// [WinitConfig::unknown_window_events].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownWindowEventPolicy {
+    /// The events are dropped, with a warning for the first event of each window.
+    #[default]
+    WarnOnce,
+    /// The events are kept until an app knows their window, and are then dispatched in the
+    /// order they arrived. Once `max_events` are kept, the oldest ones are dropped, which
+    /// eventually discards the events of windows that were closed.
+    Buffer { max_events: usize },
+}
+
+/// Determines how windows that are resized to zero width or height are reported, see
+/// [WinitConfig::zero_size_policy].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ZeroSizePolicy {
+    /// The zero size is reported with a [WindowResized](bevy_window::WindowResized) event and a
+    /// [SurfaceLost](bevy_window::SurfaceLost) event.
+    #[default]
+    Report,
+    /// The window keeps its last size and no [WindowResized](bevy_window::WindowResized) event
+    /// is sent. It is marked as minimized with a
+    /// [WindowMinimized](bevy_window::WindowMinimized) event instead, for renderers that cannot
+    /// handle zero-sized surfaces.
+    Suppress,
+}
+
+/// The delay applied to each input and window event when
+/// [WinitConfig::simulated_latency] is set.
+///
+/// Every event is held back for `latency` plus a random amount of up to `jitter`, picked
+/// according to `distribution`. Events are never reordered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulatedLatency {
+    pub latency: Duration,
+    pub jitter: Duration,
+    pub distribution: JitterDistribution,
+}
+
+/// How the random part of a [SimulatedLatency] is distributed.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum JitterDistribution {
+    /// Any delay between zero and the full jitter is equally likely.
+    #[default]
+    Uniform,
+    /// Most events get no jitter, but each one has the given probability of getting the full
+    /// jitter, like occasional hitches on a loaded machine.
+    Spikes { probability: f32 },
 }
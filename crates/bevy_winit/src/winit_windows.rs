@@ -1,13 +1,97 @@
-use bevy_math::IVec2;
-use bevy_utils::HashMap;
-use bevy_window::{Window, WindowDescriptor, WindowId, WindowMode};
-use winit::dpi::LogicalSize;
+use crate::{
+    converters,
+    cursor_lock::EmulatedCursorLock,
+    raw_input::RawInputWindows,
+    safe_area::{self, SafeAreaViews},
+    size_lock::SizeLock,
+};
+use bevy_ecs::{entity::Entity, world::World};
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_utils::{
+    tracing::{error, warn},
+    Duration, HashMap, HashSet, Instant,
+};
+use bevy_window::{
+    AnnouncementPriority, BackgroundEffect, CursorGrabMode, MonitorSelection,
+    RawWindowHandleWrapper, RawWindowHandles, ResizeConstraintUnits, Window, WindowBackend,
+    WindowCommand, WindowCommandError, WindowCoordinateConvention, WindowDescriptor,
+    WindowEventMask, WindowId, WindowMode, WindowPosition, WindowResizeConstraints, WindowTheme,
+};
+use raw_window_handle::HasRawWindowHandle;
+use std::sync::{Arc, RwLock};
+use winit::{
+    dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size},
+    monitor::{MonitorHandle, VideoMode},
+    window::UserAttentionType,
+};
+
+/// Applies a [BackgroundEffect] to a newly created window, see
+/// [WinitWindows::background_effect_hook]. Returns whether the effect was applied.
+pub type BackgroundEffectHook = fn(&winit::window::Window, BackgroundEffect) -> bool;
+
+/// How long an announcement is shown in the title of a window.
+const ANNOUNCEMENT_DURATION: Duration = Duration::from_secs(3);
+
+/// The event masks of the windows that do not forward every event, shared with the thread of
+/// the event loop, which drops the masked events.
+pub(crate) type EventMasks = Arc<RwLock<HashMap<winit::window::WindowId, WindowEventMask>>>;
+
+/// The component of the entity that is spawned for each window. Despawning the entity closes
+/// the window, and closing the window despawns the entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowHandle {
+    id: WindowId,
+}
+
+impl WindowHandle {
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct WinitWindows {
     pub windows: HashMap<winit::window::WindowId, winit::window::Window>,
     pub window_id_to_winit: HashMap<WindowId, winit::window::WindowId>,
     pub winit_to_window_id: HashMap<winit::window::WindowId, WindowId>,
+    /// Windows whose cursor is locked by moving it back to the center whenever it moves.
+    emulated_cursor_locks: HashMap<WindowId, EmulatedCursorLock>,
+    /// Windows that were only made layered to let the cursor pass through them, which is
+    /// undone once their hit test is enabled again.
+    hit_test_layered: HashSet<WindowId>,
+    /// Set from [WinitConfig::emulate_cursor_lock](crate::WinitConfig::emulate_cursor_lock).
+    pub(crate) emulate_cursor_lock: bool,
+    /// Synced from the [WindowCoordinateConvention] resource before commands are applied.
+    pub(crate) coordinate_convention: WindowCoordinateConvention,
+    /// Applies background effects in place of the built-in support, or where there is none,
+    /// e.g. macOS vibrancy through the `cocoa` crate. When it returns `false`, the built-in
+    /// support is used.
+    pub background_effect_hook: Option<BackgroundEffectHook>,
+    pub(crate) event_masks: EventMasks,
+    /// Shared with the thread of the event loop, which reports the safe area insets.
+    pub(crate) safe_area_views: SafeAreaViews,
+    /// Shared with the thread of the event loop, which sends the raw input of these windows to
+    /// the [RawInputChannel](crate::RawInputChannel) of the app.
+    pub(crate) raw_input_windows: RawInputWindows,
+    /// Windows whose title shows an announcement, with the time to restore the title at.
+    announced_titles: HashMap<WindowId, Instant>,
+    /// Windows whose position and size have to be queried again once the commands are applied.
+    pub(crate) geometry_refreshes: Vec<WindowId>,
+    size_locks: HashMap<WindowId, SizeLock>,
+    /// Errors of windows that were created with a fallback, e.g. windowed instead of
+    /// fullscreen, reported once the windows exist.
+    pub(crate) creation_errors: Vec<(WindowId, WindowCommandError)>,
+    /// The checked resize constraints of the windows, which the size locks stay within.
+    resize_constraints: HashMap<WindowId, WindowResizeConstraints>,
+    /// The position and size of windows before they entered
+    /// [WindowMode::BorderlessWindowedMax], restored when they become windowed again.
+    borderless_max_restores: HashMap<WindowId, (Option<PhysicalPosition<i32>>, PhysicalSize<u32>)>,
+    /// The entities with a [WindowHandle], kept while a window is recreated for a backend
+    /// restart.
+    window_entities: HashMap<WindowId, Entity>,
+    entity_windows: HashMap<Entity, WindowId>,
+    /// Entities of closed windows, despawned once the commands are applied.
+    pub(crate) despawned_entities: Vec<Entity>,
 }
 
 impl WinitWindows {
@@ -16,30 +100,138 @@ impl WinitWindows {
         event_loop: &winit::event_loop::EventLoopWindowTarget<()>,
         window_id: WindowId,
         window_descriptor: &WindowDescriptor,
-    ) -> Window {
+    ) -> Result<Window, WindowCommandError> {
         #[cfg(target_os = "windows")]
         let mut winit_window_builder = {
+            use winapi::shared::windef::HWND;
             use winit::platform::windows::WindowBuilderExtWindows;
-            winit::window::WindowBuilder::new().with_drag_and_drop(false)
+
+            let windows = &window_descriptor.windows;
+            let mut winit_window_builder = winit::window::WindowBuilder::new()
+                .with_drag_and_drop(windows.drag_and_drop)
+                .with_no_redirection_bitmap(windows.no_redirection_bitmap)
+                .with_theme(
+                    window_descriptor
+                        .preferred_theme
+                        .map(crate::converters::convert_window_theme),
+                );
+            if let Some(parent_window) = windows.parent_window {
+                winit_window_builder =
+                    winit_window_builder.with_parent_window(parent_window as HWND);
+            }
+            winit_window_builder
         };
 
         #[cfg(not(target_os = "windows"))]
         let mut winit_window_builder = winit::window::WindowBuilder::new();
 
+        // winit 0.25 always activates new windows and cannot hide them from the taskbar, so on
+        // Windows such windows are created invisible and shown once they have been adjusted
+        let show_after_creation = cfg!(target_os = "windows")
+            && window_descriptor.visible
+            && (!window_descriptor.active || window_descriptor.skip_taskbar);
+
+        #[cfg(target_os = "macos")]
+        {
+            use winit::platform::macos::WindowBuilderExtMacOS;
+
+            let macos = &window_descriptor.macos;
+            winit_window_builder = winit_window_builder
+                .with_titlebar_transparent(macos.titlebar_transparent)
+                .with_title_hidden(macos.title_hidden)
+                .with_fullsize_content_view(macos.fullsize_content_view)
+                .with_movable_by_window_background(macos.movable_by_window_background);
+        }
+
+        #[cfg(target_os = "ios")]
+        {
+            use winit::platform::ios::WindowBuilderExtIOS;
+
+            let ios = &window_descriptor.ios;
+            winit_window_builder = winit_window_builder
+                .with_prefers_home_indicator_hidden(ios.prefers_home_indicator_hidden)
+                .with_prefers_status_bar_hidden(ios.prefers_status_bar_hidden);
+            if let Some(scale_factor) = window_descriptor.scale_factor_override {
+                winit_window_builder = winit_window_builder.with_scale_factor(scale_factor);
+            }
+        }
+
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        if let Some(name) = &window_descriptor.name {
+            #[cfg(feature = "x11")]
+            {
+                use winit::platform::unix::WindowBuilderExtUnix;
+                winit_window_builder = winit_window_builder.with_class(name.clone(), name.clone());
+            }
+            #[cfg(feature = "wayland")]
+            {
+                use winit::platform::unix::WindowBuilderExtUnix;
+                winit_window_builder = winit_window_builder.with_app_id(name.clone());
+            }
+        }
+
+        // reported once the window exists
+        let mut fallback_error = None;
         winit_window_builder = match window_descriptor.mode {
             WindowMode::BorderlessFullscreen => winit_window_builder.with_fullscreen(Some(
                 winit::window::Fullscreen::Borderless(event_loop.primary_monitor()),
             )),
-            WindowMode::Fullscreen { use_size } => winit_window_builder.with_fullscreen(Some(
-                winit::window::Fullscreen::Exclusive(match use_size {
-                    true => get_fitting_videomode(
-                        &event_loop.primary_monitor().unwrap(),
-                        window_descriptor.width as u32,
-                        window_descriptor.height as u32,
-                    ),
-                    false => get_best_videomode(&event_loop.primary_monitor().unwrap()),
-                }),
-            )),
+            WindowMode::Fullscreen {
+                use_size,
+                monitor,
+                refresh_rate,
+                video_mode_index,
+            } => {
+                let monitor = match monitor {
+                    MonitorSelection::Current | MonitorSelection::Primary => {
+                        event_loop.primary_monitor()
+                    }
+                    MonitorSelection::Index(index) => event_loop.available_monitors().nth(index),
+                }
+                .ok_or(WindowCommandError::MonitorNotFound(monitor));
+                match monitor.and_then(|monitor| {
+                    get_fullscreen_videomode(
+                        &monitor,
+                        use_size,
+                        physical_size(window_descriptor, &monitor),
+                        refresh_rate,
+                        video_mode_index,
+                    )
+                }) {
+                    Ok(video_mode) => winit_window_builder
+                        .with_fullscreen(Some(winit::window::Fullscreen::Exclusive(video_mode))),
+                    Err(e) => {
+                        warn!("Falling back to a windowed window for {}", window_id);
+                        fallback_error = Some(e);
+                        winit_window_builder
+                    }
+                }
+            }
+            WindowMode::SizedFullscreen => {
+                match event_loop
+                    .primary_monitor()
+                    .ok_or(WindowCommandError::MonitorNotFound(
+                        MonitorSelection::Primary,
+                    ))
+                    .and_then(|monitor| {
+                        let (width, height) = physical_size(window_descriptor, &monitor);
+                        get_fitting_videomode(&monitor, width, height, None)
+                    }) {
+                    Ok(video_mode) => winit_window_builder
+                        .with_fullscreen(Some(winit::window::Fullscreen::Exclusive(video_mode))),
+                    Err(e) => {
+                        warn!("Falling back to a windowed window for {}", window_id);
+                        fallback_error = Some(e);
+                        winit_window_builder
+                    }
+                }
+            }
             _ => {
                 let WindowDescriptor {
                     width,
@@ -58,26 +250,26 @@ impl WinitWindows {
             }
             .with_resizable(window_descriptor.resizable)
             .with_decorations(window_descriptor.decorations),
-        };
+        }
+        .with_transparent(window_descriptor.transparent)
+        .with_visible(window_descriptor.visible && !show_after_creation);
 
-        let constraints = window_descriptor.resize_constraints.check_constraints();
-        let min_inner_size = LogicalSize {
-            width: constraints.min_width,
-            height: constraints.min_height,
-        };
-        let max_inner_size = LogicalSize {
-            width: constraints.max_width,
-            height: constraints.max_height,
+        let (min_inner_size, max_inner_size) = resize_limits(
+            &window_descriptor.resize_constraints,
+            window_descriptor.scale_factor_override,
+        );
+
+        let winit_window_builder = match get_window_position(event_loop, window_descriptor) {
+            Some(position) => winit_window_builder.with_position(position),
+            None => winit_window_builder,
         };
 
-        let winit_window_builder =
-            if constraints.max_width.is_finite() && constraints.max_height.is_finite() {
-                winit_window_builder
-                    .with_min_inner_size(min_inner_size)
-                    .with_max_inner_size(max_inner_size)
-            } else {
-                winit_window_builder.with_min_inner_size(min_inner_size)
-            };
+        let winit_window_builder = match max_inner_size {
+            Some(max_inner_size) => winit_window_builder
+                .with_min_inner_size(min_inner_size)
+                .with_max_inner_size(max_inner_size),
+            None => winit_window_builder.with_min_inner_size(min_inner_size),
+        };
 
         #[allow(unused_mut)]
         let mut winit_window_builder = winit_window_builder.with_title(&window_descriptor.title);
@@ -88,32 +280,91 @@ impl WinitWindows {
             use winit::platform::web::WindowBuilderExtWebSys;
 
             if let Some(selector) = &window_descriptor.canvas {
-                let window = web_sys::window().unwrap();
-                let document = window.document().unwrap();
-                let canvas = document
-                    .query_selector(&selector)
-                    .expect("Cannot query for canvas element.");
-                if let Some(canvas) = canvas {
-                    let canvas = canvas.dyn_into::<web_sys::HtmlCanvasElement>().ok();
-                    winit_window_builder = winit_window_builder.with_canvas(canvas);
-                } else {
-                    panic!("Cannot find element: {}.", selector);
-                }
+                let canvas = web_sys::window()
+                    .and_then(|window| window.document())
+                    .and_then(|document| document.query_selector(&selector).ok().flatten())
+                    .ok_or_else(|| {
+                        WindowCommandError::CreationFailed(format!(
+                            "cannot find element {}",
+                            selector
+                        ))
+                    })?
+                    .dyn_into::<web_sys::HtmlCanvasElement>()
+                    .map_err(|_| {
+                        WindowCommandError::CreationFailed(format!(
+                            "element {} is not a canvas",
+                            selector
+                        ))
+                    })?;
+                winit_window_builder = winit_window_builder.with_canvas(Some(canvas));
             }
         }
 
-        let winit_window = winit_window_builder.build(&event_loop).unwrap();
+        let winit_window = winit_window_builder
+            .build(event_loop)
+            .map_err(|e| WindowCommandError::CreationFailed(e.to_string()))?;
 
-        match winit_window.set_cursor_grab(window_descriptor.cursor_locked) {
-            Ok(_) => {}
-            Err(winit::error::ExternalError::NotSupported(_)) => {}
-            Err(err) => Err(err).unwrap(),
+        if window_descriptor.skip_taskbar {
+            hide_from_taskbar(&winit_window);
+        }
+        if show_after_creation {
+            if window_descriptor.active {
+                winit_window.set_visible(true);
+            } else {
+                show_window_inactive(&winit_window);
+            }
         }
 
+        let emulated_cursor_lock = match set_cursor_grab_mode(
+            &winit_window,
+            window_descriptor.cursor_grab_mode,
+            self.emulate_cursor_lock,
+        ) {
+            Ok(emulated) => emulated,
+            Err(WindowCommandError::Unsupported { .. }) => false,
+            Err(e) => return Err(e),
+        };
+
         winit_window.set_cursor_visible(window_descriptor.cursor_visible);
+        let hit_test_layered = if window_descriptor.cursor_hit_test {
+            false
+        } else {
+            match set_cursor_hit_test(&winit_window, false, false) {
+                Ok(layered) => layered,
+                Err(WindowCommandError::Unsupported { .. }) => {
+                    warn!("Disabling the cursor hit test is not supported on this platform");
+                    false
+                }
+                Err(e) => return Err(e),
+            }
+        };
 
-        self.window_id_to_winit.insert(window_id, winit_window.id());
-        self.winit_to_window_id.insert(winit_window.id(), window_id);
+        if window_descriptor.background_effect != BackgroundEffect::None {
+            let effect = window_descriptor.background_effect;
+            let hooked = self
+                .background_effect_hook
+                .map_or(false, |hook| hook(&winit_window, effect));
+            if !hooked {
+                match set_background_effect(&winit_window, effect) {
+                    Ok(()) => {}
+                    Err(WindowCommandError::Unsupported { .. }) => {
+                        warn!("Background effects are not supported on this platform")
+                    }
+                    Err(e) => warn!("Failed to apply the background effect: {}", e),
+                }
+            }
+        }
+
+        let mut content_protected = false;
+        if window_descriptor.content_protected {
+            match set_content_protected(&winit_window, true) {
+                Ok(()) => content_protected = true,
+                Err(WindowCommandError::Unsupported { .. }) => {
+                    warn!("Content protection is not supported on this platform")
+                }
+                Err(e) => return Err(e),
+            }
+        }
 
         #[cfg(target_arch = "wasm32")]
         {
@@ -122,30 +373,100 @@ impl WinitWindows {
             if window_descriptor.canvas.is_none() {
                 let canvas = winit_window.canvas();
 
-                let window = web_sys::window().unwrap();
-                let document = window.document().unwrap();
-                let body = document.body().unwrap();
-
-                body.append_child(&canvas)
-                    .expect("Append canvas to HTML body.");
+                web_sys::window()
+                    .and_then(|window| window.document())
+                    .and_then(|document| document.body())
+                    .and_then(|body| body.append_child(&canvas).ok())
+                    .ok_or_else(|| {
+                        WindowCommandError::CreationFailed(
+                            "cannot append the canvas to the HTML body".to_string(),
+                        )
+                    })?;
             }
         }
 
+        // nothing fails after this, so no failed window is left in the lookup tables
+        self.window_id_to_winit.insert(window_id, winit_window.id());
+        self.winit_to_window_id.insert(winit_window.id(), window_id);
+        if emulated_cursor_lock {
+            self.emulated_cursor_locks
+                .insert(window_id, EmulatedCursorLock::default());
+        }
+        if hit_test_layered {
+            self.hit_test_layered.insert(window_id);
+        }
+        self.creation_errors
+            .extend(fallback_error.map(|error| (window_id, error)));
+        self.resize_constraints.insert(
+            window_id,
+            window_descriptor.resize_constraints.check_constraints(),
+        );
+        self.set_event_mask(winit_window.id(), window_descriptor.event_mask);
+
         let position = winit_window
             .outer_position()
             .ok()
             .map(|position| IVec2::new(position.x, position.y));
         let inner_size = winit_window.inner_size();
         let scale_factor = winit_window.scale_factor();
+        let theme = window_theme(&winit_window).or(window_descriptor.preferred_theme);
+        let maximized = winit_window.is_maximized();
+        if let Some(view) = safe_area::native_view(&winit_window) {
+            self.safe_area_views
+                .write()
+                .unwrap()
+                .insert(winit_window.id(), view);
+        }
+        self.raw_input_windows
+            .write()
+            .unwrap()
+            .insert(winit_window.id());
         self.windows.insert(winit_window.id(), winit_window);
-        Window::new(
+        let mut window = Window::new(
             window_id,
             &window_descriptor,
             inner_size.width,
             inner_size.height,
             scale_factor,
             position,
-        )
+        );
+        window.update_theme_from_backend(theme);
+        window.update_maximized_from_backend(maximized);
+        window.update_from_applied_command(&WindowCommand::SetContentProtected {
+            protected: content_protected,
+        });
+        if window_descriptor.mode == WindowMode::BorderlessWindowedMax {
+            if let Err(e) = self.maximize_borderless(window_id) {
+                error!("Unable to cover the work area with the window: {}", e);
+            }
+        }
+        self.update_geometry(&mut window);
+        Ok(window)
+    }
+
+    /// Removes the window from the lookup tables and returns it. The backend window is
+    /// destroyed when the returned value is dropped.
+    pub fn remove_window(&mut self, id: WindowId) -> Option<winit::window::Window> {
+        self.emulated_cursor_locks.remove(&id);
+        self.hit_test_layered.remove(&id);
+        self.size_locks.remove(&id);
+        self.resize_constraints.remove(&id);
+        self.borderless_max_restores.remove(&id);
+        let winit_id = self.window_id_to_winit.remove(&id)?;
+        self.winit_to_window_id.remove(&winit_id);
+        self.event_masks.write().unwrap().remove(&winit_id);
+        self.safe_area_views.write().unwrap().remove(&winit_id);
+        self.raw_input_windows.write().unwrap().remove(&winit_id);
+        self.windows.remove(&winit_id)
+    }
+
+    fn set_event_mask(&self, winit_id: winit::window::WindowId, mask: WindowEventMask) {
+        let mut event_masks = self.event_masks.write().unwrap();
+        if mask == WindowEventMask::ALL {
+            event_masks.remove(&winit_id);
+        } else {
+            event_masks.insert(winit_id, mask);
+        }
     }
 
     pub fn get_window(&self, id: WindowId) -> Option<&winit::window::Window> {
@@ -157,51 +478,1067 @@ impl WinitWindows {
     pub fn get_window_id(&self, id: winit::window::WindowId) -> Option<WindowId> {
         self.winit_to_window_id.get(&id).cloned()
     }
-}
-pub fn get_fitting_videomode(
-    monitor: &winit::monitor::MonitorHandle,
-    width: u32,
-    height: u32,
-) -> winit::monitor::VideoMode {
-    let mut modes = monitor.video_modes().collect::<Vec<_>>();
 
-    fn abs_diff(a: u32, b: u32) -> u32 {
-        if a > b {
-            return a - b;
+    pub fn get_window_entity(&self, id: WindowId) -> Option<Entity> {
+        self.window_entities.get(&id).copied()
+    }
+
+    pub fn entity_to_winit(&self, entity: Entity) -> Option<winit::window::WindowId> {
+        self.entity_windows
+            .get(&entity)
+            .and_then(|id| self.window_id_to_winit.get(id))
+            .copied()
+    }
+
+    pub fn winit_to_entity(&self, id: winit::window::WindowId) -> Option<Entity> {
+        self.winit_to_window_id
+            .get(&id)
+            .and_then(|id| self.window_entities.get(id))
+            .copied()
+    }
+
+    /// Spawns an entity with a [WindowHandle] for each window that does not have one yet.
+    pub(crate) fn spawn_window_entities(&mut self, world: &mut World) {
+        let ids = self
+            .window_id_to_winit
+            .keys()
+            .filter(|id| !self.window_entities.contains_key(id))
+            .copied()
+            .collect::<Vec<_>>();
+        for id in ids {
+            let entity = world.spawn().insert(WindowHandle { id }).id();
+            self.window_entities.insert(id, entity);
+            self.entity_windows.insert(entity, id);
         }
-        b - a
     }
 
-    modes.sort_by(|a, b| {
-        use std::cmp::Ordering::*;
-        match abs_diff(a.size().width, width).cmp(&abs_diff(b.size().width, width)) {
-            Equal => {
-                match abs_diff(a.size().height, height).cmp(&abs_diff(b.size().height, height)) {
-                    Equal => b.refresh_rate().cmp(&a.refresh_rate()),
-                    default => default,
-                }
+    /// Sets the handles of the open windows in `handles`, and removes those of closed windows.
+    /// Handles are replaced every time, since recreated windows keep their id.
+    pub(crate) fn update_raw_window_handles(&self, handles: &mut RawWindowHandles) {
+        handles.retain(|id| self.window_id_to_winit.contains_key(&id));
+        for (id, winit_id) in &self.window_id_to_winit {
+            let handle = self.windows[winit_id].raw_window_handle();
+            handles.insert(*id, RawWindowHandleWrapper::new(handle));
+        }
+    }
+
+    /// The windows whose entity was despawned, which have to be closed.
+    pub(crate) fn windows_of_despawned_entities(&self, world: &World) -> Vec<WindowId> {
+        self.entity_windows
+            .iter()
+            .filter(|(entity, _)| world.get_entity(**entity).is_none())
+            .map(|(_, id)| *id)
+            .collect()
+    }
+
+    /// Removes the decorations of the window of `id` and makes it cover the work area of its
+    /// monitor, remembering its position and size for [WindowMode::Windowed].
+    fn maximize_borderless(&mut self, id: WindowId) -> Result<(), WindowCommandError> {
+        let window = self
+            .get_window(id)
+            .ok_or(WindowCommandError::UnknownWindow)?;
+        let monitor = window
+            .current_monitor()
+            .or_else(|| window.primary_monitor())
+            .ok_or(WindowCommandError::MonitorNotFound(
+                MonitorSelection::Current,
+            ))?;
+        let restore = (window.outer_position().ok(), window.inner_size());
+        window.set_fullscreen(None);
+        window.set_decorations(false);
+        let (area_position, area_size) = work_area(&monitor);
+        window.set_outer_position(area_position);
+        window.set_inner_size(area_size);
+        // a repeated switch, e.g. after the monitor changed, keeps the original restore state
+        self.borderless_max_restores.entry(id).or_insert(restore);
+        Ok(())
+    }
+
+    /// Resizes the window of `id` back to the closest size that keeps its resize increments and
+    /// aspect ratio within its resize constraints, if it was resized from `previous` to a size
+    /// that does not. Returns the corrected size. Minimized, maximized and fullscreen windows
+    /// are left alone, as their size is up to the platform.
+    pub(crate) fn enforce_size_lock(
+        &self,
+        id: WindowId,
+        previous: PhysicalSize<u32>,
+    ) -> Option<PhysicalSize<u32>> {
+        let (lock, window) = match (self.size_locks.get(&id), self.get_window(id)) {
+            (Some(lock), Some(window)) => (lock, window),
+            _ => return None,
+        };
+        let physical_size = window.inner_size();
+        if physical_size.width == 0
+            || physical_size.height == 0
+            || window.is_maximized()
+            || window.fullscreen().is_some()
+        {
+            return None;
+        }
+        let scale_factor = window.scale_factor();
+        let (min, max) = self.resize_constraints.get(&id).map_or(
+            (Vec2::ONE, Vec2::splat(f32::INFINITY)),
+            |constraints| {
+                let constraints = constraints.to_physical(scale_factor);
+                let scale_factor = scale_factor as f32;
+                (
+                    Vec2::new(constraints.min_width, constraints.min_height) / scale_factor,
+                    Vec2::new(constraints.max_width, constraints.max_height) / scale_factor,
+                )
+            },
+        );
+        let size = physical_size.to_logical::<f32>(scale_factor);
+        let previous = previous.to_logical::<f32>(scale_factor);
+        let size = Vec2::new(size.width, size.height);
+        let locked = lock.apply(size, Vec2::new(previous.width, previous.height), min, max);
+        // rounding to physical pixels must not resize the window again and again
+        if (locked - size).abs().max_element() < 1.0 {
+            return None;
+        }
+        let locked = LogicalSize::new(locked.x, locked.y);
+        window.set_inner_size(locked);
+        Some(locked.to_physical(scale_factor))
+    }
+
+    /// Updates the outer position and size and the inner position of `window` from its winit
+    /// window.
+    pub fn update_geometry(&self, window: &mut Window) {
+        if let Some(winit_window) = self.get_window(window.id()) {
+            let outer_size = winit_window.outer_size();
+            window.update_geometry_from_backend(
+                winit_window
+                    .outer_position()
+                    .ok()
+                    .map(|position| IVec2::new(position.x, position.y)),
+                winit_window
+                    .inner_position()
+                    .ok()
+                    .map(|position| IVec2::new(position.x, position.y)),
+                UVec2::new(outer_size.width, outer_size.height),
+            );
+        }
+    }
+
+    /// Resizes the canvases of the windows with `fit_canvas_to_parent` to their parent elements.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn fit_canvases_to_parents(&self, windows: &bevy_window::Windows) {
+        use winit::platform::web::WindowExtWebSys;
+
+        for window in windows.iter().filter(|window| window.fit_canvas_to_parent) {
+            let winit_window = match self.get_window(window.id()) {
+                Some(winit_window) => winit_window,
+                None => continue,
+            };
+            let parent = match winit_window.canvas().parent_element() {
+                Some(parent) => parent,
+                None => continue,
+            };
+            let size = winit::dpi::LogicalSize::new(parent.client_width(), parent.client_height())
+                .to_physical::<u32>(winit_window.scale_factor());
+            if winit_window.inner_size() != size {
+                winit_window.set_inner_size(size);
             }
-            default => default,
         }
-    });
+    }
 
-    modes.first().unwrap().clone()
+    /// Restores the titles of the windows whose announcement has been shown long enough.
+    pub(crate) fn restore_announced_titles(
+        &mut self,
+        windows: &bevy_window::Windows,
+        now: Instant,
+    ) {
+        let winit_windows = &self.windows;
+        let window_id_to_winit = &self.window_id_to_winit;
+        self.announced_titles.retain(|id, restore_at| {
+            if now < *restore_at {
+                return true;
+            }
+            let winit_window = window_id_to_winit
+                .get(id)
+                .and_then(|winit_id| winit_windows.get(winit_id));
+            if let (Some(window), Some(winit_window)) = (windows.get(*id), winit_window) {
+                winit_window.set_title(window.title());
+            }
+            false
+        });
+    }
+
+    /// When the title of the next window whose announcement has been shown long enough has to
+    /// be restored.
+    pub(crate) fn next_title_restore(&self) -> Option<Instant> {
+        self.announced_titles.values().min().copied()
+    }
+
+    /// Whether the cursor lock of any window is emulated. Raw mouse motion is not reported
+    /// then, since [locked_cursor_motion](Self::locked_cursor_motion) reports it instead.
+    pub(crate) fn has_emulated_cursor_lock(&self) -> bool {
+        !self.emulated_cursor_locks.is_empty()
+    }
+
+    /// Shows the cursor and releases any grab of the window, without touching the state the
+    /// app requested for it.
+    pub(crate) fn release_cursor(&mut self, id: WindowId) {
+        self.emulated_cursor_locks.remove(&id);
+        if let Some(window) = self.get_window(id) {
+            window.set_cursor_visible(true);
+            if let Err(e) = set_cursor_grab_mode(window, CursorGrabMode::None, false) {
+                warn!("Failed to release the cursor of window {}: {}", id, e);
+            }
+        }
+    }
+
+    /// Applies the cursor state of `window` again after [release_cursor](Self::release_cursor).
+    pub(crate) fn restore_cursor(&mut self, window: &Window) {
+        let id = window.id();
+        let winit_window = if let Some(winit_window) = self.get_window(id) {
+            winit_window
+        } else {
+            return;
+        };
+        winit_window.set_cursor_visible(window.cursor_visible());
+        match set_cursor_grab_mode(
+            winit_window,
+            window.cursor_grab_mode(),
+            self.emulate_cursor_lock,
+        ) {
+            Ok(true) => {
+                self.emulated_cursor_locks
+                    .insert(id, EmulatedCursorLock::default());
+            }
+            Ok(false) => {}
+            Err(e) => warn!("Failed to grab the cursor of window {} again: {}", id, e),
+        }
+    }
+
+    /// If the cursor lock of the window is emulated, moves the cursor that moved to `position`
+    /// back to the center of the window and returns how far it moved, in physical pixels with y
+    /// pointing down like raw mouse motion. The cursor movement itself should not be reported
+    /// then, and the movement back to the center has no motion.
+    pub(crate) fn locked_cursor_motion(
+        &mut self,
+        id: WindowId,
+        position: PhysicalPosition<f64>,
+    ) -> Option<Vec2> {
+        let lock = self.emulated_cursor_locks.get_mut(&id)?;
+        let window = self.windows.get(self.window_id_to_winit.get(&id)?)?;
+        let delta = lock.motion(position);
+        let size = window.inner_size();
+        let center = PhysicalPosition::new(size.width / 2, size.height / 2);
+        let center_f64 = PhysicalPosition::new(center.x as f64, center.y as f64);
+        if lock.needs_warp(center_f64) && window.set_cursor_position(center).is_ok() {
+            lock.warped(center_f64);
+        }
+        Some(delta)
+    }
 }
 
-pub fn get_best_videomode(monitor: &winit::monitor::MonitorHandle) -> winit::monitor::VideoMode {
-    let mut modes = monitor.video_modes().collect::<Vec<_>>();
-    modes.sort_by(|a, b| {
-        use std::cmp::Ordering::*;
-        match b.size().width.cmp(&a.size().width) {
-            Equal => match b.size().height.cmp(&a.size().height) {
-                Equal => b.refresh_rate().cmp(&a.refresh_rate()),
-                default => default,
+impl WindowBackend for WinitWindows {
+    type Target = winit::event_loop::EventLoopWindowTarget<()>;
+
+    fn create_window(
+        &mut self,
+        target: &Self::Target,
+        id: WindowId,
+        descriptor: &WindowDescriptor,
+    ) -> Result<Window, WindowCommandError> {
+        WinitWindows::create_window(self, target, id, descriptor)
+    }
+
+    fn apply_command(
+        &mut self,
+        id: WindowId,
+        command: &WindowCommand,
+    ) -> Result<(), WindowCommandError> {
+        if let WindowCommand::SetResizeConstraints {
+            resize_constraints, ..
+        } = *command
+        {
+            if self.has_window(id) {
+                self.resize_constraints
+                    .insert(id, resize_constraints.check_constraints());
+            }
+        }
+        let window = self
+            .get_window(id)
+            .ok_or(WindowCommandError::UnknownWindow)?;
+        if let WindowCommand::SetCursorGrabMode { mode } = *command {
+            if set_cursor_grab_mode(window, mode, self.emulate_cursor_lock)? {
+                self.emulated_cursor_locks.entry(id).or_default();
+            } else {
+                self.emulated_cursor_locks.remove(&id);
+            }
+            return Ok(());
+        }
+        if let WindowCommand::SetCursorHitTest { enabled } = *command {
+            let layered = self.hit_test_layered.contains(&id);
+            if set_cursor_hit_test(window, enabled, layered)? {
+                self.hit_test_layered.insert(id);
+            } else {
+                self.hit_test_layered.remove(&id);
+            }
+            return Ok(());
+        }
+        if let WindowCommand::SetResizeIncrements { x, y } = *command {
+            let previous = window.inner_size();
+            let lock = self.size_locks.entry(id).or_default();
+            lock.increments = Vec2::new(x, y);
+            if lock.is_unlocked() {
+                self.size_locks.remove(&id);
+            }
+            self.enforce_size_lock(id, previous);
+            return Ok(());
+        }
+        if let WindowCommand::LockAspectRatio { ratio } = *command {
+            let previous = window.inner_size();
+            let lock = self.size_locks.entry(id).or_default();
+            lock.aspect_ratio = ratio;
+            if lock.is_unlocked() {
+                self.size_locks.remove(&id);
+            }
+            self.enforce_size_lock(id, previous);
+            return Ok(());
+        }
+        if let WindowCommand::SetWindowMode { mode, .. } = *command {
+            if mode == WindowMode::BorderlessWindowedMax {
+                return self.maximize_borderless(id);
+            }
+            if mode == WindowMode::Windowed {
+                if let Some(&(position, size)) = self.borderless_max_restores.get(&id) {
+                    window.set_fullscreen(None);
+                    if let Some(position) = position {
+                        window.set_outer_position(position);
+                    }
+                    window.set_inner_size(size);
+                    self.borderless_max_restores.remove(&id);
+                    return Ok(());
+                }
+            }
+        }
+        if let WindowCommand::RefreshGeometry = *command {
+            self.geometry_refreshes.push(id);
+            return Ok(());
+        }
+        if let WindowCommand::SetEventMask { mask } = *command {
+            self.set_event_mask(window.id(), mask);
+            return Ok(());
+        }
+        if let WindowCommand::Announce { ref text, priority } = *command {
+            // winit 0.25 has no accessibility adapter, screen readers read out title changes
+            window.set_title(text);
+            if priority == AnnouncementPriority::Assertive {
+                window.request_user_attention(Some(UserAttentionType::Informational));
+            }
+            self.announced_titles
+                .insert(id, Instant::now() + ANNOUNCEMENT_DURATION);
+            return Ok(());
+        }
+        match *command {
+            WindowCommand::SetWindowMode { mode, resolution } => match mode {
+                WindowMode::BorderlessFullscreen => {
+                    window.set_fullscreen(Some(winit::window::Fullscreen::Borderless(None)))
+                }
+                WindowMode::Fullscreen {
+                    use_size,
+                    monitor,
+                    refresh_rate,
+                    video_mode_index,
+                } => {
+                    let monitor = match monitor {
+                        MonitorSelection::Current => window.current_monitor(),
+                        MonitorSelection::Primary => window.primary_monitor(),
+                        MonitorSelection::Index(index) => window.available_monitors().nth(index),
+                    }
+                    .ok_or(WindowCommandError::MonitorNotFound(monitor))?;
+                    let video_mode = get_fullscreen_videomode(
+                        &monitor,
+                        use_size,
+                        resolution,
+                        refresh_rate,
+                        video_mode_index,
+                    )?;
+                    window.set_fullscreen(Some(winit::window::Fullscreen::Exclusive(video_mode)));
+                }
+                WindowMode::SizedFullscreen => {
+                    let monitor =
+                        window
+                            .current_monitor()
+                            .ok_or(WindowCommandError::MonitorNotFound(
+                                MonitorSelection::Current,
+                            ))?;
+                    let (width, height) = resolution;
+                    let video_mode = get_fitting_videomode(&monitor, width, height, None)?;
+                    window.set_fullscreen(Some(winit::window::Fullscreen::Exclusive(video_mode)));
+                }
+                WindowMode::Windowed => window.set_fullscreen(None),
+                // handled above
+                WindowMode::BorderlessWindowedMax => {}
             },
-            default => default,
+            WindowCommand::SetTitle { ref title } => {
+                window.set_title(title);
+            }
+            WindowCommand::SetResolution {
+                logical_resolution: (width, height),
+                scale_factor,
+            } => {
+                window.set_inner_size(
+                    winit::dpi::LogicalSize::new(width, height).to_physical::<f64>(scale_factor),
+                );
+            }
+            WindowCommand::SetResizable { resizable } => {
+                window.set_resizable(resizable);
+            }
+            WindowCommand::SetDecorations {
+                decorations,
+                preserve_client_area,
+            } => {
+                if preserve_client_area {
+                    set_decorations_preserving_client_area(window, decorations);
+                } else {
+                    window.set_decorations(decorations);
+                }
+            }
+            WindowCommand::SetTransparent { .. } => {
+                // winit 0.25 can only make a window transparent when it is created
+                return Err(WindowCommandError::Unsupported {
+                    command: "SetTransparent",
+                });
+            }
+            WindowCommand::SetCursorVisibility { visible } => {
+                window.set_cursor_visible(visible);
+            }
+            WindowCommand::SetCursorPosition { position } => {
+                let inner_size = window.inner_size().to_logical::<f32>(window.scale_factor());
+                window
+                    .set_cursor_position(converters::convert_to_winit_position(
+                        position,
+                        inner_size.height,
+                        self.coordinate_convention,
+                    ))
+                    .map_err(|e| {
+                        WindowCommandError::Platform(format!(
+                            "Unable to set cursor position: {}",
+                            e
+                        ))
+                    })?;
+            }
+            WindowCommand::SetCursorIcon { icon } => {
+                window.set_cursor_icon(converters::convert_cursor_icon(icon));
+            }
+            WindowCommand::SetMaximized { maximized } => window.set_maximized(maximized),
+            WindowCommand::SetMinimized { minimized } => window.set_minimized(minimized),
+            WindowCommand::SetVisible { visible } => window.set_visible(visible),
+            WindowCommand::SetEnabled { enabled } => set_window_enabled(window, enabled),
+            WindowCommand::SetContentProtected { protected } => {
+                set_content_protected(window, protected)?
+            }
+            WindowCommand::SetPosition { position } => {
+                window.set_outer_position(PhysicalPosition {
+                    x: position[0],
+                    y: position[1],
+                });
+            }
+            WindowCommand::Center { monitor } => {
+                let monitor_handle = match monitor {
+                    MonitorSelection::Current => window.current_monitor(),
+                    MonitorSelection::Primary => window.primary_monitor(),
+                    MonitorSelection::Index(index) => window.available_monitors().nth(index),
+                }
+                .ok_or(WindowCommandError::MonitorNotFound(monitor))?;
+                let size = window.outer_size();
+                window.set_outer_position(centered_position(
+                    &monitor_handle,
+                    PhysicalSize::new(size.width as i32, size.height as i32),
+                ));
+            }
+            WindowCommand::SetResizeConstraints {
+                resize_constraints,
+                scale_factor,
+            } => {
+                let (min_inner_size, max_inner_size) =
+                    resize_limits(&resize_constraints, Some(scale_factor));
+                window.set_min_inner_size(Some(min_inner_size));
+                if max_inner_size.is_some() {
+                    window.set_max_inner_size(max_inner_size);
+                }
+            }
+            WindowCommand::SetImeAllowed { .. } => {
+                // input methods are always enabled for winit 0.25 windows
+                return Err(WindowCommandError::Unsupported {
+                    command: "SetImeAllowed",
+                });
+            }
+            WindowCommand::SetImePosition { position } => {
+                let inner_size = window.inner_size().to_logical::<f32>(window.scale_factor());
+                window.set_ime_position(converters::convert_to_winit_position(
+                    position,
+                    inner_size.height,
+                    self.coordinate_convention,
+                ));
+            }
+            WindowCommand::DragWindow => {
+                window.drag_window().map_err(|e| {
+                    WindowCommandError::Platform(format!("Unable to drag window: {}", e))
+                })?;
+            }
+            WindowCommand::DragResizeWindow { .. } => {
+                // winit 0.25 can only move windows, not resize them interactively
+                return Err(WindowCommandError::Unsupported {
+                    command: "DragResizeWindow",
+                });
+            }
+            WindowCommand::RequestRedraw => window.request_redraw(),
+            WindowCommand::SetScaleFactorOverride { scale_factor } => {
+                set_scale_factor_override(window, scale_factor)
+            }
+            // handled above
+            WindowCommand::SetCursorGrabMode { .. }
+            | WindowCommand::SetCursorHitTest { .. }
+            | WindowCommand::SetEventMask { .. }
+            | WindowCommand::RefreshGeometry
+            | WindowCommand::SetResizeIncrements { .. }
+            | WindowCommand::LockAspectRatio { .. }
+            | WindowCommand::Announce { .. } => {}
+            // handled by `apply_window_commands`
+            WindowCommand::SetScaleFactor { .. }
+            | WindowCommand::SetVsync { .. }
+            | WindowCommand::Close => {}
+        }
+        // the decorations change the outer size, which is not reported by a resize
+        if let WindowCommand::SetDecorations { .. } = *command {
+            self.geometry_refreshes.push(id);
         }
+        // leaving fullscreen restores the size the window had before it, not the one from
+        // before an earlier `BorderlessWindowedMax`
+        if let WindowCommand::SetWindowMode { .. } = *command {
+            self.borderless_max_restores.remove(&id);
+        }
+        Ok(())
+    }
+
+    fn close_window(&mut self, id: WindowId) {
+        self.remove_window(id);
+        if let Some(entity) = self.window_entities.remove(&id) {
+            self.entity_windows.remove(&entity);
+            self.despawned_entities.push(entity);
+        }
+    }
+
+    fn has_window(&self, id: WindowId) -> bool {
+        self.window_id_to_winit.contains_key(&id)
+    }
+}
+
+/// The minimum and, if bounded, the maximum inner size of a window with `constraints`. Logical
+/// constraints are scaled by `scale_factor`, or by winit with the scale factor of the monitor if
+/// it is `None`.
+fn resize_limits(
+    constraints: &WindowResizeConstraints,
+    scale_factor: Option<f64>,
+) -> (Size, Option<Size>) {
+    let constraints = constraints.check_constraints();
+    let bounded = constraints.max_width.is_finite() && constraints.max_height.is_finite();
+    match (constraints.units, scale_factor) {
+        (ResizeConstraintUnits::Logical, None) => (
+            LogicalSize::new(constraints.min_width, constraints.min_height).into(),
+            Some(LogicalSize::new(constraints.max_width, constraints.max_height).into())
+                .filter(|_| bounded),
+        ),
+        (_, scale_factor) => {
+            let constraints = constraints.to_physical(scale_factor.unwrap_or(1.0));
+            (
+                PhysicalSize::new(constraints.min_width, constraints.min_height).into(),
+                Some(PhysicalSize::new(constraints.max_width, constraints.max_height).into())
+                    .filter(|_| bounded),
+            )
+        }
+    }
+}
+
+/// Toggles the decorations of `window`, then restores the size of its client area and moves it
+/// so the client area is where it was before.
+fn set_decorations_preserving_client_area(window: &winit::window::Window, decorations: bool) {
+    let inner_size = window.inner_size();
+    let inner_position = window.inner_position().ok();
+    window.set_decorations(decorations);
+    window.set_inner_size(inner_size);
+    if let (Some(inner_position), Ok(new_inner_position), Ok(new_outer_position)) = (
+        inner_position,
+        window.inner_position(),
+        window.outer_position(),
+    ) {
+        // the offset of the client area in the new frame
+        let offset_x = new_inner_position.x - new_outer_position.x;
+        let offset_y = new_inner_position.y - new_outer_position.y;
+        window.set_outer_position(PhysicalPosition::new(
+            inner_position.x - offset_x,
+            inner_position.y - offset_y,
+        ));
+    }
+}
+
+/// Disables input to a window natively where possible. Elsewhere, the input events of disabled
+/// windows are dropped by the runner.
+#[cfg(target_os = "windows")]
+fn set_window_enabled(window: &winit::window::Window, enabled: bool) {
+    use winapi::{shared::windef::HWND, um::winuser};
+    use winit::platform::windows::WindowExtWindows;
+
+    unsafe {
+        winuser::EnableWindow(window.hwnd() as HWND, enabled as i32);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_window_enabled(_window: &winit::window::Window, _enabled: bool) {}
+
+/// UIKit renders at the scale factor of the view, so an override changes the resolution of the
+/// surface instead of only scaling the logical size.
+#[cfg(target_os = "ios")]
+fn set_scale_factor_override(window: &winit::window::Window, scale_factor: Option<f64>) {
+    use winit::platform::ios::WindowExtIOS;
+
+    let scale_factor = scale_factor.or_else(|| {
+        window
+            .current_monitor()
+            .map(|monitor| monitor.scale_factor())
     });
+    if let Some(scale_factor) = scale_factor {
+        window.set_scale_factor(scale_factor);
+    }
+}
+
+#[cfg(not(target_os = "ios"))]
+fn set_scale_factor_override(_window: &winit::window::Window, _scale_factor: Option<f64>) {}
+
+/// Excludes a window from screen capture, which winit 0.25 has no API for.
+#[cfg(target_os = "windows")]
+fn set_content_protected(
+    window: &winit::window::Window,
+    protected: bool,
+) -> Result<(), WindowCommandError> {
+    use winapi::{shared::windef::HWND, um::winuser};
+    use winit::platform::windows::WindowExtWindows;
+
+    let affinity = if protected {
+        winuser::WDA_MONITOR
+    } else {
+        winuser::WDA_NONE
+    };
+    if unsafe { winuser::SetWindowDisplayAffinity(window.hwnd() as HWND, affinity) } == 0 {
+        return Err(WindowCommandError::Platform(
+            "Unable to set the display affinity of the window".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Excludes a window from screen capture, which winit 0.25 has no API for.
+#[cfg(not(target_os = "windows"))]
+fn set_content_protected(
+    _window: &winit::window::Window,
+    _protected: bool,
+) -> Result<(), WindowCommandError> {
+    Err(WindowCommandError::Unsupported {
+        command: "SetContentProtected",
+    })
+}
+
+/// winit 0.25 has no `set_cursor_hittest`, so this sets the extended styles it uses in later
+/// versions: a transparent window is skipped by hit testing, which needs it to be layered.
+/// `layered` tells whether the window was only made layered by disabling its hit test before,
+/// and the returned value whether it is now, so a window that is layered for another reason
+/// stays layered.
+#[cfg(target_os = "windows")]
+fn set_cursor_hit_test(
+    window: &winit::window::Window,
+    enabled: bool,
+    layered: bool,
+) -> Result<bool, WindowCommandError> {
+    use winapi::{shared::windef::HWND, um::winuser};
+    use winit::platform::windows::WindowExtWindows;
+
+    let hwnd = window.hwnd() as HWND;
+    let transparent = winuser::WS_EX_TRANSPARENT as i32;
+    let layered_style = winuser::WS_EX_LAYERED as i32;
+    unsafe {
+        let style = winuser::GetWindowLongW(hwnd, winuser::GWL_EXSTYLE);
+        let (style, layered) = if enabled {
+            let removed = if layered {
+                transparent | layered_style
+            } else {
+                transparent
+            };
+            (style & !removed, false)
+        } else {
+            let layered = layered || style & layered_style == 0;
+            (style | transparent | layered_style, layered)
+        };
+        winuser::SetWindowLongW(hwnd, winuser::GWL_EXSTYLE, style);
+        Ok(layered)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_cursor_hit_test(
+    _window: &winit::window::Window,
+    _enabled: bool,
+    _layered: bool,
+) -> Result<bool, WindowCommandError> {
+    Err(WindowCommandError::Unsupported {
+        command: "SetCursorHitTest",
+    })
+}
+
+/// Applies a background effect through the accent policy of the window, which is what the
+/// Windows shell uses for its own blurred surfaces.
+#[cfg(target_os = "windows")]
+fn set_background_effect(
+    window: &winit::window::Window,
+    effect: BackgroundEffect,
+) -> Result<(), WindowCommandError> {
+    use std::{ffi::c_void, mem};
+    use winapi::{
+        shared::{minwindef::BOOL, windef::HWND},
+        um::libloaderapi,
+    };
+    use winit::platform::windows::WindowExtWindows;
+
+    #[repr(C)]
+    struct AccentPolicy {
+        accent_state: u32,
+        accent_flags: u32,
+        gradient_color: u32,
+        animation_id: u32,
+    }
+
+    #[repr(C)]
+    struct WindowCompositionAttribData {
+        attrib: u32,
+        data: *mut c_void,
+        size: usize,
+    }
+
+    type SetWindowCompositionAttribute =
+        unsafe extern "system" fn(HWND, *mut WindowCompositionAttribData) -> BOOL;
+
+    const WCA_ACCENT_POLICY: u32 = 19;
+    const ACCENT_DISABLED: u32 = 0;
+    const ACCENT_ENABLE_BLURBEHIND: u32 = 3;
+    const ACCENT_ENABLE_ACRYLICBLURBEHIND: u32 = 4;
+
+    let (accent_state, gradient_color) = match effect {
+        BackgroundEffect::None => (ACCENT_DISABLED, 0),
+        BackgroundEffect::Blur => (ACCENT_ENABLE_BLURBEHIND, 0),
+        // the gradient color is laid out as 0xAABBGGRR
+        BackgroundEffect::Acrylic { tint } => {
+            (ACCENT_ENABLE_ACRYLICBLURBEHIND, u32::from_le_bytes(tint))
+        }
+    };
+
+    unsafe {
+        // undocumented, so it is looked up at runtime instead of linked
+        let user32 = libloaderapi::GetModuleHandleA(b"user32.dll\0".as_ptr() as _);
+        if user32.is_null() {
+            return Err(WindowCommandError::Unsupported {
+                command: "BackgroundEffect",
+            });
+        }
+        let function =
+            libloaderapi::GetProcAddress(user32, b"SetWindowCompositionAttribute\0".as_ptr() as _);
+        if function.is_null() {
+            return Err(WindowCommandError::Unsupported {
+                command: "BackgroundEffect",
+            });
+        }
+        let set_window_composition_attribute: SetWindowCompositionAttribute =
+            mem::transmute(function);
+
+        let mut policy = AccentPolicy {
+            accent_state,
+            accent_flags: 0,
+            gradient_color,
+            animation_id: 0,
+        };
+        let mut data = WindowCompositionAttribData {
+            attrib: WCA_ACCENT_POLICY,
+            data: &mut policy as *mut AccentPolicy as *mut c_void,
+            size: mem::size_of::<AccentPolicy>(),
+        };
+        if set_window_composition_attribute(window.hwnd() as HWND, &mut data) == 0 {
+            return Err(WindowCommandError::Platform(
+                "Unable to set the accent policy of the window".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_background_effect(
+    _window: &winit::window::Window,
+    _effect: BackgroundEffect,
+) -> Result<(), WindowCommandError> {
+    Err(WindowCommandError::Unsupported {
+        command: "BackgroundEffect",
+    })
+}
+
+/// The theme the platform applied to a new window, where winit can report it.
+#[cfg(target_os = "windows")]
+fn window_theme(window: &winit::window::Window) -> Option<WindowTheme> {
+    use winit::platform::windows::WindowExtWindows;
+
+    Some(crate::converters::convert_theme(window.theme()))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn window_theme(_window: &winit::window::Window) -> Option<WindowTheme> {
+    None
+}
+
+/// Removes the taskbar entry of a window that has not been shown yet, by turning it into a
+/// tool window.
+#[cfg(target_os = "windows")]
+fn hide_from_taskbar(window: &winit::window::Window) {
+    use winapi::{shared::windef::HWND, um::winuser};
+    use winit::platform::windows::WindowExtWindows;
+
+    unsafe {
+        let hwnd = window.hwnd() as HWND;
+        let style = winuser::GetWindowLongW(hwnd, winuser::GWL_EXSTYLE) as u32;
+        let style = (style & !winuser::WS_EX_APPWINDOW) | winuser::WS_EX_TOOLWINDOW;
+        winuser::SetWindowLongW(hwnd, winuser::GWL_EXSTYLE, style as i32);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn hide_from_taskbar(_window: &winit::window::Window) {}
+
+/// Shows a window that was created invisible without activating it.
+#[cfg(target_os = "windows")]
+fn show_window_inactive(window: &winit::window::Window) {
+    use winapi::{shared::windef::HWND, um::winuser};
+    use winit::platform::windows::WindowExtWindows;
+
+    unsafe {
+        winuser::ShowWindow(window.hwnd() as HWND, winuser::SW_SHOWNOACTIVATE);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn show_window_inactive(_window: &winit::window::Window) {}
+
+/// The mode that `set_cursor_grab(true)` results in on this platform with winit 0.25.
+const NATIVE_CURSOR_GRAB_MODE: CursorGrabMode =
+    if cfg!(any(target_os = "macos", target_arch = "wasm32")) {
+        CursorGrabMode::Locked
+    } else {
+        CursorGrabMode::Confined
+    };
+
+/// Applies `mode` to `window`. Returns `true` if the lock has to be emulated by recentering the
+/// cursor, because the platform can only confine it. Without `emulate_lock`, such a cursor is
+/// only confined.
+fn set_cursor_grab_mode(
+    window: &winit::window::Window,
+    mode: CursorGrabMode,
+    emulate_lock: bool,
+) -> Result<bool, WindowCommandError> {
+    let (grab, emulate_lock) = match mode {
+        CursorGrabMode::None => (false, false),
+        mode if mode == NATIVE_CURSOR_GRAB_MODE => (true, false),
+        CursorGrabMode::Locked => {
+            if !emulate_lock {
+                warn!(
+                    "The cursor can only be confined on this platform, enable \
+                    `WinitConfig::emulate_cursor_lock` to lock it"
+                );
+            }
+            (true, emulate_lock)
+        }
+        CursorGrabMode::Confined => {
+            return Err(WindowCommandError::Unsupported {
+                command: "SetCursorGrabMode",
+            })
+        }
+    };
+    match window.set_cursor_grab(grab) {
+        Ok(()) => {}
+        Err(winit::error::ExternalError::NotSupported(_)) => {
+            return Err(WindowCommandError::Unsupported {
+                command: "SetCursorGrabMode",
+            })
+        }
+        Err(e) => {
+            return Err(WindowCommandError::Platform(format!(
+                "Unable to un/grab cursor: {}",
+                e
+            )))
+        }
+    }
+    if emulate_lock {
+        let size = window.inner_size();
+        let _ = window.set_cursor_position(PhysicalPosition::new(size.width / 2, size.height / 2));
+    }
+    Ok(emulate_lock)
+}
+
+fn get_window_position(
+    event_loop: &winit::event_loop::EventLoopWindowTarget<()>,
+    window_descriptor: &WindowDescriptor,
+) -> Option<PhysicalPosition<i32>> {
+    match window_descriptor.position {
+        WindowPosition::Automatic => None,
+        WindowPosition::At(position) => Some(PhysicalPosition::new(position.x, position.y)),
+        WindowPosition::Centered(selection) => {
+            let monitor = match selection {
+                MonitorSelection::Current | MonitorSelection::Primary => {
+                    event_loop.primary_monitor()
+                }
+                MonitorSelection::Index(index) => event_loop.available_monitors().nth(index),
+            }?;
+            let scale_factor = window_descriptor
+                .scale_factor_override
+                .unwrap_or_else(|| monitor.scale_factor());
+            let window_size = LogicalSize::new(window_descriptor.width, window_descriptor.height)
+                .to_physical::<i32>(scale_factor);
+            Some(centered_position(&monitor, window_size))
+        }
+    }
+}
+
+/// The position that centers a window of `window_size` in the work area of `monitor`.
+fn centered_position(
+    monitor: &MonitorHandle,
+    window_size: PhysicalSize<i32>,
+) -> PhysicalPosition<i32> {
+    let (area_position, area_size) = work_area(monitor);
+    PhysicalPosition::new(
+        area_position.x + (area_size.width as i32 - window_size.width) / 2,
+        area_position.y + (area_size.height as i32 - window_size.height) / 2,
+    )
+}
+
+/// The part of `monitor` that is not covered by the taskbar.
+#[cfg(target_os = "windows")]
+fn work_area(monitor: &MonitorHandle) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+    use winapi::um::winuser;
+    use winit::platform::windows::MonitorHandleExtWindows;
+
+    let mut info: winuser::MONITORINFO = unsafe { std::mem::zeroed() };
+    info.cbSize = std::mem::size_of::<winuser::MONITORINFO>() as u32;
+    if unsafe { winuser::GetMonitorInfoW(monitor.hmonitor() as _, &mut info) } == 0 {
+        return (monitor.position(), monitor.size());
+    }
+    let work = info.rcWork;
+    (
+        PhysicalPosition::new(work.left, work.top),
+        PhysicalSize::new(
+            (work.right - work.left) as u32,
+            (work.bottom - work.top) as u32,
+        ),
+    )
+}
+
+/// The whole monitor, winit 0.25 does not know the work area on this platform.
+#[cfg(not(target_os = "windows"))]
+fn work_area(monitor: &MonitorHandle) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+    (monitor.position(), monitor.size())
+}
+
+fn get_fullscreen_videomode(
+    monitor: &MonitorHandle,
+    use_size: bool,
+    (width, height): (u32, u32),
+    refresh_rate: Option<u16>,
+    video_mode_index: Option<usize>,
+) -> Result<VideoMode, WindowCommandError> {
+    if let Some(index) = video_mode_index {
+        return monitor
+            .video_modes()
+            .nth(index)
+            .ok_or(WindowCommandError::VideoModeNotFound { index });
+    }
+    match use_size {
+        true => get_fitting_videomode(monitor, width, height, refresh_rate),
+        false => get_best_videomode(monitor, refresh_rate),
+    }
+}
+
+/// The size of a window created from `window_descriptor` on `monitor`, in physical pixels as
+/// the video modes are.
+fn physical_size(window_descriptor: &WindowDescriptor, monitor: &MonitorHandle) -> (u32, u32) {
+    let scale_factor = window_descriptor
+        .scale_factor_override
+        .unwrap_or_else(|| monitor.scale_factor());
+    let size = LogicalSize::new(window_descriptor.width, window_descriptor.height)
+        .to_physical::<u32>(scale_factor);
+    (size.width, size.height)
+}
+
+/// Orders video modes by how close their refresh rate is to `preferred_refresh_rate`, or by
+/// the highest refresh rate without a preference, and then by the highest bit depth.
+fn cmp_refresh_rate(
+    a: &VideoMode,
+    b: &VideoMode,
+    preferred_refresh_rate: Option<u16>,
+) -> std::cmp::Ordering {
+    let distance = |mode: &VideoMode| {
+        preferred_refresh_rate.map_or(0, |preferred| {
+            (i32::from(mode.refresh_rate()) - i32::from(preferred)).abs()
+        })
+    };
+    distance(a)
+        .cmp(&distance(b))
+        .then_with(|| b.refresh_rate().cmp(&a.refresh_rate()))
+        .then_with(|| b.bit_depth().cmp(&a.bit_depth()))
+}
+
+/// The video mode of `monitor` closest to `width`x`height`, in physical pixels. Among equally
+/// close modes, the refresh rate closest to `preferred_refresh_rate` wins. Fails only if the
+/// monitor reports no video modes.
+pub fn get_fitting_videomode(
+    monitor: &MonitorHandle,
+    width: u32,
+    height: u32,
+    preferred_refresh_rate: Option<u16>,
+) -> Result<VideoMode, WindowCommandError> {
+    fn abs_diff(a: u32, b: u32) -> u32 {
+        if a > b {
+            return a - b;
+        }
+        b - a
+    }
+
+    monitor
+        .video_modes()
+        .min_by(|a, b| {
+            abs_diff(a.size().width, width)
+                .cmp(&abs_diff(b.size().width, width))
+                .then_with(|| {
+                    abs_diff(a.size().height, height).cmp(&abs_diff(b.size().height, height))
+                })
+                .then_with(|| cmp_refresh_rate(a, b, preferred_refresh_rate))
+        })
+        .ok_or(WindowCommandError::NoMatchingVideoMode)
+}
 
-    modes.first().unwrap().clone()
+/// The largest video mode of `monitor`. Among modes of the same size, the refresh rate closest
+/// to `preferred_refresh_rate` wins. Fails only if the monitor reports no video modes.
+pub fn get_best_videomode(
+    monitor: &MonitorHandle,
+    preferred_refresh_rate: Option<u16>,
+) -> Result<VideoMode, WindowCommandError> {
+    monitor
+        .video_modes()
+        .min_by(|a, b| {
+            b.size()
+                .width
+                .cmp(&a.size().width)
+                .then_with(|| b.size().height.cmp(&a.size().height))
+                .then_with(|| cmp_refresh_rate(a, b, preferred_refresh_rate))
+        })
+        .ok_or(WindowCommandError::NoMatchingVideoMode)
 }
 
 // WARNING: this only works under the assumption that wasm runtime is single threaded
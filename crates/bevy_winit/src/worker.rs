@@ -0,0 +1,113 @@
+use crate::WinitEvent;
+use std::sync::mpsc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+use winit::event_loop::EventLoopProxy;
+
+type Job = Box<dyn FnOnce() -> Option<WinitEvent> + Send>;
+
+/// Runs jobs of the event loop on a thread of their own, for work like starting subprocesses
+/// that would block the event loop for as long as it runs. The events of the jobs are taken by
+/// the event loop, which the worker wakes up for them.
+pub(crate) struct Worker {
+    job_sender: Option<mpsc::Sender<Job>>,
+    result_sender: mpsc::Sender<WinitEvent>,
+    result_receiver: mpsc::Receiver<WinitEvent>,
+}
+
+impl Worker {
+    /// Starts a worker thread called `name`, which wakes up the event loop with
+    /// `event_loop_proxy` whenever a job resulted in an event. Where there are no threads, like
+    /// on the web, the jobs run right away.
+    pub fn new(name: &str, event_loop_proxy: Option<EventLoopProxy<()>>) -> Self {
+        let (result_sender, result_receiver) = mpsc::channel();
+        #[cfg(not(target_arch = "wasm32"))]
+        let job_sender = Self::spawn(name, result_sender.clone(), event_loop_proxy);
+        #[cfg(target_arch = "wasm32")]
+        let job_sender = {
+            let _ = (name, event_loop_proxy);
+            None
+        };
+        Worker {
+            job_sender,
+            result_sender,
+            result_receiver,
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spawn(
+        name: &str,
+        result_sender: mpsc::Sender<WinitEvent>,
+        event_loop_proxy: Option<EventLoopProxy<()>>,
+    ) -> Option<mpsc::Sender<Job>> {
+        let (job_sender, jobs) = mpsc::channel::<Job>();
+        thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                for job in jobs {
+                    let e = match job() {
+                        Some(e) => e,
+                        None => continue,
+                    };
+                    if result_sender.send(e).is_err() {
+                        return;
+                    }
+                    if let Some(proxy) = &event_loop_proxy {
+                        let _ = proxy.send_event(());
+                    }
+                }
+            })
+            .ok()?;
+        Some(job_sender)
+    }
+
+    /// Runs `job` on the worker, its event is [taken](Self::take_results) once it is done.
+    pub fn run(&self, job: impl FnOnce() -> Option<WinitEvent> + Send + 'static) {
+        match &self.job_sender {
+            // fails only if a job panicked and took the worker with it
+            Some(job_sender) => {
+                let _ = job_sender.send(Box::new(job));
+            }
+            None => {
+                if let Some(e) = job() {
+                    let _ = self.result_sender.send(e);
+                }
+            }
+        }
+    }
+
+    /// Pushes the events of the jobs that are done to `out`.
+    pub fn take_results(&self, out: &mut Vec<WinitEvent>) {
+        out.extend(self.result_receiver.try_iter());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_utils::{Duration, Instant};
+    use bevy_window::SystemPreferences;
+
+    #[test]
+    fn runs_jobs_on_its_thread() {
+        let worker = Worker::new("test worker", None);
+        let caller = thread::current().id();
+        worker.run(move || {
+            assert_ne!(thread::current().id(), caller);
+            None
+        });
+        worker.run(|| Some(WinitEvent::SystemPreferences(SystemPreferences::default())));
+
+        let mut results = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while results.is_empty() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+            worker.take_results(&mut results);
+        }
+        assert!(matches!(
+            results.as_slice(),
+            [WinitEvent::SystemPreferences(_)]
+        ));
+    }
+}
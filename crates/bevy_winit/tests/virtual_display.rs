@@ -0,0 +1,130 @@
+//! Runs the threaded runner against a real display server, to catch regressions in the logic
+//! between the winit thread and the app that unit tests cannot reach. Needs the
+//! `virtual_display_tests` feature and a display, e.g. from Xvfb:
+//!
+//! ```sh
+//! xvfb-run cargo test -p bevy_winit --features "x11 virtual_display_tests" --test virtual_display
+//! ```
+//!
+//! Winit supports only one event loop per process, so everything runs in a single test.
+#![cfg(feature = "virtual_display_tests")]
+
+use bevy_app::{App, AppExit, EventReader, EventWriter};
+use bevy_ecs::system::{IntoSystem, Local, Res, ResMut};
+use bevy_input::InputPlugin;
+use bevy_math::Vec2;
+use bevy_utils::{Duration, Instant};
+use bevy_window::{CursorMoved, WindowClosed, WindowCreated, WindowPlugin, WindowResized, Windows};
+use bevy_winit::{WinitConfig, WinitPlugin};
+use std::sync::{Arc, Mutex};
+
+/// How long the display server may take to answer before the test fails.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The steps of the script in the order they are reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Created,
+    Resized,
+    CursorMoved,
+    Closed,
+    TimedOut,
+}
+
+#[derive(Default, Clone)]
+struct Reached(Arc<Mutex<Vec<Step>>>);
+
+impl Reached {
+    fn push(&self, step: Step) {
+        self.0.lock().unwrap().push(step);
+    }
+
+    fn last(&self) -> Option<Step> {
+        self.0.lock().unwrap().last().copied()
+    }
+}
+
+/// Drives the primary window through the script: each step requests a change from the display
+/// server, and the next one waits for the event it produces.
+#[allow(clippy::too_many_arguments)]
+fn run_script(
+    reached: Res<Reached>,
+    mut started_at: Local<Option<Instant>>,
+    mut windows: ResMut<Windows>,
+    mut created_events: EventReader<WindowCreated>,
+    mut resized_events: EventReader<WindowResized>,
+    mut cursor_moved_events: EventReader<CursorMoved>,
+    mut closed_events: EventReader<WindowClosed>,
+    mut app_exit_events: EventWriter<AppExit>,
+) {
+    let started_at = *started_at.get_or_insert_with(Instant::now);
+    if started_at.elapsed() > TIMEOUT {
+        reached.push(Step::TimedOut);
+        app_exit_events.send(AppExit);
+        return;
+    }
+
+    match reached.last() {
+        None => {
+            if created_events.iter().next().is_some() {
+                reached.push(Step::Created);
+                windows
+                    .get_primary_mut()
+                    .unwrap()
+                    .set_resolution(320.0, 240.0);
+            }
+        }
+        Some(Step::Created) => {
+            #[allow(clippy::float_cmp)]
+            if resized_events
+                .iter()
+                .any(|event| (event.width, event.height) == (320.0, 240.0))
+            {
+                reached.push(Step::Resized);
+                windows
+                    .get_primary_mut()
+                    .unwrap()
+                    .set_cursor_position(Vec2::new(100.0, 100.0));
+            }
+        }
+        Some(Step::Resized) => {
+            if cursor_moved_events.iter().next().is_some() {
+                reached.push(Step::CursorMoved);
+                windows.get_primary_mut().unwrap().close();
+            }
+        }
+        Some(Step::CursorMoved) => {
+            if closed_events.iter().next().is_some() {
+                reached.push(Step::Closed);
+                app_exit_events.send(AppExit);
+            }
+        }
+        Some(Step::Closed) | Some(Step::TimedOut) => {}
+    }
+}
+
+#[test]
+fn threaded_runner_round_trip() {
+    let reached = Reached::default();
+    App::build()
+        .insert_resource(WinitConfig {
+            return_from_run: true,
+            ..Default::default()
+        })
+        .insert_resource(reached.clone())
+        .add_plugin(InputPlugin)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(WinitPlugin)
+        .add_system(run_script.system())
+        .run();
+
+    assert_eq!(
+        *reached.0.lock().unwrap(),
+        [
+            Step::Created,
+            Step::Resized,
+            Step::CursorMoved,
+            Step::Closed
+        ]
+    );
+}